@@ -0,0 +1,265 @@
+//! Lists and cleans up the containers, networks and volumes of testcontainers sessions, e.g. the
+//! ones left behind by test runs that crashed, see [`testcontainers::cleanup`].
+
+use std::{
+    process::ExitCode,
+    time::{Duration, SystemTime},
+};
+
+use testcontainers::{
+    cleanup::{self, Removed, Session, SessionContainer},
+    core::TestcontainersError,
+};
+
+const USAGE: &str = "\
+Usage: testcontainers-cli <command>
+
+Commands:
+  list                            List the sessions and their containers, networks and volumes
+  clean --all                     Remove the resources of all other sessions
+  clean --older-than <duration>   Remove the resources of the sessions that started more than
+                                  <duration> ago, e.g. 30m, 1h or 2d
+  clean <session>                 Remove the resources of the session
+  logs <session>                  Print the logs of the containers of the session
+";
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Help,
+    List,
+    Clean { older_than: Duration },
+    CleanSession(String),
+    Logs(String),
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let command = match parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    run(command).await.unwrap_or_else(|err| {
+        eprintln!("error: {err}");
+        ExitCode::FAILURE
+    })
+}
+
+async fn run(command: Command) -> Result<ExitCode, TestcontainersError> {
+    match command {
+        Command::Help => {
+            print!("{USAGE}");
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::List => cleanup::sessions().await.map(list),
+        Command::Clean { older_than } => cleanup::remove_stale(older_than).await.map(report),
+        Command::CleanSession(session_id) => cleanup::remove_session(&session_id).await.map(report),
+        Command::Logs(session_id) => cleanup::session_logs(&session_id)
+            .await
+            .map(|logs| print_logs(&session_id, logs)),
+    }
+}
+
+fn parse(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut args = args.into_iter();
+    let command = match args.next().as_deref() {
+        None | Some("help" | "-h" | "--help") => Command::Help,
+        Some("list") => Command::List,
+        Some("clean") => match args
+            .next()
+            .ok_or("`clean` requires `--older-than <duration>`, `--all` or a session")?
+            .as_str()
+        {
+            "--all" => Command::Clean {
+                older_than: Duration::ZERO,
+            },
+            "--older-than" => {
+                let duration = args
+                    .next()
+                    .ok_or("`--older-than` requires a duration, e.g. 1h")?;
+                Command::Clean {
+                    older_than: parse_duration(&duration)?,
+                }
+            }
+            // a misspelled option would otherwise be taken for a session that doesn't exist
+            option if option.starts_with('-') => {
+                return Err(format!("unknown option `{option}` for `clean`"))
+            }
+            session_id => Command::CleanSession(session_id.to_string()),
+        },
+        Some("logs") => Command::Logs(args.next().ok_or("`logs` requires a session")?),
+        Some(command) => return Err(format!("unknown command `{command}`")),
+    };
+
+    match args.next() {
+        Some(arg) => Err(format!("unexpected argument `{arg}`")),
+        None => Ok(command),
+    }
+}
+
+/// Parses a duration like `90s`, `30m`, `1h` or `2d`.
+fn parse_duration(duration: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid duration `{duration}`, expected e.g. 30m, 1h or 2d");
+
+    let split = duration
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(invalid)?;
+    let (amount, unit) = duration.split_at(split);
+    let amount: u64 = amount.parse().map_err(|_| invalid())?;
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+    Ok(Duration::from_secs(amount.saturating_mul(seconds)))
+}
+
+fn list(sessions: Vec<Session>) -> ExitCode {
+    if sessions.is_empty() {
+        println!("no sessions");
+        return ExitCode::SUCCESS;
+    }
+
+    for session in sessions {
+        let age = session
+            .started_at
+            .and_then(|started_at| SystemTime::now().duration_since(started_at).ok())
+            .map(|age| format!("started {} ago", format_age(age)))
+            .unwrap_or_else(|| "started at an unknown time".to_string());
+        println!("{} ({age})", session.id);
+        for container in session.containers {
+            println!(
+                "  container {} {} {} ({})",
+                short_id(&container.id),
+                container.name,
+                container.image,
+                container.state
+            );
+        }
+        for network in session.networks {
+            println!("  network {network}");
+        }
+        for volume in session.volumes {
+            println!("  volume {volume}");
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn report(removed: Removed) -> ExitCode {
+    println!(
+        "removed {} containers, {} networks and {} volumes",
+        removed.containers.len(),
+        removed.networks.len(),
+        removed.volumes.len()
+    );
+    ExitCode::SUCCESS
+}
+
+fn print_logs(session_id: &str, logs: Vec<(SessionContainer, String)>) -> ExitCode {
+    if logs.is_empty() {
+        eprintln!("session {session_id} has no containers");
+        return ExitCode::FAILURE;
+    }
+    for (container, logs) in logs {
+        println!("==> {} ({}) <==", container.name, container.image);
+        print!("{logs}");
+    }
+    ExitCode::SUCCESS
+}
+
+/// Formats the age with its two largest units, e.g. `2h 5m`.
+fn format_age(age: Duration) -> String {
+    let seconds = age.as_secs();
+    let (days, hours, minutes) = (seconds / 86400, seconds / 3600 % 24, seconds / 60 % 60);
+    match (days, hours, minutes) {
+        (0, 0, 0) => format!("{seconds}s"),
+        (0, 0, _) => format!("{minutes}m {}s", seconds % 60),
+        (0, _, _) => format!("{hours}h {minutes}m"),
+        _ => format!("{days}d {hours}h"),
+    }
+}
+
+/// Returns the id in the short form of `docker ps`.
+fn short_id(id: &str) -> &str {
+    id.get(..12).unwrap_or(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> Result<Command, String> {
+        parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!(parse_args(&[]), Ok(Command::Help));
+        assert_eq!(parse_args(&["list"]), Ok(Command::List));
+        assert_eq!(
+            parse_args(&["clean", "--all"]),
+            Ok(Command::Clean {
+                older_than: Duration::ZERO
+            })
+        );
+        assert_eq!(
+            parse_args(&["clean", "--older-than", "1h"]),
+            Ok(Command::Clean {
+                older_than: Duration::from_secs(3600)
+            })
+        );
+        assert_eq!(
+            parse_args(&["clean", "01hx5e2k7zq0c6t1m7hy0qj3sw"]),
+            Ok(Command::CleanSession(
+                "01hx5e2k7zq0c6t1m7hy0qj3sw".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_args(&["logs", "01hx5e2k7zq0c6t1m7hy0qj3sw"]),
+            Ok(Command::Logs("01hx5e2k7zq0c6t1m7hy0qj3sw".to_string()))
+        );
+
+        assert!(parse_args(&["clean"]).is_err());
+        assert!(parse_args(&["clean", "--older", "1h"]).is_err());
+        assert!(parse_args(&["clean", "--olderthan"]).is_err());
+        assert!(parse_args(&["logs"]).is_err());
+        assert!(parse_args(&["list", "--all"]).is_err());
+        assert!(parse_args(&["prune"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn reports_unreachable_daemon_as_error() {
+        // nothing listens there, and this is the only test that talks to the daemon
+        std::env::set_var("DOCKER_HOST", "tcp://127.0.0.1:1");
+
+        let err = run(Command::List).await.unwrap_err();
+        assert!(
+            matches!(err, TestcontainersError::DaemonUnreachable { .. }),
+            "expected an unreachable daemon, got: {err}"
+        );
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("30m"), Ok(Duration::from_secs(30 * 60)));
+        assert_eq!(parse_duration("2d"), Ok(Duration::from_secs(2 * 86400)));
+        assert!(parse_duration("1").is_err());
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("1w").is_err());
+    }
+
+    #[test]
+    fn formats_ages() {
+        assert_eq!(format_age(Duration::from_secs(42)), "42s");
+        assert_eq!(format_age(Duration::from_secs(125)), "2m 5s");
+        assert_eq!(format_age(Duration::from_secs(7500)), "2h 5m");
+        assert_eq!(format_age(Duration::from_secs(90000)), "1d 1h");
+    }
+}
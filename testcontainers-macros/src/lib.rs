@@ -0,0 +1,192 @@
+//! Attribute macros for [`testcontainers`], re-exported by it under the `macros` feature.
+//!
+//! [`testcontainers`]: https://docs.rs/testcontainers
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::{
+    parse_macro_input, punctuated::Punctuated, Attribute, Expr, FnArg, GenericArgument, ItemFn,
+    Pat, PathArguments, Type,
+};
+
+/// Marks a test that gets its containers as parameters.
+///
+/// Every parameter has to be a `ContainerAsync<I>` in an `async` test, which runs as a
+/// `#[tokio::test]`, or a `Container<I>` in a synchronous test, which requires the `blocking`
+/// feature. The containers are started before the test, concurrently in an `async` test, from
+/// `I::default()` or the image given with `#[image(...)]`, and are removed once the test is
+/// done. The arguments of the attribute are passed on to `#[tokio::test]`. The test doesn't
+/// need to depend on tokio, which is used through `testcontainers`.
+///
+/// ```rust,ignore
+/// #[testcontainers::test(flavor = "multi_thread")]
+/// async fn a_test(
+///     postgres: ContainerAsync<Postgres>,
+///     #[image(GenericImage::new("redis", "7.2.4").with_exposed_port(6379))]
+///     redis: ContainerAsync<GenericImage>,
+/// ) {
+///     let port = postgres.get_host_port_ipv4(5432).await;
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn test(args: TokenStream, item: TokenStream) -> TokenStream {
+    let function = parse_macro_input!(item as ItemFn);
+
+    expand(args.into(), function)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// A parameter of the test, which is bound to a started container.
+struct Parameter {
+    name: String,
+    pattern: TokenStream2,
+    ty: Type,
+    image_type: Type,
+    image: Expr,
+}
+
+fn expand(args: TokenStream2, mut function: ItemFn) -> syn::Result<TokenStream2> {
+    let is_async = function.sig.asyncness.is_some();
+    let container = if is_async {
+        "ContainerAsync"
+    } else {
+        "Container"
+    };
+
+    let parameters = std::mem::replace(&mut function.sig.inputs, Punctuated::new())
+        .into_iter()
+        .map(|input| parameter(input, container))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let starts = parameters.iter().map(|parameter| {
+        let Parameter {
+            name,
+            image_type,
+            image,
+            ..
+        } = parameter;
+        if is_async {
+            quote! {
+                async {
+                    <_ as ::testcontainers::runners::AsyncRunner<#image_type>>::start(#image)
+                        .await
+                        .unwrap_or_else(|err| panic!("failed to start the container of `{}`: {err}", #name))
+                }
+            }
+        } else {
+            quote! {
+                <_ as ::testcontainers::runners::SyncRunner<#image_type>>::start(#image)
+                    .unwrap_or_else(|err| panic!("failed to start the container of `{}`: {err}", #name))
+            }
+        }
+    });
+    let patterns = parameters.iter().map(|parameter| &parameter.pattern);
+    let types = parameters.iter().map(|parameter| &parameter.ty);
+
+    let setup = if parameters.is_empty() {
+        quote! {}
+    } else if is_async {
+        quote! {
+            let (#(#patterns,)*): (#(#types,)*) = ::testcontainers::__tokio::join!(#(#starts),*);
+        }
+    } else {
+        quote! {
+            #(let #patterns: #types = #starts;)*
+        }
+    };
+
+    // tokio is used through testcontainers, so the test doesn't need to depend on it
+    let test_attribute = if is_async && args.is_empty() {
+        quote! { #[::testcontainers::__tokio::test(crate = "::testcontainers::__tokio")] }
+    } else if is_async {
+        quote! { #[::testcontainers::__tokio::test(crate = "::testcontainers::__tokio", #args)] }
+    } else if args.is_empty() {
+        quote! { #[::core::prelude::v1::test] }
+    } else {
+        return Err(syn::Error::new_spanned(
+            args,
+            "arguments are only supported for async tests",
+        ));
+    };
+
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = function;
+    Ok(quote! {
+        #(#attrs)*
+        #test_attribute
+        #vis #sig {
+            #setup
+            #block
+        }
+    })
+}
+
+fn parameter(input: FnArg, container: &str) -> syn::Result<Parameter> {
+    let mut input = match input {
+        FnArg::Typed(input) => input,
+        FnArg::Receiver(receiver) => {
+            return Err(syn::Error::new_spanned(receiver, "tests can't take `self`"))
+        }
+    };
+
+    let image_type = image_type(&input.ty, container).ok_or_else(|| {
+        syn::Error::new_spanned(
+            &input.ty,
+            format!("expected a `{container}<I>`, where `I` is the image of the container"),
+        )
+    })?;
+    let image = match take_image_attribute(&mut input.attrs)? {
+        Some(image) => image,
+        None => syn::parse_quote! { <#image_type as ::core::default::Default>::default() },
+    };
+    if let Some(attr) = input.attrs.first() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "only `#[image(...)]` is supported on parameters",
+        ));
+    }
+
+    let name = match &*input.pat {
+        Pat::Ident(pattern) => pattern.ident.to_string(),
+        pattern => pattern.to_token_stream().to_string(),
+    };
+    Ok(Parameter {
+        name,
+        pattern: input.pat.into_token_stream(),
+        ty: *input.ty,
+        image_type,
+        image,
+    })
+}
+
+/// Returns `I` of a `Container<I>` or `ContainerAsync<I>` type, whichever is expected.
+fn image_type(ty: &Type, container: &str) -> Option<Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != container {
+        return None;
+    }
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.first()? {
+        GenericArgument::Type(image_type) if arguments.args.len() == 1 => Some(image_type.clone()),
+        _ => None,
+    }
+}
+
+/// Removes the `#[image(...)]` attribute and returns its expression.
+fn take_image_attribute(attrs: &mut Vec<Attribute>) -> syn::Result<Option<Expr>> {
+    let Some(index) = attrs.iter().position(|attr| attr.path().is_ident("image")) else {
+        return Ok(None);
+    };
+    attrs.remove(index).parse_args().map(Some)
+}
@@ -0,0 +1,316 @@
+//! Lists and removes containers, networks and volumes that were left behind by sessions which
+//! could not clean up after themselves, e.g. because the test process crashed or was killed.
+//!
+//! Every resource created by testcontainers is labeled with the id of its session, which is
+//! unique per process. A CI job can sweep the leftovers of previous runs before running the
+//! tests:
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let removed = testcontainers::cleanup::remove_stale(Duration::from_secs(60 * 60))
+//!         .await
+//!         .unwrap();
+//!     println!("removed {} leaked containers", removed.containers.len());
+//! }
+//! ```
+//!
+//! The `testcontainers-cli` binary does the same from the command line.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::{Duration, SystemTime},
+};
+
+use bollard::{
+    container::{ListContainersOptions, RemoveContainerOptions},
+    network::ListNetworksOptions,
+    volume::ListVolumesOptions,
+};
+
+use crate::core::{
+    client::{Client, DesiredLogStream},
+    runtime, session, TestcontainersError,
+};
+
+/// The resources that were removed by [`remove_session`] or [`remove_stale`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Removed {
+    /// The ids of the removed containers.
+    pub containers: Vec<String>,
+    /// The names of the removed networks.
+    pub networks: Vec<String>,
+    /// The names of the removed volumes.
+    pub volumes: Vec<String>,
+}
+
+impl Removed {
+    fn extend(&mut self, other: Removed) {
+        self.containers.extend(other.containers);
+        self.networks.extend(other.networks);
+        self.volumes.extend(other.volumes);
+    }
+}
+
+/// A session that owns at least one container, network or volume, see [`sessions`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    pub id: String,
+    /// When the session started, if its id is one of a session of testcontainers.
+    pub started_at: Option<SystemTime>,
+    pub containers: Vec<SessionContainer>,
+    /// The names of the networks of the session.
+    pub networks: Vec<String>,
+    /// The names of the volumes of the session.
+    pub volumes: Vec<String>,
+}
+
+/// A container of a [`Session`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionContainer {
+    pub id: String,
+    pub name: String,
+    pub image: String,
+    /// The state of the container, e.g. `running` or `exited`.
+    pub state: String,
+}
+
+/// Returns the id of the current session.
+pub fn session_id() -> &'static str {
+    session::session_id()
+}
+
+/// Returns all sessions that own at least one container, network or volume, oldest first.
+///
+/// Returns an error if the daemon can't be reached or the resources can't be listed.
+pub async fn sessions() -> Result<Vec<Session>, TestcontainersError> {
+    runtime::compat(async {
+        let client = Client::try_lazy_client().await?;
+        list_sessions(&client).await
+    })
+    .await
+}
+
+/// Returns the containers of the session, along with everything they have written to stdout
+/// and stderr so far.
+///
+/// Returns an error if the daemon can't be reached or the containers can't be listed.
+pub async fn session_logs(
+    session_id: &str,
+) -> Result<Vec<(SessionContainer, String)>, TestcontainersError> {
+    runtime::compat(async {
+        let client = Client::try_lazy_client().await?;
+        let label = format!("{}={session_id}", session::SESSION_ID_LABEL);
+
+        let mut logs = Vec::new();
+        for (_, container) in list_labeled_containers(&client, &label).await? {
+            let container_logs = client
+                .collected_logs(&container.id, DesiredLogStream::Both)
+                .await;
+            logs.push((container, container_logs));
+        }
+        Ok(logs)
+    })
+    .await
+}
+
+/// Removes all containers, networks and volumes of the session.
+///
+/// Resources that can't be removed, e.g. a network that is still in use by a container of
+/// another session, are skipped with a warning. Returns an error if the daemon can't be reached
+/// or the resources can't be listed.
+pub async fn remove_session(session_id: &str) -> Result<Removed, TestcontainersError> {
+    runtime::compat(async {
+        let client = Client::try_lazy_client().await?;
+        remove_labeled(
+            &client,
+            &format!("{}={session_id}", session::SESSION_ID_LABEL),
+        )
+        .await
+    })
+    .await
+}
+
+/// Removes all containers, networks and volumes of the sessions that started more than
+/// `older_than` ago. The resources of the current session are never removed.
+///
+/// Returns an error if the daemon can't be reached or the resources can't be listed.
+pub async fn remove_stale(older_than: Duration) -> Result<Removed, TestcontainersError> {
+    runtime::compat(async {
+        let client = Client::try_lazy_client().await?;
+        let cutoff = SystemTime::now()
+            .checked_sub(older_than)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+
+        let mut removed = Removed::default();
+        for Session { id, started_at, .. } in list_sessions(&client).await? {
+            let stale = started_at.is_some_and(|started_at| started_at < cutoff);
+            if stale && id != session::session_id() {
+                log::debug!("Removing resources of stale session {id}");
+                removed.extend(
+                    remove_labeled(&client, &format!("{}={id}", session::SESSION_ID_LABEL)).await?,
+                );
+            }
+        }
+        Ok(removed)
+    })
+    .await
+}
+
+/// Returns all sessions that own at least one container, network or volume, ordered by their
+/// ids, which start with the time the session started.
+async fn list_sessions(client: &Client) -> Result<Vec<Session>, TestcontainersError> {
+    let mut sessions = BTreeMap::new();
+    let mut session = |id: String| {
+        sessions.entry(id.clone()).or_insert_with(|| Session {
+            started_at: session::started_at(&id),
+            id,
+            containers: Vec::new(),
+            networks: Vec::new(),
+            volumes: Vec::new(),
+        })
+    };
+
+    for (id, container) in list_labeled_containers(client, session::SESSION_ID_LABEL).await? {
+        session(id).containers.push(container);
+    }
+
+    let filters = HashMap::from([("label", vec![session::SESSION_ID_LABEL])]);
+    let networks = client
+        .bollard
+        .list_networks(Some(ListNetworksOptions {
+            filters: filters.clone(),
+        }))
+        .await
+        .map_err(list_failed("networks"))?;
+    for network in networks {
+        if let (Some(name), Some(id)) = (network.name, session_label(network.labels)) {
+            session(id).networks.push(name);
+        }
+    }
+
+    let volumes = client
+        .bollard
+        .list_volumes(Some(ListVolumesOptions { filters }))
+        .await
+        .map_err(list_failed("volumes"))?
+        .volumes
+        .unwrap_or_default();
+    for volume in volumes {
+        if let Some(id) = session_label(Some(volume.labels)) {
+            session(id).volumes.push(volume.name);
+        }
+    }
+
+    Ok(sessions.into_values().collect())
+}
+
+/// Returns the containers with the label, along with the ids of their sessions.
+async fn list_labeled_containers(
+    client: &Client,
+    label: &str,
+) -> Result<Vec<(String, SessionContainer)>, TestcontainersError> {
+    let containers = client
+        .bollard
+        .list_containers(Some(ListContainersOptions {
+            all: true,
+            filters: HashMap::from([("label", vec![label])]),
+            ..Default::default()
+        }))
+        .await
+        .map_err(list_failed("containers"))?;
+
+    Ok(containers
+        .into_iter()
+        .filter_map(|container| {
+            let session_id = session_label(container.labels)?;
+            let name = container
+                .names
+                .and_then(|names| names.into_iter().next())
+                .map(|name| name.trim_start_matches('/').to_string())
+                .unwrap_or_default();
+            Some((
+                session_id,
+                SessionContainer {
+                    id: container.id?,
+                    name,
+                    image: container.image.unwrap_or_default(),
+                    state: container.state.unwrap_or_default(),
+                },
+            ))
+        })
+        .collect())
+}
+
+/// Classifies a failure to list the resources of the given kind.
+fn list_failed(kind: &'static str) -> impl FnOnce(bollard::errors::Error) -> TestcontainersError {
+    move |err| {
+        TestcontainersError::from_client(err, |message| TestcontainersError::CleanupFailed {
+            message: format!("failed to list {kind}: {message}"),
+        })
+    }
+}
+
+fn session_label(labels: Option<HashMap<String, String>>) -> Option<String> {
+    labels?.remove(session::SESSION_ID_LABEL)
+}
+
+/// Removes all resources with the label, containers first so that their networks and volumes
+/// are no longer in use.
+async fn remove_labeled(client: &Client, label: &str) -> Result<Removed, TestcontainersError> {
+    let filters = HashMap::from([("label", vec![label])]);
+    let mut removed = Removed::default();
+
+    let containers = client
+        .bollard
+        .list_containers(Some(ListContainersOptions {
+            all: true,
+            filters: filters.clone(),
+            ..Default::default()
+        }))
+        .await
+        .map_err(list_failed("containers"))?;
+    for id in containers.into_iter().filter_map(|container| container.id) {
+        let options = RemoveContainerOptions {
+            force: true,
+            v: true,
+            ..Default::default()
+        };
+        match client.bollard.remove_container(&id, Some(options)).await {
+            Ok(()) => removed.containers.push(id),
+            Err(err) => log::warn!("Failed to remove container {id}: {err}"),
+        }
+    }
+
+    let networks = client
+        .bollard
+        .list_networks(Some(ListNetworksOptions {
+            filters: filters.clone(),
+        }))
+        .await
+        .map_err(list_failed("networks"))?;
+    for name in networks.into_iter().filter_map(|network| network.name) {
+        match client.bollard.remove_network(&name).await {
+            Ok(()) => removed.networks.push(name),
+            Err(err) => log::warn!("Failed to remove network {name}: {err}"),
+        }
+    }
+
+    let volumes = client
+        .bollard
+        .list_volumes(Some(ListVolumesOptions { filters }))
+        .await
+        .map_err(list_failed("volumes"))?
+        .volumes
+        .unwrap_or_default();
+    for volume in volumes {
+        match client.bollard.remove_volume(&volume.name, None).await {
+            Ok(()) => removed.volumes.push(volume.name),
+            Err(err) => log::warn!("Failed to remove volume {}: {err}", volume.name),
+        }
+    }
+
+    Ok(removed)
+}
@@ -0,0 +1,402 @@
+//! Starts the services of a [compose file], e.g. the stack a project already defines for local
+//! development, with the `docker compose` command, which has to be installed.
+//!
+//! ```rust,no_run
+//! use testcontainers::{compose::DockerCompose, core::WaitFor};
+//!
+//! async fn a_test() {
+//!     let compose = DockerCompose::from_file("docker-compose.yml")
+//!         .with_env_var(("REDIS_VERSION", "7.2.4"))
+//!         .with_wait_for(
+//!             "redis",
+//!             WaitFor::message_on_stdout("Ready to accept connections"),
+//!         )
+//!         .up()
+//!         .await
+//!         .unwrap();
+//!     let port = compose.service_port("redis", 6379).await.unwrap();
+//! }
+//! ```
+//!
+//! Profiles select a subset of the services to start, see [`DockerCompose::with_profile`], and
+//! services can be started with multiple containers, see [`DockerCompose::with_scale`].
+//!
+//! [compose file]: https://docs.docker.com/compose/compose-file/
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+    sync::Arc,
+    time::Duration,
+};
+
+use bollard::container::ListContainersOptions;
+
+use crate::core::{
+    client::Client, containers::async_container::block_until_ready, runtime, TestcontainersError,
+    WaitFor,
+};
+
+const PROJECT_LABEL: &str = "com.docker.compose.project";
+const SERVICE_LABEL: &str = "com.docker.compose.service";
+const CONTAINER_NUMBER_LABEL: &str = "com.docker.compose.container-number";
+
+/// The services of one or more compose files that are started together as a project.
+///
+/// Every instance is started as a project of its own, so tests can start the same compose file
+/// in parallel.
+pub struct DockerCompose {
+    files: Vec<PathBuf>,
+    project: String,
+    env_vars: BTreeMap<String, String>,
+    ready_conditions: BTreeMap<String, Vec<WaitFor>>,
+    startup_timeout: Option<Duration>,
+    profiles: Vec<String>,
+    scales: BTreeMap<String, usize>,
+}
+
+impl DockerCompose {
+    /// Creates a project of the services of the compose file.
+    pub fn from_file(path: impl AsRef<Path>) -> Self {
+        Self::from_files([path])
+    }
+
+    /// Creates a project of the services of the compose files, where later files override
+    /// earlier ones, like with `docker compose --file a.yml --file b.yml`.
+    pub fn from_files(paths: impl IntoIterator<Item = impl AsRef<Path>>) -> Self {
+        Self {
+            files: paths
+                .into_iter()
+                .map(|path| path.as_ref().to_path_buf())
+                .collect(),
+            project: format!(
+                "testcontainers-{}",
+                ulid::Ulid::new().to_string().to_lowercase()
+            ),
+            env_vars: BTreeMap::new(),
+            ready_conditions: BTreeMap::new(),
+            startup_timeout: None,
+            profiles: Vec::new(),
+            scales: BTreeMap::new(),
+        }
+    }
+
+    /// Sets an environment variable that is substituted for `${KEY}` in the compose files.
+    pub fn with_env_var(mut self, (key, value): (impl Into<String>, impl Into<String>)) -> Self {
+        self.env_vars.insert(key.into(), value.into());
+        self
+    }
+
+    /// Adds a condition that every container of the service has to meet before the project is
+    /// considered up.
+    pub fn with_wait_for(mut self, service: impl Into<String>, wait_for: WaitFor) -> Self {
+        self.ready_conditions
+            .entry(service.into())
+            .or_default()
+            .push(wait_for);
+        self
+    }
+
+    /// Sets how long to wait for the containers of each service to meet their ready
+    /// conditions. Defaults to the value of the `TESTCONTAINERS_STARTUP_TIMEOUT` environment
+    /// variable (in seconds), or 60 seconds.
+    pub fn with_startup_timeout(self, timeout: Duration) -> Self {
+        Self {
+            startup_timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// Activates the profile, so that the services assigned to it are started along with the
+    /// services without a profile. Services of profiles that are not active are not started.
+    pub fn with_profile(mut self, profile: impl Into<String>) -> Self {
+        self.profiles.push(profile.into());
+        self
+    }
+
+    /// Sets the number of containers the service is started with. A service that publishes a
+    /// fixed host port can't be scaled, as the port can only be bound once.
+    pub fn with_scale(mut self, service: impl Into<String>, replicas: usize) -> Self {
+        self.scales.insert(service.into(), replicas);
+        self
+    }
+
+    /// Returns the name of the compose project.
+    pub fn project(&self) -> &str {
+        &self.project
+    }
+
+    /// Starts the services and waits until their containers meet their ready conditions.
+    ///
+    /// If the project can't be started, it is taken down again and the error is returned.
+    pub async fn up(self) -> Result<ComposeAsync, TestcontainersError> {
+        runtime::compat(async move {
+            let client = Client::try_lazy_client().await?;
+            // taken down on drop if a service fails to start
+            let compose = ComposeAsync {
+                compose: self,
+                client,
+            };
+
+            log::debug!("Starting compose project {}", compose.project());
+            let mut args = vec!["up".to_string(), "--detach".to_string()];
+            for (service, replicas) in &compose.compose.scales {
+                args.push("--scale".to_string());
+                args.push(format!("{service}={replicas}"));
+            }
+            compose.run(&args).await?;
+
+            for service in compose.compose.ready_conditions.keys() {
+                compose.wait_until_ready(service).await?;
+            }
+
+            Ok(compose)
+        })
+        .await
+    }
+
+    /// Returns the `docker compose` command for the project, which talks to the same daemon
+    /// as the client.
+    fn command(&self, client: &Client) -> Command {
+        let mut command = Command::new("docker");
+        command
+            .arg("compose")
+            .arg("--project-name")
+            .arg(&self.project);
+        for file in &self.files {
+            command.arg("--file").arg(file);
+        }
+        for profile in &self.profiles {
+            command.arg("--profile").arg(profile);
+        }
+
+        command
+            .env("DOCKER_HOST", client.config.docker_host().as_str())
+            .envs(&self.env_vars);
+        if client.config.tls_verify() {
+            command.env("DOCKER_TLS_VERIFY", "1");
+            if let Some(cert_path) = client.config.cert_path() {
+                command.env("DOCKER_CERT_PATH", cert_path);
+            }
+        }
+        command
+    }
+}
+
+impl fmt::Debug for DockerCompose {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DockerCompose")
+            .field("files", &self.files)
+            .field("project", &self.project)
+            .field("env_vars", &self.env_vars)
+            .field("ready_conditions", &self.ready_conditions)
+            .field("startup_timeout", &self.startup_timeout)
+            .field("profiles", &self.profiles)
+            .field("scales", &self.scales)
+            .finish()
+    }
+}
+
+/// A compose project that is up, see [`DockerCompose::up`].
+///
+/// Once it is dropped, the project is taken down and its volumes are removed, unless the
+/// [`RemovePolicy`] keeps it.
+///
+/// [`RemovePolicy`]: crate::core::RemovePolicy
+pub struct ComposeAsync {
+    compose: DockerCompose,
+    client: Arc<Client>,
+}
+
+impl ComposeAsync {
+    /// Returns the name of the compose project.
+    pub fn project(&self) -> &str {
+        &self.compose.project
+    }
+
+    /// Returns the ids of the containers of the service, ordered by their number.
+    ///
+    /// Returns an error if the containers of the project can't be listed.
+    pub async fn container_ids(&self, service: &str) -> Result<Vec<String>, TestcontainersError> {
+        let project_filter = format!("{PROJECT_LABEL}={}", self.compose.project);
+        let service_filter = format!("{SERVICE_LABEL}={service}");
+        let options = ListContainersOptions {
+            all: true,
+            filters: [(
+                "label",
+                vec![project_filter.as_str(), service_filter.as_str()],
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+
+        let mut containers = runtime::compat(self.client.bollard.list_containers(Some(options)))
+            .await
+            .map_err(|err| TestcontainersError::from_client(err, |message| self.failed(message)))?;
+        containers.sort_by_key(|container| {
+            container
+                .labels
+                .as_ref()
+                .and_then(|labels| labels.get(CONTAINER_NUMBER_LABEL))
+                .and_then(|number| number.parse::<u32>().ok())
+        });
+        Ok(containers
+            .into_iter()
+            .filter_map(|container| container.id)
+            .collect())
+    }
+
+    /// Returns the id of the first container of the service.
+    ///
+    /// Returns an error if the service has no containers.
+    pub async fn container_id(&self, service: &str) -> Result<String, TestcontainersError> {
+        self.container_ids(service)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| self.failed(format!("service {service} has no containers")))
+    }
+
+    /// Returns the host on which the published ports of the services are reachable.
+    pub fn host(&self) -> String {
+        self.client.docker_hostname()
+    }
+
+    /// Returns the host port that the port of the first container of the service is published
+    /// on, on the host's IPv4 interfaces.
+    ///
+    /// Returns an error if the service has no containers or does not publish the port.
+    pub async fn service_port(&self, service: &str, port: u16) -> Result<u16, TestcontainersError> {
+        let id = self.container_id(service).await?;
+        runtime::compat(self.client.ports(&id))
+            .await
+            .map_to_host_port_ipv4(port)
+            .ok_or_else(|| {
+                self.failed(format!(
+                    "port {port} of service {service} is not published, add it to its `ports`"
+                ))
+            })
+    }
+
+    /// Scales the service to the number of containers, keeping the containers that are already
+    /// running, and waits until the new containers meet the ready conditions of the service.
+    pub async fn scale(&self, service: &str, replicas: usize) -> Result<(), TestcontainersError> {
+        runtime::compat(async {
+            log::debug!(
+                "Scaling service {service} of compose project {} to {replicas}",
+                self.compose.project
+            );
+            let args = [
+                "up".to_string(),
+                "--detach".to_string(),
+                "--no-recreate".to_string(),
+                "--scale".to_string(),
+                format!("{service}={replicas}"),
+                service.to_string(),
+            ];
+            self.run(&args).await?;
+
+            if self.compose.ready_conditions.contains_key(service) {
+                self.wait_until_ready(service).await?;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    /// Waits until every container of the service meets the ready conditions of the service.
+    async fn wait_until_ready(&self, service: &str) -> Result<(), TestcontainersError> {
+        let conditions = self
+            .compose
+            .ready_conditions
+            .get(service)
+            .cloned()
+            .unwrap_or_default();
+        let timeout = self
+            .compose
+            .startup_timeout
+            .unwrap_or_else(|| self.client.config.startup_timeout());
+
+        let ids = self.container_ids(service).await?;
+        if ids.is_empty() {
+            return Err(self.failed(format!("service {service} has no containers")));
+        }
+        for id in ids {
+            block_until_ready(&self.client, &id, conditions.clone(), timeout).await?;
+        }
+        Ok(())
+    }
+
+    async fn run(&self, args: &[String]) -> Result<(), TestcontainersError> {
+        let mut command = self.compose.command(&self.client);
+        command.args(args);
+
+        let output = tokio::process::Command::from(command).output().await;
+        self.check(args, output)
+    }
+
+    /// Returns an error if the compose command could not be run or failed.
+    fn check(
+        &self,
+        args: &[String],
+        output: std::io::Result<Output>,
+    ) -> Result<(), TestcontainersError> {
+        let message = match output {
+            Ok(output) if output.status.success() => return Ok(()),
+            Ok(output) => format!(
+                "`docker compose {}` exited with {}: {}",
+                args.join(" "),
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            Err(err) => format!("failed to run `docker compose`: {err}"),
+        };
+        Err(self.failed(message))
+    }
+
+    fn failed(&self, message: String) -> TestcontainersError {
+        TestcontainersError::ComposeFailed {
+            project: self.compose.project.clone(),
+            message,
+        }
+    }
+}
+
+impl fmt::Debug for ComposeAsync {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ComposeAsync")
+            .field("compose", &self.compose)
+            .finish()
+    }
+}
+
+impl Drop for ComposeAsync {
+    fn drop(&mut self) {
+        let failed = std::thread::panicking();
+        if !self.client.config.remove_policy().removes(failed) {
+            if failed {
+                log::warn!(
+                    "Kept compose project {} of the failed test for debugging, take it down with \
+                     `docker compose --project-name {} down --volumes`",
+                    self.compose.project,
+                    self.compose.project
+                );
+            }
+            return;
+        }
+
+        log::debug!("Taking down compose project {}", self.compose.project);
+        let args = ["down", "--volumes", "--remove-orphans"].map(String::from);
+        let mut command = tokio::process::Command::from(self.compose.command(&self.client));
+        command.args(&args);
+        // async drop is not available, so the command is awaited on the runtime of
+        // testcontainers, which doesn't block a worker of the runtime of the test
+        let output = runtime::block_on(async move { command.output().await });
+        if let Err(err) = self.check(&args, output) {
+            log::warn!("{err}");
+        }
+    }
+}
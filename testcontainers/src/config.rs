@@ -0,0 +1,63 @@
+//! Settings that apply to all containers started by the test binary.
+//!
+//! Most settings are taken from environment variables, like `TESTCONTAINERS_STARTUP_TIMEOUT`,
+//! and the ones here are set from code, e.g. at the start of each test or in a fixture.
+
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// The limit of concurrent starts, along with the semaphore that enforces it.
+static START_LIMIT: Mutex<Option<(usize, Arc<Semaphore>)>> = Mutex::new(None);
+
+/// Limits how many containers are started at the same time, across all tests of the binary.
+///
+/// Cargo runs tests on as many threads as there are CPUs, so a suite that starts a container
+/// in every test makes the daemon pull, create and start dozens of containers at once, which
+/// can make these requests time out. Once a limit is set, further starts wait until one of
+/// the starts in progress has created and started its container. Waiting for the ready
+/// conditions of a container is not limited, as that is up to the container.
+///
+/// Setting the limit again replaces it for the starts that didn't begin yet. Calling this
+/// from every test with the same limit is fine, the limit is only replaced if it changes.
+///
+/// ```rust
+/// async fn a_test() {
+///     testcontainers::config::set_max_concurrent_starts(4);
+///     // start containers as usual
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if the limit is `0`, as no container could be started.
+pub fn set_max_concurrent_starts(limit: usize) {
+    assert!(
+        limit > 0,
+        "at least one container has to be allowed to start"
+    );
+
+    let mut start_limit = START_LIMIT.lock().unwrap_or_else(|err| err.into_inner());
+    if !matches!(&*start_limit, Some((current, _)) if *current == limit) {
+        *start_limit = Some((limit, Arc::new(Semaphore::new(limit))));
+    }
+}
+
+/// Returns how many containers are started at the same time, if it is limited, see
+/// [`set_max_concurrent_starts`].
+pub fn max_concurrent_starts() -> Option<usize> {
+    let start_limit = START_LIMIT.lock().unwrap_or_else(|err| err.into_inner());
+    start_limit.as_ref().map(|(limit, _)| *limit)
+}
+
+/// Waits until a container may be started, and returns the permit to hold while it is pulled,
+/// created and started. Returns `None` if starts are not limited.
+pub(crate) async fn start_permit() -> Option<OwnedSemaphorePermit> {
+    let semaphore = {
+        let start_limit = START_LIMIT.lock().unwrap_or_else(|err| err.into_inner());
+        start_limit.as_ref().map(|(_, semaphore)| semaphore.clone())
+    }?;
+
+    // the semaphore is never closed
+    semaphore.acquire_owned().await.ok()
+}
@@ -1,15 +1,63 @@
-pub use self::image::{
-    ContainerState, ExecCommand, Host, Image, ImageArgs, Port, RunnableImage, WaitFor,
+#[cfg(feature = "grpc_wait")]
+#[cfg_attr(docsrs, doc(cfg(feature = "grpc_wait")))]
+pub use self::wait::GrpcHealthWaitStrategy;
+#[cfg(feature = "http_wait")]
+#[cfg_attr(docsrs, doc(cfg(feature = "http_wait")))]
+pub use self::wait::HttpWaitStrategy;
+pub use self::{
+    changes::{FileChange, FileChangeKind},
+    error::{ContainerDied, PortError, StartupError, StartupFailure, TestcontainersError},
+    events::{ContainerEvent, ContainerEventKind},
+    healthcheck::{HealthProbe, HealthState, HealthStatus, Healthcheck},
+    image::{
+        CgroupnsMode, ContainerState, ExecCommand, ExecResult, ExitStatus, Host, Image, ImageArgs,
+        Port, RunnableImage,
+    },
+    inspect::{ConfigInfo, ContainerInfo, MountInfo, NetworkInfo, StateInfo},
+    logs::{LogConsumer, LogDump, LogLine, LogSource},
+    mounts::{Mount, SelinuxRelabel},
+    network::Network,
+    plan::ContainerPlan,
+    ports::Ports,
+    processes::ProcessList,
+    removal::RemovePolicy,
+    resources::ResourceLimits,
+    signal::Signal,
+    snapshot::Snapshot,
+    stats::ContainerStats,
+    volume::Volume,
+    wait::{HealthWaitStrategy, WaitFor},
 };
 
+#[allow(deprecated)]
+pub use self::error::StartupTimeoutError;
+
 pub use self::containers::*;
 
 mod image;
 
+pub(crate) mod changes;
 pub(crate) mod client;
 pub(crate) mod containers;
+pub(crate) mod copy;
 pub(crate) mod env;
+pub(crate) mod env_file;
+pub(crate) mod error;
+pub(crate) mod events;
+pub(crate) mod healthcheck;
+pub(crate) mod inspect;
 pub(crate) mod logs;
-pub(crate) mod macros;
+pub(crate) mod mounts;
 pub(crate) mod network;
+pub(crate) mod plan;
 pub(crate) mod ports;
+pub(crate) mod processes;
+pub(crate) mod removal;
+pub(crate) mod resources;
+pub(crate) mod runtime;
+pub(crate) mod session;
+pub(crate) mod signal;
+pub(crate) mod snapshot;
+pub(crate) mod stats;
+pub(crate) mod volume;
+pub(crate) mod wait;
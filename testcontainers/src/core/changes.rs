@@ -0,0 +1,34 @@
+use bollard_stubs::models::{ChangeType, FilesystemChange};
+
+/// A path in the filesystem of a container that differs from its image, as reported by
+/// `docker diff`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    pub path: String,
+    pub kind: FileChangeKind,
+}
+
+/// The kind of a [`FileChange`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileChangeKind {
+    /// The path was modified, which includes the parent directories of added and deleted
+    /// paths.
+    Modified,
+    Added,
+    Deleted,
+}
+
+impl From<FilesystemChange> for FileChange {
+    fn from(change: FilesystemChange) -> Self {
+        let kind = match change.kind {
+            ChangeType::_0 => FileChangeKind::Modified,
+            ChangeType::_1 => FileChangeKind::Added,
+            ChangeType::_2 => FileChangeKind::Deleted,
+        };
+
+        Self {
+            path: change.path,
+            kind,
+        }
+    }
+}
@@ -1,49 +1,216 @@
-use crate::core::{env, logs::LogStreamAsync, ports::Ports, WaitFor};
+use crate::core::{
+    env,
+    logs::{LineBuffer, LogLine, LogSource, LogStreamAsync},
+    ports::Ports,
+    session, ContainerEvent, ContainerStats, ExecCommand, ExecResult, FileChange, ProcessList,
+    ResourceLimits, Signal, TestcontainersError, WaitFor,
+};
 use bollard::{
-    container::{Config, CreateContainerOptions, LogsOptions, RemoveContainerOptions},
+    container::{
+        Config, CreateContainerOptions, DownloadFromContainerOptions, KillContainerOptions,
+        LogOutput, LogsOptions, RemoveContainerOptions, RestartContainerOptions, StatsOptions,
+        StopContainerOptions, TopOptions, UpdateContainerOptions, UploadToContainerOptions,
+    },
     exec::{CreateExecOptions, StartExecOptions, StartExecResults},
-    image::CreateImageOptions,
+    image::{CommitContainerOptions, CreateImageOptions, TagImageOptions},
     network::CreateNetworkOptions,
+    system::EventsOptions,
+    volume::CreateVolumeOptions,
     Docker,
 };
-use bollard_stubs::models::{ContainerCreateResponse, ContainerInspectResponse, HealthStatusEnum};
-use futures::{StreamExt, TryStreamExt};
-use std::{io, time::Duration};
+use bollard_stubs::models::{
+    ContainerCreateResponse, ContainerInspectResponse, ContainerStateStatusEnum, CreateImageInfo,
+    Ipam, IpamConfig,
+};
+use bytes::Bytes;
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt, TryStreamExt,
+};
+use std::{
+    collections::HashMap,
+    io,
+    pin::Pin,
+    time::{Duration, SystemTime},
+};
+use tokio::{io::AsyncWrite, sync::OnceCell};
 
 mod bollard_client;
+mod engine;
 mod factory;
+mod pull;
+mod ssh;
+
+pub(crate) use engine::Engine;
 
 /// The desired log stream.
 pub(crate) enum DesiredLogStream {
     Stdout,
     Stderr,
+    Both,
 }
 
 /// The internal client.
 pub(crate) struct Client {
     pub(crate) config: env::Config,
     pub(crate) bollard: Docker,
+    engine: OnceCell<Engine>,
+    /// Forwards the docker socket of an `ssh://` docker host for as long as the client lives.
+    _ssh_tunnel: Option<ssh::SshTunnel>,
 }
 
 impl Client {
-    async fn new() -> Client {
+    async fn new() -> Result<Client, TestcontainersError> {
         let config = env::Config::load::<env::Os>().await;
-        let bollard = bollard_client::init(&config);
+        let ssh_tunnel = match config.docker_host().scheme() {
+            "ssh" => Some(ssh::SshTunnel::open(&config).await?),
+            _ => None,
+        };
+        let bollard = bollard_client::init(&config, ssh_tunnel.as_ref())?;
+
+        Ok(Client {
+            config,
+            bollard,
+            engine: OnceCell::new(),
+            _ssh_tunnel: ssh_tunnel,
+        })
+    }
 
-        Client { config, bollard }
+    /// Returns the container engine that serves the docker API, which is detected once.
+    pub(crate) async fn engine(&self) -> Engine {
+        *self
+            .engine
+            .get_or_init(|| Engine::detect(&self.bollard))
+            .await
     }
 
     pub(crate) fn stdout_logs(&self, id: &str) -> LogStreamAsync<'_> {
-        self.logs(id, DesiredLogStream::Stdout)
+        self.logs(id, DesiredLogStream::Stdout, true)
     }
 
     pub(crate) fn stderr_logs(&self, id: &str) -> LogStreamAsync<'_> {
-        self.logs(id, DesiredLogStream::Stderr)
+        self.logs(id, DesiredLogStream::Stderr, true)
     }
 
-    pub(crate) async fn ports(&self, id: &str) -> Ports {
-        self.inspect(id)
+    pub(crate) fn all_logs(&self, id: &str) -> LogStreamAsync<'_> {
+        self.logs(id, DesiredLogStream::Both, true)
+    }
+
+    /// Returns everything the container has written to the desired log stream so far.
+    pub(crate) async fn collected_logs(&self, id: &str, desired_log: DesiredLogStream) -> String {
+        self.logs(id, desired_log, false)
+            .read_to_string()
             .await
+            .unwrap_or_else(|err| {
+                log::warn!("Failed to read logs of container {id}: {err}");
+                String::new()
+            })
+    }
+
+    /// Returns the lines the container writes to the desired log stream, from its start on and
+    /// following the logs until the container stops.
+    pub(crate) fn log_lines(
+        &self,
+        id: &str,
+        desired_log: DesiredLogStream,
+    ) -> BoxStream<'static, Result<(LogSource, LogLine), io::Error>> {
+        let (stdout, stderr) = match desired_log {
+            DesiredLogStream::Stdout => (true, false),
+            DesiredLogStream::Stderr => (false, true),
+            DesiredLogStream::Both => (true, true),
+        };
+        let options = LogsOptions::<String> {
+            follow: true,
+            stdout,
+            stderr,
+            timestamps: true,
+            tail: "all".to_owned(),
+            ..Default::default()
+        };
+
+        // the frames of each stream are joined into lines, and incomplete lines are returned
+        // once the logs end
+        let mut stdout = LineBuffer::default();
+        let mut stderr = LineBuffer::default();
+        self.bollard
+            .logs(id, Some(options))
+            .map(Some)
+            .chain(stream::once(async { None }))
+            .flat_map(move |chunk| {
+                let lines = match chunk {
+                    Some(Ok(LogOutput::StdErr { message })) => {
+                        vec![stderr
+                            .push(&message)
+                            .map(|line| Ok((LogSource::StdErr, line)))]
+                    }
+                    Some(Ok(output)) => vec![stdout
+                        .push(&output.into_bytes())
+                        .map(|line| Ok((LogSource::StdOut, line)))],
+                    Some(Err(err)) => vec![Some(Err(io::Error::new(io::ErrorKind::Other, err)))],
+                    None => vec![
+                        stdout.finish().map(|line| Ok((LogSource::StdOut, line))),
+                        stderr.finish().map(|line| Ok((LogSource::StdErr, line))),
+                    ],
+                };
+                stream::iter(lines.into_iter().flatten())
+            })
+            .boxed()
+    }
+
+    /// Returns the resource usage of the container, either once or every second until the
+    /// container stops.
+    pub(crate) fn stats(
+        &self,
+        id: &str,
+        stream: bool,
+    ) -> BoxStream<'static, Result<ContainerStats, io::Error>> {
+        let options = StatsOptions {
+            stream,
+            one_shot: false,
+        };
+
+        self.bollard
+            .stats(id, Some(options))
+            .map_ok(ContainerStats::from)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+            .boxed()
+    }
+
+    /// Returns the lifecycle events of the container from now on.
+    pub(crate) fn events(&self, id: &str) -> BoxStream<'static, Result<ContainerEvent, io::Error>> {
+        // the request is only sent once the stream is polled, so events that happen meanwhile
+        // are requested explicitly
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+        let options = EventsOptions::<String> {
+            since: Some(format!("{}.{:09}", now.as_secs(), now.subsec_nanos())),
+            filters: HashMap::from([
+                ("type".to_string(), vec!["container".to_string()]),
+                ("container".to_string(), vec![id.to_string()]),
+            ]),
+            ..Default::default()
+        };
+
+        self.bollard
+            .events(Some(options))
+            .map_ok(ContainerEvent::from)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+            .boxed()
+    }
+
+    pub(crate) async fn ports(&self, id: &str) -> Ports {
+        let container = self.inspect(id).await;
+
+        let network_mode = container
+            .host_config
+            .as_ref()
+            .and_then(|host_config| host_config.network_mode.as_deref());
+        if network_mode == Some("host") {
+            return Ports::host_network();
+        }
+
+        container
             .network_settings
             .unwrap_or_default()
             .ports
@@ -52,7 +219,14 @@ impl Client {
     }
 
     pub(crate) async fn inspect(&self, id: &str) -> ContainerInspectResponse {
-        self.bollard.inspect_container(id, None).await.unwrap()
+        self.try_inspect(id).await.unwrap()
+    }
+
+    pub(crate) async fn try_inspect(
+        &self,
+        id: &str,
+    ) -> Result<ContainerInspectResponse, bollard::errors::Error> {
+        self.bollard.inspect_container(id, None).await
     }
 
     pub(crate) async fn rm(&self, id: &str) {
@@ -69,8 +243,99 @@ impl Client {
             .unwrap();
     }
 
-    pub(crate) async fn stop(&self, id: &str) {
-        self.bollard.stop_container(id, None).await.unwrap();
+    /// Removes the container, unless docker has already removed it (or is about to) because it
+    /// was started with auto-remove.
+    pub(crate) async fn rm_if_exists(&self, id: &str) {
+        let result = self
+            .bollard
+            .remove_container(
+                id,
+                Some(RemoveContainerOptions {
+                    force: true,
+                    v: true,
+                    ..Default::default()
+                }),
+            )
+            .await;
+
+        match result {
+            Ok(()) => {}
+            // 409 is returned if the removal is already in progress
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404 | 409,
+                ..
+            }) => log::debug!("Container {id} was already removed"),
+            Err(err) => panic!("failed to remove container {id}: {err}"),
+        }
+    }
+
+    /// Stops the container, killing it once the timeout (in whole seconds) elapsed. Without a
+    /// timeout, the stop timeout of the container applies.
+    pub(crate) async fn stop(&self, id: &str, timeout: Option<Duration>) {
+        let options = timeout.map(|timeout| StopContainerOptions {
+            t: timeout.as_secs() as i64,
+        });
+        self.bollard.stop_container(id, options).await.unwrap();
+    }
+
+    /// Restarts the container, killing it once the timeout (in whole seconds) elapsed. Without
+    /// a timeout, the stop timeout of the container applies.
+    pub(crate) async fn restart(&self, id: &str, timeout: Option<Duration>) {
+        let options = timeout.map(|timeout| RestartContainerOptions {
+            t: timeout.as_secs() as isize,
+        });
+        self.bollard.restart_container(id, options).await.unwrap();
+    }
+
+    /// Returns the processes running in the container, listed by `ps` with the given
+    /// arguments.
+    pub(crate) async fn top(&self, id: &str, ps_args: Option<&str>) -> ProcessList {
+        let options = ps_args.map(|ps_args| TopOptions { ps_args });
+        self.bollard
+            .top_processes(id, options)
+            .await
+            .unwrap_or_else(|err| panic!("failed to list processes of container {id}: {err}"))
+            .into()
+    }
+
+    pub(crate) async fn update(&self, id: &str, limits: &ResourceLimits) {
+        self.bollard
+            .update_container(id, UpdateContainerOptions::<String>::from(limits))
+            .await
+            .unwrap_or_else(|err| panic!("failed to update limits of container {id}: {err}"));
+    }
+
+    pub(crate) async fn kill(&self, id: &str, signal: &Signal) {
+        let options = KillContainerOptions {
+            signal: signal.to_string(),
+        };
+        self.bollard
+            .kill_container(id, Some(options))
+            .await
+            .unwrap();
+    }
+
+    /// Creates an image `repo:tag` from the current filesystem and configuration of the
+    /// container, which is paused meanwhile.
+    pub(crate) async fn commit(&self, id: &str, repo: &str, tag: &str) {
+        let options = CommitContainerOptions {
+            container: id,
+            repo,
+            tag,
+            pause: true,
+            ..Default::default()
+        };
+        self.bollard
+            .commit_container(options, Config::<String>::default())
+            .await
+            .unwrap_or_else(|err| panic!("failed to commit container {id}: {err}"));
+    }
+
+    /// Removes the image, which fails while containers of it exist.
+    pub(crate) async fn remove_image(&self, descriptor: &str) {
+        if let Err(err) = self.bollard.remove_image(descriptor, None, None).await {
+            log::warn!("Failed to remove image {descriptor}: {err}");
+        }
     }
 
     pub(crate) async fn start(&self, id: &str) {
@@ -80,117 +345,228 @@ impl Client {
             .unwrap();
     }
 
-    pub(crate) async fn exec(
+    /// Blocks until the container exits and returns its exit code.
+    pub(crate) async fn wait_for_exit(&self, id: &str) -> i64 {
+        let result = self
+            .bollard
+            .wait_container::<String>(id, None)
+            .boxed()
+            .next()
+            .await
+            .unwrap_or_else(|| panic!("wait stream of container {id} ended without a result"));
+
+        match result {
+            Ok(response) => response.status_code,
+            // bollard reports non-zero exit codes as an error
+            Err(bollard::errors::Error::DockerContainerWaitError { code, .. }) => code,
+            Err(err) => panic!("failed to wait for container {id} to exit: {err}"),
+        }
+    }
+
+    /// Returns the exit code of the container, or `None` if it has not exited.
+    pub(crate) async fn exit_code(&self, id: &str) -> Option<i64> {
+        let state = self.inspect(id).await.state?;
+
+        match state.status {
+            Some(ContainerStateStatusEnum::EXITED | ContainerStateStatusEnum::DEAD) => {
+                state.exit_code
+            }
+            _ => None,
+        }
+    }
+
+    /// Starts the command inside the container and returns the ID of the exec with its output,
+    /// which is `None` if the command is detached.
+    pub(crate) async fn start_exec(
         &self,
         container_id: &str,
-        cmd: Vec<String>,
-        desired_log: DesiredLogStream,
-    ) -> LogStreamAsync<'_> {
-        let (stdout, stderr) = match desired_log {
-            DesiredLogStream::Stdout => (true, false),
-            DesiredLogStream::Stderr => (false, true),
-        };
+        cmd: &ExecCommand,
+    ) -> Result<
+        (
+            String,
+            Option<BoxStream<'static, Result<LogOutput, bollard::errors::Error>>>,
+        ),
+        bollard::errors::Error,
+    > {
+        let exec = self
+            .bollard
+            .create_exec(container_id, exec_options(cmd, false))
+            .await?;
 
-        let config = CreateExecOptions {
-            cmd: Some(cmd),
-            attach_stdout: Some(stdout),
-            attach_stderr: Some(stderr),
+        let options = StartExecOptions {
+            detach: cmd.detach,
             ..Default::default()
         };
+        match self.bollard.start_exec(&exec.id, Some(options)).await? {
+            StartExecResults::Attached { output, .. } => Ok((exec.id, Some(output))),
+            StartExecResults::Detached => Ok((exec.id, None)),
+        }
+    }
+
+    /// Runs the command inside the container until it finishes and returns its output and
+    /// exit code.
+    ///
+    /// A detached command is only started, its output and exit code are not available.
+    pub(crate) async fn exec(&self, container_id: &str, cmd: &ExecCommand) -> ExecResult {
+        let (id, output) = self
+            .start_exec(container_id, cmd)
+            .await
+            .expect("failed to execute command");
+        let Some(mut output) = output else {
+            return ExecResult {
+                exit_code: None,
+                stdout: Vec::new(),
+                stderr: Vec::new(),
+            };
+        };
 
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        // the command has finished once its output is closed
+        while let Some(chunk) = output.next().await {
+            match chunk.expect("failed to read output of exec") {
+                LogOutput::StdOut { message } => stdout.extend_from_slice(&message),
+                LogOutput::StdErr { message } => stderr.extend_from_slice(&message),
+                _ => {}
+            }
+        }
+
+        ExecResult {
+            exit_code: self.exec_exit_code(&id).await,
+            stdout,
+            stderr,
+        }
+    }
+
+    /// Starts the command inside the container with its stdin attached, and returns the ID
+    /// of the exec with its output and input.
+    pub(crate) async fn exec_attached(
+        &self,
+        container_id: &str,
+        cmd: &ExecCommand,
+    ) -> (
+        String,
+        BoxStream<'static, Result<LogOutput, bollard::errors::Error>>,
+        Pin<Box<dyn AsyncWrite + Send>>,
+    ) {
         let exec = self
             .bollard
-            .create_exec(container_id, config)
+            .create_exec(container_id, exec_options(cmd, true))
             .await
             .expect("failed to create exec");
 
-        let res = self
+        match self
             .bollard
-            .start_exec(
-                &exec.id,
-                Some(StartExecOptions {
-                    detach: false,
-                    tty: false,
-                    output_capacity: None,
-                }),
-            )
+            .start_exec(&exec.id, None)
             .await
-            .expect("failed to start exec");
-
-        match res {
-            StartExecResults::Attached { output, .. } => {
-                let stream = output
-                    .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
-                    .map(|chunk| {
-                        let bytes = chunk?.into_bytes();
-                        let str = std::str::from_utf8(bytes.as_ref())
-                            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
-
-                        Ok(str.to_string())
-                    })
-                    .boxed();
+            .expect("failed to start exec")
+        {
+            StartExecResults::Attached { output, input } => (exec.id, output, input),
+            StartExecResults::Detached => panic!("exec {} was started detached", exec.id),
+        }
+    }
 
-                LogStreamAsync::new(stream)
-            }
-            StartExecResults::Detached => unreachable!("detach is false"),
+    /// Returns the exit code of the exec, or `None` if it is still running.
+    pub(crate) async fn exec_exit_code(&self, exec_id: &str) -> Option<i64> {
+        self.bollard
+            .inspect_exec(exec_id)
+            .await
+            .expect("failed to inspect exec")
+            .exit_code
+    }
+
+    /// Returns the paths in the filesystem of the container that differ from its image.
+    pub(crate) async fn changes(&self, id: &str) -> Vec<FileChange> {
+        self.bollard
+            .container_changes(id)
+            .await
+            .unwrap_or_else(|err| panic!("failed to get changes of container {id}: {err}"))
+            .unwrap_or_default()
+            .into_iter()
+            .map(FileChange::from)
+            .collect()
+    }
+
+    /// Returns the filesystem of the container as a tar archive.
+    pub(crate) fn export(&self, id: &str) -> BoxStream<'static, Result<Bytes, io::Error>> {
+        self.bollard
+            .export_container(id)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+            .boxed()
+    }
+
+    /// Returns whether the given path exists in the filesystem of the container.
+    pub(crate) async fn path_exists(&self, container_id: &str, path: &str) -> bool {
+        let options = DownloadFromContainerOptions { path };
+        let first_chunk = self
+            .bollard
+            .download_from_container(container_id, Some(options))
+            .boxed()
+            .next()
+            .await;
+
+        match first_chunk {
+            Some(Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404,
+                ..
+            })) => false,
+            Some(Err(err)) => panic!("failed to look up {path} in container {container_id}: {err}"),
+            Some(Ok(_)) | None => true,
         }
     }
 
+    /// Extracts the tar archive at the root of the filesystem of the container.
+    pub(crate) async fn upload(&self, container_id: &str, archive: Vec<u8>) {
+        let options = UploadToContainerOptions {
+            path: "/",
+            ..Default::default()
+        };
+
+        self.bollard
+            .upload_to_container(container_id, Some(options), archive.into())
+            .await
+            .unwrap_or_else(|err| panic!("failed to copy into container {container_id}: {err}"));
+    }
+
+    /// Returns a tar archive of the file or directory at the given path of the container.
+    pub(crate) async fn download(&self, container_id: &str, path: &str) -> Vec<u8> {
+        let options = DownloadFromContainerOptions { path };
+
+        self.bollard
+            .download_from_container(container_id, Some(options))
+            .try_fold(Vec::new(), |mut archive, chunk| async move {
+                archive.extend_from_slice(&chunk);
+                Ok(archive)
+            })
+            .await
+            .unwrap_or_else(|err| {
+                panic!("failed to copy {path} from container {container_id}: {err}")
+            })
+    }
+
     pub(crate) async fn block_until_ready(&self, id: &str, ready_conditions: &[WaitFor]) {
         log::debug!("Waiting for container {id} to be ready");
 
         for condition in ready_conditions {
-            match condition {
-                WaitFor::StdOutMessage { message } => self
-                    .stdout_logs(id)
-                    .wait_for_message(message)
-                    .await
-                    .unwrap(),
-                WaitFor::StdErrMessage { message } => self
-                    .stderr_logs(id)
-                    .wait_for_message(message)
-                    .await
-                    .unwrap(),
-                WaitFor::Duration { length } => {
-                    tokio::time::sleep(*length).await;
-                }
-                WaitFor::Healthcheck => loop {
-                    use HealthStatusEnum::*;
-
-                    let health_status = self
-                        .inspect(id)
-                        .await
-                        .state
-                        .unwrap_or_else(|| panic!("Container state not available"))
-                        .health
-                        .unwrap_or_else(|| panic!("Health state not available"))
-                        .status;
-
-                    match health_status {
-                        Some(HEALTHY) => break,
-                        None | Some(EMPTY) | Some(NONE) => {
-                            panic!("Healthcheck not configured for container")
-                        }
-                        Some(UNHEALTHY) => panic!("Healthcheck reports unhealthy"),
-                        Some(STARTING) => {
-                            tokio::time::sleep(Duration::from_millis(100)).await;
-                        }
-                    }
-                },
-                WaitFor::Nothing => {}
-            }
+            condition.wait_until_ready(self, id).await;
         }
 
         log::debug!("Container {id} is now ready!");
     }
 
-    fn logs(&self, container_id: &str, desired_log: DesiredLogStream) -> LogStreamAsync<'_> {
+    fn logs(
+        &self,
+        container_id: &str,
+        desired_log: DesiredLogStream,
+        follow: bool,
+    ) -> LogStreamAsync<'_> {
         let (stdout, stderr) = match desired_log {
             DesiredLogStream::Stdout => (true, false),
             DesiredLogStream::Stderr => (false, true),
+            DesiredLogStream::Both => (true, true),
         };
         let options = LogsOptions {
-            follow: true,
+            follow,
             stdout,
             stderr,
             tail: "all".to_owned(),
@@ -201,31 +577,46 @@ impl Client {
             .bollard
             .logs(container_id, Some(options))
             .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
-            .map(|chunk| {
-                let bytes = chunk?.into_bytes();
-                let str = std::str::from_utf8(bytes.as_ref())
-                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
-
-                Ok(String::from(str))
-            })
+            // containers may write anything to their logs, so invalid UTF-8 is replaced
+            .map_ok(|chunk| String::from_utf8_lossy(&chunk.into_bytes()).into_owned())
             .boxed();
 
         LogStreamAsync::new(stream)
     }
 
-    /// Creates a network with given name and returns an ID
-    pub(crate) async fn create_network(&self, name: &str) -> Option<String> {
+    /// Creates a network with given name and subnets and returns an ID
+    ///
+    /// IPv6 is enabled if any of the subnets is an IPv6 subnet.
+    pub(crate) async fn create_network(
+        &self,
+        name: &str,
+        subnets: &[String],
+    ) -> Result<Option<String>, bollard::errors::Error> {
+        let ipam = Ipam {
+            config: (!subnets.is_empty()).then(|| {
+                subnets
+                    .iter()
+                    .map(|subnet| IpamConfig {
+                        subnet: Some(subnet.clone()),
+                        ..Default::default()
+                    })
+                    .collect()
+            }),
+            ..Default::default()
+        };
         let network = self
             .bollard
             .create_network(CreateNetworkOptions {
                 name: name.to_owned(),
                 check_duplicate: true,
+                ipam,
+                enable_ipv6: subnets.iter().any(|subnet| subnet.contains(':')),
+                labels: session::labels().collect(),
                 ..Default::default()
             })
-            .await
-            .unwrap();
+            .await?;
 
-        network.id
+        Ok(network.id)
     }
 
     pub(crate) async fn create_container(
@@ -236,40 +627,134 @@ impl Client {
         self.bollard.create_container(options, config).await
     }
 
-    pub(crate) async fn pull_image(&self, descriptor: &str) {
+    pub(crate) async fn pull_image(
+        &self,
+        descriptor: &str,
+        platform: Option<&str>,
+    ) -> Result<(), bollard::errors::Error> {
+        self.pull_image_with_progress(descriptor, platform, |_| {})
+            .await
+    }
+
+    /// Pulls the image and passes each progress message of the daemon to the callback.
+    ///
+    /// A pull that fails for a transient reason is retried with an exponential backoff, see
+    /// [`env::Config::pull_retries`]. If all retries fail, a Docker Hub image is pulled from the
+    /// registry mirrors, and tagged with its descriptor once one of them succeeds.
+    pub(crate) async fn pull_image_with_progress(
+        &self,
+        descriptor: &str,
+        platform: Option<&str>,
+        mut on_progress: impl FnMut(CreateImageInfo),
+    ) -> Result<(), bollard::errors::Error> {
+        let mut retry = 0;
+        let err = loop {
+            match self
+                .pull_image_once(descriptor, platform, &mut on_progress)
+                .await
+            {
+                Ok(()) => return Ok(()),
+                Err(err) if pull::is_transient(&err) && retry < self.config.pull_retries() => {
+                    retry += 1;
+                    let backoff = pull::backoff(retry);
+                    log::warn!("Failed to pull image {descriptor}, retrying in {backoff:?}: {err}");
+                    tokio::time::sleep(backoff).await;
+                }
+                Err(err) => break err,
+            }
+        };
+        if !pull::is_transient(&err) {
+            return Err(err);
+        }
+
+        for mirror in self.config.registry_mirrors() {
+            let Some((mirrored, repo, tag)) = pull::mirrored(descriptor, mirror) else {
+                break;
+            };
+            log::warn!("Failed to pull image {descriptor}, pulling {mirrored} instead: {err}");
+            let pulled = match self
+                .pull_image_once(&mirrored, platform, &mut on_progress)
+                .await
+            {
+                Ok(()) => {
+                    self.bollard
+                        .tag_image(&mirrored, Some(TagImageOptions { repo, tag }))
+                        .await
+                }
+                Err(err) => Err(err),
+            };
+            match pulled {
+                Ok(()) => return Ok(()),
+                Err(err) => log::warn!("Failed to pull image {mirrored}: {err}"),
+            }
+        }
+        Err(err)
+    }
+
+    async fn pull_image_once(
+        &self,
+        descriptor: &str,
+        platform: Option<&str>,
+        on_progress: &mut impl FnMut(CreateImageInfo),
+    ) -> Result<(), bollard::errors::Error> {
         let pull_options = Some(CreateImageOptions {
             from_image: descriptor,
+            platform: platform.unwrap_or_default(),
             ..Default::default()
         });
         let mut pulling = self.bollard.create_image(pull_options, None, None);
         while let Some(result) = pulling.next().await {
-            result.unwrap_or_else(|err| {
-                panic!("Error pulling the image: '{descriptor}', error: {err}")
-            });
+            on_progress(result?);
         }
+        Ok(())
     }
 
-    pub(crate) async fn network_exists(&self, network: &str) -> bool {
-        let networks = self.bollard.list_networks::<String>(None).await.unwrap();
-        networks
+    pub(crate) async fn network_exists(
+        &self,
+        network: &str,
+    ) -> Result<bool, bollard::errors::Error> {
+        let networks = self.bollard.list_networks::<String>(None).await?;
+        Ok(networks
             .iter()
-            .any(|i| matches!(&i.name, Some(name) if name == network))
+            .any(|i| matches!(&i.name, Some(name) if name == network)))
     }
 
     pub(crate) async fn remove_network(&self, network: &str) {
-        self.bollard
-            .remove_network(network)
-            .await
-            .expect("Failed to remove network");
+        // fails if a container that was kept is still connected to it
+        if let Err(err) = self.bollard.remove_network(network).await {
+            log::warn!("Failed to remove network {network}: {err}");
+        }
+    }
+
+    /// Creates the volume with the labels of the session, unless it already exists.
+    pub(crate) async fn create_volume(&self, name: &str) -> Result<(), bollard::errors::Error> {
+        let options = CreateVolumeOptions {
+            name: name.to_string(),
+            labels: session::labels().collect(),
+            ..Default::default()
+        };
+        self.bollard.create_volume(options).await?;
+        Ok(())
+    }
+
+    pub(crate) async fn remove_volume(&self, name: &str) {
+        match self.bollard.remove_volume(name, None).await {
+            Ok(()) => {}
+            // the volume is only created once a container mounts it
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => {}
+            Err(err) => log::warn!("Failed to remove volume {name}: {err}"),
+        }
     }
 
     pub(crate) async fn docker_host_ip_address(&self) -> String {
         let docker_host = self.config.docker_host();
         match docker_host.scheme() {
-            "tcp" | "http" | "https" => docker_host.host().unwrap().to_string(),
+            "tcp" | "http" | "https" | "ssh" => docker_host.host().unwrap().to_string(),
             "unix" | "npipe" => self
                 .bollard
-                .inspect_network::<String>("bridge", None)
+                .inspect_network::<String>(self.engine().await.default_network(), None)
                 .await
                 .ok()
                 .and_then(|net| net.ipam)
@@ -282,4 +767,33 @@ impl Client {
             _ => unreachable!("docker host is already validated in the config"),
         }
     }
+
+    /// Returns the host on which ports mapped by the docker daemon are reachable.
+    pub(crate) fn docker_hostname(&self) -> String {
+        let docker_host = self.config.docker_host();
+        match docker_host.scheme() {
+            "tcp" | "http" | "https" | "ssh" => docker_host.host_str().unwrap().to_string(),
+            "unix" | "npipe" => "127.0.0.1".to_string(),
+            _ => unreachable!("docker host is already validated in the config"),
+        }
+    }
+}
+
+fn exec_options(cmd: &ExecCommand, attach_stdin: bool) -> CreateExecOptions<String> {
+    let env = cmd
+        .env_vars
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect();
+
+    CreateExecOptions {
+        cmd: Some(cmd.cmd.clone()),
+        attach_stdin: Some(attach_stdin),
+        attach_stdout: Some(!cmd.detach),
+        attach_stderr: Some(!cmd.detach),
+        env: Some(env),
+        user: cmd.user.clone(),
+        working_dir: cmd.working_dir.clone(),
+        ..Default::default()
+    }
 }
@@ -1,31 +1,43 @@
-use crate::core::env;
+use crate::core::{client::ssh::SshTunnel, env, TestcontainersError};
 use bollard::{Docker, API_DEFAULT_VERSION};
 use std::time::Duration;
 
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2 * 60);
 
-pub(super) fn init(config: &env::Config) -> Docker {
+/// Connects to the docker host of the configuration, or returns
+/// [`TestcontainersError::DaemonUnreachable`] if it can't be connected to, e.g. because its TLS
+/// certificates are missing.
+#[cfg_attr(windows, allow(unused_variables))]
+pub(super) fn init(
+    config: &env::Config,
+    ssh_tunnel: Option<&SshTunnel>,
+) -> Result<Docker, TestcontainersError> {
     let host = config.docker_host();
 
-    match host.scheme() {
-        "https" => connect_with_ssl(config),
-        "http" | "tcp" => {
-            if config.tls_verify() {
-                connect_with_ssl(config)
-            } else {
-                Docker::connect_with_http(
-                    host.as_str(),
-                    DEFAULT_TIMEOUT.as_secs(),
-                    API_DEFAULT_VERSION,
-                )
-            }
-        }
+    let connected = match host.scheme() {
+        "https" => return connect_with_ssl(config),
+        "http" | "tcp" if config.tls_verify() => return connect_with_ssl(config),
+        "http" | "tcp" => Docker::connect_with_http(
+            host.as_str(),
+            DEFAULT_TIMEOUT.as_secs(),
+            API_DEFAULT_VERSION,
+        ),
         #[cfg(unix)]
         "unix" => Docker::connect_with_unix(
             host.as_str(),
             DEFAULT_TIMEOUT.as_secs(),
             API_DEFAULT_VERSION,
         ),
+        #[cfg(unix)]
+        "ssh" => Docker::connect_with_unix(
+            ssh_tunnel
+                .expect("the socket of an ssh docker host is forwarded")
+                .socket()
+                .to_str()
+                .expect("the forwarded socket has a valid path"),
+            DEFAULT_TIMEOUT.as_secs(),
+            API_DEFAULT_VERSION,
+        ),
         #[cfg(windows)]
         "npipe" => Docker::connect_with_named_pipe(
             host.as_str(),
@@ -33,21 +45,87 @@ pub(super) fn init(config: &env::Config) -> Docker {
             API_DEFAULT_VERSION,
         ),
         scheme => {
-            panic!("Unsupported scheme: {scheme}");
+            return Err(daemon_unreachable(format!(
+                "unsupported scheme {scheme} of the docker host {host}"
+            )));
         }
-    }
-    .expect("Failed to connect to Docker")
+    };
+    connected.map_err(|err| connect_failed(config, err))
 }
 
-fn connect_with_ssl(config: &env::Config) -> Result<Docker, bollard::errors::Error> {
-    let cert_path = config.cert_path().expect("cert path not found");
+fn connect_failed(config: &env::Config, err: bollard::errors::Error) -> TestcontainersError {
+    daemon_unreachable(format!(
+        "failed to connect to {}: {err}",
+        config.docker_host()
+    ))
+}
+
+fn daemon_unreachable(message: String) -> TestcontainersError {
+    TestcontainersError::DaemonUnreachable { message }
+}
+
+/// Connects with mutual TLS, e.g. to a remote daemon or a Docker-in-Docker service of a CI
+/// pipeline, authenticating with the client certificate of the cert path.
+fn connect_with_ssl(config: &env::Config) -> Result<Docker, TestcontainersError> {
+    let cert_path = config.cert_path().ok_or_else(|| {
+        daemon_unreachable(format!(
+            "TLS is enabled for the docker host {}, but no cert path was found; set DOCKER_CERT_PATH to the directory with the certificates",
+            config.docker_host()
+        ))
+    })?;
+    let [key, cert, ca] = ["key.pem", "cert.pem", "ca.pem"].map(|file| cert_path.join(file));
+    // the key and certificate are only read during the handshake, which hides why it failed
+    for file in [&key, &cert, &ca] {
+        if !file.is_file() {
+            return Err(daemon_unreachable(format!(
+                "{} does not exist, but TLS is enabled for the docker host {}; DOCKER_CERT_PATH has to contain ca.pem, cert.pem and key.pem",
+                file.display(),
+                config.docker_host()
+            )));
+        }
+    }
 
     Docker::connect_with_ssl(
         config.docker_host().as_str(),
-        &cert_path.join("key.pem"),
-        &cert_path.join("cert.pem"),
-        &cert_path.join("ca.pem"),
+        &key,
+        &cert,
+        &ca,
         DEFAULT_TIMEOUT.as_secs(),
         API_DEFAULT_VERSION,
     )
+    .map_err(|err| connect_failed(config, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::env::GetEnvValue;
+
+    struct FakeEnvMissingCertificates;
+
+    impl GetEnvValue for FakeEnvMissingCertificates {
+        fn get_env_value(key: &str) -> Option<String> {
+            match key {
+                "DOCKER_HOST" => Some("tcp://docker:2376".to_owned()),
+                "DOCKER_TLS_VERIFY" => Some("1".to_owned()),
+                "DOCKER_CERT_PATH" => Some("/nonexistent/testcontainers/certs".to_owned()),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn missing_certificates_make_daemon_unreachable() {
+        let config = env::Config::load_from_env_config::<FakeEnvMissingCertificates>();
+
+        let err = init(&config, None).unwrap_err();
+        assert!(
+            matches!(
+                &err,
+                TestcontainersError::DaemonUnreachable { message }
+                    if message.starts_with("/nonexistent/testcontainers/certs/key.pem does not exist")
+            ),
+            "unexpected error: {err}"
+        );
+    }
 }
@@ -0,0 +1,54 @@
+use bollard::Docker;
+
+/// The container engine that serves the docker API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Engine {
+    Docker,
+    /// Podman, which runs its containers as the user that runs the tests if it is rootless.
+    Podman {
+        rootless: bool,
+    },
+}
+
+impl Engine {
+    /// Detects the engine from the components it reports in its version. Assumes docker if
+    /// the version can't be read, e.g. because the daemon is not reachable.
+    pub(super) async fn detect(bollard: &Docker) -> Self {
+        let components = match bollard.version().await {
+            Ok(version) => version.components.unwrap_or_default(),
+            Err(err) => {
+                log::debug!("Failed to detect the container engine, assuming docker: {err}");
+                return Engine::Docker;
+            }
+        };
+        if !components
+            .iter()
+            .any(|component| component.name.contains("Podman"))
+        {
+            return Engine::Docker;
+        }
+
+        let rootless = bollard
+            .info()
+            .await
+            .ok()
+            .and_then(|info| info.security_options)
+            .unwrap_or_default()
+            .iter()
+            .any(|option| option.contains("name=rootless"));
+        log::debug!("Detected Podman as the container engine (rootless: {rootless})");
+        Engine::Podman { rootless }
+    }
+
+    pub(crate) fn is_podman(self) -> bool {
+        matches!(self, Engine::Podman { .. })
+    }
+
+    /// Returns the name of the network that containers join unless configured otherwise.
+    pub(crate) fn default_network(self) -> &'static str {
+        match self {
+            Engine::Docker => "bridge",
+            Engine::Podman { .. } => "podman",
+        }
+    }
+}
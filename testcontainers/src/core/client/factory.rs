@@ -1,4 +1,4 @@
-use crate::core::client::Client;
+use crate::core::{client::Client, TestcontainersError};
 use std::sync::{Arc, OnceLock, Weak};
 use tokio::sync::Mutex;
 
@@ -9,8 +9,20 @@ static DOCKER_CLIENT: OnceLock<Mutex<Weak<Client>>> = OnceLock::new();
 
 impl Client {
     /// Returns a client instance, reusing already created or initializing a new one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the client can't be initialized, see [`Client::try_lazy_client`].
     // We don't expose this function to the public API for now. We can do it later if needed.
     pub(crate) async fn lazy_client() -> Arc<Client> {
+        Self::try_lazy_client()
+            .await
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Returns a client instance like [`Client::lazy_client`], or an error if a new one can't be
+    /// initialized, e.g. because the TLS certificates of the docker host are missing.
+    pub(crate) async fn try_lazy_client() -> Result<Arc<Client>, TestcontainersError> {
         let mut guard = DOCKER_CLIENT
             .get_or_init(|| Mutex::new(Weak::new()))
             .lock()
@@ -18,12 +30,12 @@ impl Client {
         let maybe_client = guard.upgrade();
 
         if let Some(client) = maybe_client {
-            client
+            Ok(client)
         } else {
-            let client = Arc::new(Client::new().await);
+            let client = Arc::new(Client::new().await?);
             *guard = Arc::downgrade(&client);
 
-            client
+            Ok(client)
         }
     }
 }
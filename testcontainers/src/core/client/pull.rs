@@ -0,0 +1,113 @@
+use std::time::Duration;
+
+/// The delay before the first retry of a pull, which doubles with every further retry.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Returns how long to wait before the retry, counted from 1.
+pub(super) fn backoff(retry: u32) -> Duration {
+    INITIAL_BACKOFF
+        .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+        .min(MAX_BACKOFF)
+}
+
+/// Whether the pull failed for a reason that may go away, like a registry that responds with a
+/// 5xx status or times out, as opposed to e.g. an image that doesn't exist.
+pub(super) fn is_transient(err: &bollard::errors::Error) -> bool {
+    match err {
+        bollard::errors::Error::DockerResponseServerError { status_code, .. } => {
+            *status_code >= 500
+        }
+        bollard::errors::Error::DockerStreamError { .. }
+        | bollard::errors::Error::RequestTimeoutError => true,
+        _ => false,
+    }
+}
+
+/// Returns the descriptor of a Docker Hub image on the registry mirror, e.g.
+/// `mirror.gcr.io/library/redis:7` for `redis:7`, and the repository and tag to tag the pulled
+/// image with, so that it can be found by its original descriptor.
+///
+/// Returns `None` for images of other registries, which mirrors don't serve, and for images
+/// referenced by digest, which can't be tagged.
+pub(super) fn mirrored(descriptor: &str, mirror: &str) -> Option<(String, String, String)> {
+    if descriptor.contains('@') {
+        return None;
+    }
+    let (repo, tag) = match descriptor.rsplit_once(':') {
+        Some((repo, tag)) if !tag.contains('/') => (repo, tag),
+        _ => (descriptor, "latest"),
+    };
+
+    let path = repo.strip_prefix("docker.io/").unwrap_or(repo);
+    let path = match path.split_once('/') {
+        // the first component names a registry if it looks like a host
+        Some((first, _)) if first.contains(['.', ':']) || first == "localhost" => return None,
+        Some(_) => path.to_string(),
+        None => format!("library/{path}"),
+    };
+    let mirror = mirror.trim_end_matches('/');
+
+    Some((
+        format!("{mirror}/{path}:{tag}"),
+        repo.to_string(),
+        tag.to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        assert_eq!(backoff(1), Duration::from_secs(1));
+        assert_eq!(backoff(2), Duration::from_secs(2));
+        assert_eq!(backoff(4), Duration::from_secs(8));
+        assert_eq!(backoff(40), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn server_errors_are_transient() {
+        let unavailable = bollard::errors::Error::DockerResponseServerError {
+            status_code: 500,
+            message: "received unexpected HTTP status: 503 Service Unavailable".to_string(),
+        };
+        let not_found = bollard::errors::Error::DockerResponseServerError {
+            status_code: 404,
+            message: "manifest unknown".to_string(),
+        };
+
+        assert!(is_transient(&unavailable));
+        assert!(!is_transient(&not_found));
+    }
+
+    #[test]
+    fn docker_hub_images_are_mirrored() {
+        let mirrored = |descriptor| mirrored(descriptor, "mirror.gcr.io/");
+
+        assert_eq!(
+            mirrored("redis:7"),
+            Some((
+                "mirror.gcr.io/library/redis:7".to_string(),
+                "redis".to_string(),
+                "7".to_string()
+            ))
+        );
+        assert_eq!(
+            mirrored("testcontainers/ryuk"),
+            Some((
+                "mirror.gcr.io/testcontainers/ryuk:latest".to_string(),
+                "testcontainers/ryuk".to_string(),
+                "latest".to_string()
+            ))
+        );
+        assert_eq!(
+            mirrored("docker.io/library/redis:7").map(|(descriptor, ..)| descriptor),
+            Some("mirror.gcr.io/library/redis:7".to_string())
+        );
+        assert_eq!(mirrored("ghcr.io/owner/app:1.0"), None);
+        assert_eq!(mirrored("localhost:5000/app:1.0"), None);
+        assert_eq!(mirrored("alpine@sha256:c5b1261d"), None);
+    }
+}
@@ -0,0 +1,163 @@
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    time::Duration,
+};
+
+use url::Url;
+
+use crate::core::{env, runtime, TestcontainersError};
+
+/// How long to wait for the connection to the docker host.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// An `ssh` process that forwards a local unix socket to the docker socket of an
+/// `ssh://[user@]host[:port]` docker host, like the docker CLI does.
+///
+/// The process is killed once the tunnel is dropped, along with the client that uses it.
+pub(super) struct SshTunnel {
+    process: Child,
+    socket: PathBuf,
+}
+
+impl SshTunnel {
+    /// Starts `ssh` and waits until the local socket is forwarded.
+    ///
+    /// The `ssh` command of the system is used, so the host, user and keys are taken from its
+    /// configuration and agent as usual. It runs in batch mode, as nobody can answer a password
+    /// prompt of a test.
+    ///
+    /// Returns [`TestcontainersError::DaemonUnreachable`] if `ssh` can't be started or exits
+    /// before the socket is forwarded, e.g. because the host refused the key.
+    pub(super) async fn open(config: &env::Config) -> Result<Self, TestcontainersError> {
+        let host = config.docker_host();
+        let socket = std::env::temp_dir().join(format!(
+            "testcontainers-ssh-{}.sock",
+            ulid::Ulid::new().to_string().to_lowercase()
+        ));
+        let remote_socket = config.docker_socket();
+
+        log::debug!(
+            "Forwarding {} to {remote_socket} on {host} over ssh",
+            socket.display()
+        );
+        let process = command(&host, &socket, &remote_socket)?
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| {
+                daemon_unreachable(format!("failed to run `ssh` to connect to {host}: {err}"))
+            })?;
+        let mut tunnel = SshTunnel { process, socket };
+
+        runtime::compat(tunnel.wait_until_forwarded(&host)).await?;
+        Ok(tunnel)
+    }
+
+    /// Returns the local socket that is forwarded to the docker socket of the host.
+    #[cfg_attr(windows, allow(dead_code))]
+    pub(super) fn socket(&self) -> &Path {
+        &self.socket
+    }
+
+    async fn wait_until_forwarded(&mut self, host: &Url) -> Result<(), TestcontainersError> {
+        let started = tokio::time::Instant::now();
+        while !self.socket.exists() {
+            if let Ok(Some(status)) = self.process.try_wait() {
+                let mut stderr = String::new();
+                if let Some(mut pipe) = self.process.stderr.take() {
+                    let _ = pipe.read_to_string(&mut stderr);
+                }
+                return Err(daemon_unreachable(format!(
+                    "`ssh` to {host} exited with {status}: {}",
+                    stderr.trim()
+                )));
+            }
+            if started.elapsed() > CONNECT_TIMEOUT {
+                return Err(daemon_unreachable(format!(
+                    "`ssh` did not connect to {host} within {CONNECT_TIMEOUT:?}"
+                )));
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for SshTunnel {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+        let _ = std::fs::remove_file(&self.socket);
+    }
+}
+
+fn daemon_unreachable(message: String) -> TestcontainersError {
+    TestcontainersError::DaemonUnreachable { message }
+}
+
+/// Returns the `ssh` command that forwards the local socket to the remote one.
+fn command(host: &Url, socket: &Path, remote_socket: &str) -> Result<Command, TestcontainersError> {
+    let host_name = host
+        .host_str()
+        .ok_or_else(|| daemon_unreachable(format!("docker host {host} has no host name")))?;
+
+    let mut command = Command::new("ssh");
+    command
+        .arg("-N")
+        .args(["-o", "BatchMode=yes"])
+        .args(["-o", "ExitOnForwardFailure=yes"])
+        .arg("-L")
+        .arg(format!("{}:{remote_socket}", socket.display()));
+    if !host.username().is_empty() {
+        command.args(["-l", host.username()]);
+    }
+    if let Some(port) = host.port() {
+        command.args(["-p", &port.to_string()]);
+    }
+    command.arg("--").arg(host_name);
+    Ok(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn forwards_socket_to_docker_host() {
+        let host = Url::from_str("ssh://builder@build-server:2222").unwrap();
+        let command = command(
+            &host,
+            Path::new("/tmp/testcontainers.sock"),
+            "/var/run/docker.sock",
+        )
+        .unwrap();
+
+        let args: Vec<_> = command
+            .get_args()
+            .map(|arg| arg.to_str().unwrap())
+            .collect();
+        assert_eq!(
+            args,
+            [
+                "-N",
+                "-o",
+                "BatchMode=yes",
+                "-o",
+                "ExitOnForwardFailure=yes",
+                "-L",
+                "/tmp/testcontainers.sock:/var/run/docker.sock",
+                "-l",
+                "builder",
+                "-p",
+                "2222",
+                "--",
+                "build-server",
+            ]
+        );
+    }
+}
@@ -1,27 +1,58 @@
 use crate::{
     core::{
         client::{Client, DesiredLogStream},
-        env, macros,
-        network::Network,
+        containers::ExecSessionAsync,
+        copy,
+        logs::{self, LogDump, LogLine, LogStreamAsync, WaitError},
+        network::CreatedNetwork,
         ports::Ports,
-        ContainerState, ExecCommand, WaitFor,
+        removal, runtime, wait, ContainerDied, ContainerEvent, ContainerInfo, ContainerState,
+        ContainerStats, ExecCommand, ExecResult, ExitStatus, FileChange, HealthStatus, PortError,
+        ProcessList, RemovePolicy, ResourceLimits, Signal, Snapshot, StartupError, StartupFailure,
+        WaitFor,
     },
-    Image, RunnableImage,
+    GenericImage, Image, RunnableImage,
+};
+use bollard::container::LogOutput;
+use futures::{
+    future::{BoxFuture, Either},
+    stream::BoxStream,
+    FutureExt, Stream, StreamExt, TryStreamExt,
+};
+use std::{
+    any::Any,
+    fmt,
+    future::Future,
+    io,
+    net::IpAddr,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncWrite, AsyncWriteExt},
+    time::Instant,
 };
-use std::{fmt, net::IpAddr, str::FromStr, sync::Arc};
-use tokio::runtime::RuntimeFlavor;
 
 /// Represents a running docker container that has been started using an async client.
 ///
 /// Containers have a [`custom destructor`][drop_impl] that removes them as soon as they
 /// go out of scope. However, async drop is not available in rust yet. This implementation
-/// is using block_on.
+/// is using block_on. See [`RemovePolicy`] to keep the containers of failed tests instead.
+///
+/// The removal runs on a runtime of testcontainers, so dropping a container doesn't deadlock a
+/// `current_thread` runtime, but the thread of the caller is blocked until it is done. To clean
+/// up without blocking, await [`ContainerAsync::stop`] and [`ContainerAsync::rm`] at the end of
+/// the test, or run the test in [`ContainerAsync::remove_after`], which also cleans up after a
+/// panic.
 ///
 /// ```rust
 /// use testcontainers::*;
 /// #[tokio::test]
 /// async fn a_test() {
-///     let container = MyImage::default().start().await;
+///     let container = MyImage::default().start().await.unwrap();
 ///     // Docker container is stopped/removed at the end of this scope.
 /// }
 /// ```
@@ -31,8 +62,9 @@ pub struct ContainerAsync<I: Image> {
     id: String,
     image: RunnableImage<I>,
     pub(super) docker_client: Arc<Client>,
-    #[cfg_attr(not(feature = "blocking"), allow(dead_code))]
-    pub(super) network: Option<Arc<Network>>,
+    /// Keeps the network alive until the container is removed.
+    #[allow(dead_code)]
+    network: Option<Arc<CreatedNetwork>>,
     dropped: bool,
 }
 
@@ -41,22 +73,21 @@ where
     I: Image,
 {
     /// Constructs a new container given an id, a docker client and the image.
-    /// ContainerAsync::new().await
-    pub(crate) async fn new(
+    ///
+    /// The container is expected to be ready, see [`ContainerAsync::block_until_ready`].
+    pub(crate) fn new(
         id: String,
         docker_client: Arc<Client>,
         image: RunnableImage<I>,
-        network: Option<Arc<Network>>,
+        network: Option<Arc<CreatedNetwork>>,
     ) -> ContainerAsync<I> {
-        let container = ContainerAsync {
+        ContainerAsync {
             id,
             image,
             docker_client,
             network,
             dropped: false,
-        };
-        container.block_until_ready().await;
-        container
+        }
     }
 
     /// Returns the id of this container.
@@ -81,8 +112,77 @@ where
         self.image.args()
     }
 
+    /// Returns a stream of the lines the container writes to stdout, from its start on.
+    ///
+    /// The stream follows the logs as they are written, and ends once the container stops.
+    pub fn stdout_lines(&self) -> impl Stream<Item = Result<LogLine, io::Error>> + Send + 'static {
+        runtime::compat(
+            self.docker_client
+                .log_lines(&self.id, DesiredLogStream::Stdout)
+                .map_ok(|(_, line)| line),
+        )
+    }
+
+    /// Returns a stream of the lines the container writes to stderr, from its start on.
+    ///
+    /// The stream follows the logs as they are written, and ends once the container stops.
+    pub fn stderr_lines(&self) -> impl Stream<Item = Result<LogLine, io::Error>> + Send + 'static {
+        runtime::compat(
+            self.docker_client
+                .log_lines(&self.id, DesiredLogStream::Stderr)
+                .map_ok(|(_, line)| line),
+        )
+    }
+
+    /// Returns a snapshot of the resource usage of the container.
+    ///
+    /// The CPU usage is sampled over about a second, so this method takes that long.
+    pub async fn stats(&self) -> ContainerStats {
+        runtime::compat(self.docker_client.stats(&self.id, false).next())
+            .await
+            .unwrap_or_else(|| panic!("no stats of container {} available", self.id))
+            .unwrap_or_else(|err| panic!("failed to get stats of container {}: {err}", self.id))
+    }
+
+    /// Returns a stream of the resource usage of the container, sampled every second until the
+    /// container stops.
+    pub fn stats_stream(
+        &self,
+    ) -> impl Stream<Item = Result<ContainerStats, io::Error>> + Send + 'static {
+        runtime::compat(self.docker_client.stats(&self.id, true))
+    }
+
+    /// Returns a stream of the lifecycle events of the container that happen from now on, e.g.
+    /// to assert that it was killed because it ran out of memory, or to react when it dies.
+    pub fn events(&self) -> impl Stream<Item = Result<ContainerEvent, io::Error>> + Send + 'static {
+        runtime::compat(self.docker_client.events(&self.id))
+    }
+
+    /// Returns how the container was actually created and its current state, as reported by
+    /// `docker inspect`.
+    pub async fn inspect(&self) -> ContainerInfo {
+        runtime::compat(self.docker_client.inspect(&self.id))
+            .await
+            .into()
+    }
+
+    /// Returns the result of the healthcheck of the container, e.g. to assert the transitions
+    /// of its own healthcheck. The state is [`HealthState::None`] if it has no healthcheck.
+    ///
+    /// [`HealthState::None`]: crate::core::HealthState::None
+    pub async fn health(&self) -> HealthStatus {
+        runtime::compat(self.docker_client.inspect(&self.id))
+            .await
+            .state
+            .and_then(|state| state.health)
+            .unwrap_or_default()
+            .into()
+    }
+
+    /// Returns all ports of the container that are mapped to ports on the host, e.g. to
+    /// discover everything that was exposed.
     pub async fn ports(&self) -> Ports {
-        self.docker_client.ports(&self.id).await
+        runtime::compat(self.docker_client.ports(&self.id)).await
     }
 
     /// Returns the mapped host port for an internal port of this docker container, on the host's
@@ -97,11 +197,9 @@ where
     /// Testcontainers is designed to be used in tests only. If a certain port is not mapped, the container
     /// is unlikely to be useful.
     pub async fn get_host_port_ipv4(&self, internal_port: u16) -> u16 {
-        self.docker_client
-            .ports(&self.id)
+        self.try_get_host_port_ipv4(internal_port)
             .await
-            .map_to_host_port_ipv4(internal_port)
-            .unwrap_or_else(|| {
+            .unwrap_or_else(|_| {
                 panic!(
                     "container {} does not expose (IPV4) port {}",
                     self.id, internal_port
@@ -121,11 +219,9 @@ where
     /// Testcontainers is designed to be used in tests only. If a certain port is not mapped, the container
     /// is unlikely to be useful.
     pub async fn get_host_port_ipv6(&self, internal_port: u16) -> u16 {
-        self.docker_client
-            .ports(&self.id)
+        self.try_get_host_port_ipv6(internal_port)
             .await
-            .map_to_host_port_ipv6(internal_port)
-            .unwrap_or_else(|| {
+            .unwrap_or_else(|_| {
                 panic!(
                     "container {} does not expose (IPV6) port {}",
                     self.id, internal_port
@@ -133,9 +229,32 @@ where
             })
     }
 
+    /// Returns the mapped host port for an internal port of this docker container, on the host's
+    /// IPv4 interfaces, or an error if it is not mapped, e.g. for fixtures that report their
+    /// own errors instead of panicking.
+    pub async fn try_get_host_port_ipv4(&self, internal_port: u16) -> Result<u16, PortError> {
+        self.ports()
+            .await
+            .map_to_host_port_ipv4(internal_port)
+            .ok_or(PortError::NotMappedIpv4 {
+                port: internal_port,
+            })
+    }
+
+    /// Returns the mapped host port for an internal port of this docker container, on the host's
+    /// IPv6 interfaces, or an error if it is not mapped.
+    pub async fn try_get_host_port_ipv6(&self, internal_port: u16) -> Result<u16, PortError> {
+        self.ports()
+            .await
+            .map_to_host_port_ipv6(internal_port)
+            .ok_or(PortError::NotMappedIpv6 {
+                port: internal_port,
+            })
+    }
+
     /// Returns the bridge ip address of docker container as specified in NetworkSettings.Networks.IPAddress
     pub async fn get_bridge_ip_address(&self) -> IpAddr {
-        let result = self.docker_client.inspect(&self.id).await;
+        let result = runtime::compat(self.docker_client.inspect(&self.id)).await;
 
         let settings = result
             .network_settings
@@ -161,66 +280,507 @@ where
             .unwrap_or_else(|_| panic!("container {} has invalid bridge IP", self.id))
     }
 
+    /// Returns the ip address of the container in the given network, e.g. to configure a
+    /// sibling container in a user-defined network when DNS aliases are not an option.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the container can't be inspected, is not connected to the network, or has no ip
+    /// address in it.
+    pub async fn get_network_ip(&self, network_name: &str) -> IpAddr {
+        self.try_network_ip(network_name)
+            .await
+            .unwrap_or_else(|message| panic!("{message}"))
+    }
+
+    /// Returns the ip address of the container in the network like
+    /// [`ContainerAsync::get_network_ip`], but returns an error message instead of panicking.
+    pub(crate) async fn try_network_ip(&self, network_name: &str) -> Result<IpAddr, String> {
+        let info: ContainerInfo = runtime::compat(self.docker_client.try_inspect(&self.id))
+            .await
+            .map_err(|err| format!("failed to inspect container {}: {err}", self.id))?
+            .into();
+        let mut networks = info.networks;
+
+        let network = networks.remove(network_name).ok_or_else(|| {
+            format!(
+                "container {} is not connected to network {network_name}",
+                self.id
+            )
+        })?;
+
+        network.ip_address.ok_or_else(|| {
+            format!(
+                "container {} has no ip address in network {network_name}",
+                self.id
+            )
+        })
+    }
+
+    /// Returns the ip address of the gateway of the network of the container, which is the
+    /// default bridge unless [`RunnableImage::with_network`] is used.
+    ///
+    /// Services running on the host can be reached at this address, e.g. when the daemon does
+    /// not support `host-gateway` in [`RunnableImage::with_host`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the network of the container has no gateway, e.g. with
+    /// [`RunnableImage::with_network_mode`].
+    pub async fn get_gateway_ip(&self) -> IpAddr {
+        let network_name = self.image.network().as_deref().unwrap_or("bridge");
+
+        self.inspect()
+            .await
+            .networks
+            .remove(network_name)
+            .and_then(|network| network.gateway)
+            .unwrap_or_else(|| {
+                panic!(
+                    "container {} has no gateway in network {network_name}",
+                    self.id
+                )
+            })
+    }
+
     /// Returns the host ip address of docker container
     pub async fn get_host_ip_address(&self) -> IpAddr {
-        self.docker_client
-            .docker_host_ip_address()
+        runtime::compat(self.docker_client.docker_host_ip_address())
             .await
             .parse()
             .expect("invalid host IP")
     }
 
+    /// Executes the command inside the container and returns once it meets its ready
+    /// condition, while the command may keep running, e.g. a server that logs that it has
+    /// started. See [`ContainerAsync::exec_to_completion`] to wait for the command to finish
+    /// and get its output and exit code instead.
+    ///
+    /// The container ready conditions of the command are awaited as well.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the command can't be executed, or if its output ends before it meets its
+    /// ready condition.
     pub async fn exec(&self, cmd: ExecCommand) {
-        let ExecCommand {
-            cmd,
-            container_ready_conditions,
-            cmd_ready_condition,
-        } = cmd;
-
-        log::debug!("Executing command {:?}", cmd);
-
-        let desired_log = if let WaitFor::StdErrMessage { .. } = &cmd_ready_condition {
-            DesiredLogStream::Stderr
-        } else {
-            DesiredLogStream::Stdout
-        };
+        if let Err(message) = self.try_exec(&cmd).await {
+            panic!("{message}");
+        }
+    }
 
-        let output = self.docker_client.exec(&self.id, cmd, desired_log).await;
-        self.docker_client
-            .block_until_ready(self.id(), &container_ready_conditions)
-            .await;
+    /// Executes the command like [`ContainerAsync::exec`], but returns an error message
+    /// instead of panicking.
+    pub(crate) async fn try_exec(&self, cmd: &ExecCommand) -> Result<(), String> {
+        log::debug!("Executing command {:?}", cmd.cmd);
 
-        match cmd_ready_condition {
-            WaitFor::StdOutMessage { message } | WaitFor::StdErrMessage { message } => {
-                output.wait_for_message(&message).await.unwrap();
-            }
-            WaitFor::Duration { length } => {
-                tokio::time::sleep(length).await;
+        runtime::compat(async {
+            let (_, output) = self
+                .docker_client
+                .start_exec(&self.id, cmd)
+                .await
+                .map_err(|err| {
+                    format!(
+                        "failed to execute command {:?} in container {}: {err}",
+                        cmd.cmd, self.id
+                    )
+                })?;
+            // waiting for a condition panics if it can't be met
+            AssertUnwindSafe(
+                self.docker_client
+                    .block_until_ready(self.id(), &cmd.container_ready_conditions),
+            )
+            .catch_unwind()
+            .await
+            .map_err(|panic| panic_message(panic.as_ref()))?;
+
+            match (&cmd.cmd_ready_condition, output) {
+                (WaitFor::Duration { length }, _) => {
+                    tokio::time::sleep(*length).await;
+                    Ok(())
+                }
+                (condition, _) if !condition.is_log_based() => Ok(()),
+                (condition, None) => Err(format!(
+                    "the output of detached command {:?} is not available, it can't meet {condition:?}",
+                    cmd.cmd
+                )),
+                (condition, Some(output)) => {
+                    wait_for_output(output, condition).await.map_err(|err| {
+                        format!(
+                            "output of command {:?} in container {} does not meet {condition:?}: {err:?}",
+                            cmd.cmd, self.id
+                        )
+                    })
+                }
             }
-            _ => {}
+        })
+        .await
+    }
+
+    /// Executes the command inside the container, waits for it to finish and returns its
+    /// output and exit code, e.g. to assert on the result of a CLI. A detached command is only
+    /// started, see [`ExecCommand::with_detach`].
+    ///
+    /// The container ready conditions of the command are awaited while it runs, its own ready
+    /// condition is checked against its whole output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the command can't be executed, or if its output does not meet its ready
+    /// condition.
+    pub async fn exec_to_completion(&self, cmd: ExecCommand) -> ExecResult {
+        log::debug!("Executing command {:?} to completion", cmd.cmd);
+
+        let (result, ()) = runtime::compat(futures::future::join(
+            self.docker_client.exec(&self.id, &cmd),
+            self.docker_client
+                .block_until_ready(self.id(), &cmd.container_ready_conditions),
+        ))
+        .await;
+
+        if let WaitFor::Duration { length } = cmd.cmd_ready_condition {
+            runtime::compat(async { tokio::time::sleep(length).await }).await;
+        } else if cmd.detach && cmd.cmd_ready_condition.is_log_based() {
+            panic!(
+                "the output of detached command {:?} is not available, it can't meet {:?}",
+                cmd.cmd, cmd.cmd_ready_condition
+            );
+        } else if !output_meets_condition(&result, &cmd.cmd_ready_condition) {
+            panic!(
+                "output of command {:?} in container {} does not meet {:?}",
+                cmd.cmd, self.id, cmd.cmd_ready_condition
+            );
         }
+
+        result
     }
 
+    /// Starts the command inside the container with its stdin attached, e.g. to test a CLI or
+    /// a REPL-style service, and returns a session to write to its stdin and read its output
+    /// while it runs.
+    ///
+    /// The ready conditions of the command are not awaited.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the command is detached, see [`ExecCommand::with_detach`], or can't be
+    /// executed.
+    pub async fn exec_interactive(&self, cmd: ExecCommand) -> ExecSessionAsync {
+        log::debug!("Executing interactive command {:?}", cmd.cmd);
+
+        if cmd.detach {
+            panic!("detached command {:?} can't be interactive", cmd.cmd);
+        }
+
+        let (id, output, input) =
+            runtime::compat(self.docker_client.exec_attached(&self.id, &cmd)).await;
+        ExecSessionAsync::new(id, self.docker_client.clone(), output, input)
+    }
+
+    /// Returns the paths in the filesystem of the container that were added, modified or
+    /// deleted compared to its image, e.g. to verify which paths an application writes to.
+    /// Volumes and other mounts are not included.
+    pub async fn changes(&self) -> Vec<FileChange> {
+        runtime::compat(self.docker_client.changes(&self.id)).await
+    }
+
+    /// Writes the whole filesystem of the container as a tar archive to the writer, e.g. to
+    /// archive a snapshot of a failed test container as a CI artifact. Volumes and other
+    /// mounts are not included.
+    pub async fn export(&self, mut writer: impl AsyncWrite + Unpin) -> io::Result<()> {
+        let mut archive = runtime::compat(self.docker_client.export(&self.id));
+        while let Some(chunk) = archive.next().await {
+            writer.write_all(&chunk?).await?;
+        }
+        writer.flush().await
+    }
+
+    /// Copies a file of the host into the running container, e.g. to inject fixtures after
+    /// the container has started. The destination is the absolute path of the file inside the
+    /// container; missing parent directories are created.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the destination is not absolute, or if the file can't be read or copied.
+    pub async fn copy_file_to(&self, source: impl AsRef<Path>, destination: impl Into<String>) {
+        let (source, destination) = (source.as_ref(), destination.into());
+        check_copy_destination(&destination);
+
+        let archive = copy::tar_file(source, &destination)
+            .unwrap_or_else(|err| panic!("failed to read {}: {err}", source.display()));
+        runtime::compat(self.docker_client.upload(&self.id, archive)).await;
+    }
+
+    /// Copies the content of a directory of the host into the given directory of the running
+    /// container, which is created if it does not exist.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the destination is not absolute, or if the directory can't be read or copied.
+    pub async fn copy_dir_to(&self, source: impl AsRef<Path>, destination: impl Into<String>) {
+        let (source, destination) = (source.as_ref(), destination.into());
+        check_copy_destination(&destination);
+
+        let archive = copy::tar_dir(source, &destination)
+            .unwrap_or_else(|err| panic!("failed to read {}: {err}", source.display()));
+        runtime::compat(self.docker_client.upload(&self.id, archive)).await;
+    }
+
+    /// Returns the content of a file of the container, e.g. a report the application has
+    /// written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path does not exist or is not a regular file.
+    pub async fn read_file(&self, path: impl AsRef<str>) -> Vec<u8> {
+        let path = path.as_ref();
+        let archive = runtime::compat(self.docker_client.download(&self.id, path)).await;
+
+        copy::read_file(&archive)
+            .unwrap_or_else(|err| panic!("failed to read {path} of container {}: {err}", self.id))
+    }
+
+    /// Copies the content of a directory of the container into the given directory of the host,
+    /// which is created if it does not exist.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path does not exist in the container, or if the content can't be written.
+    pub async fn copy_dir_from(&self, path: impl AsRef<str>, destination: impl AsRef<Path>) {
+        let (path, destination) = (path.as_ref(), destination.as_ref());
+        let archive = runtime::compat(self.docker_client.download(&self.id, path)).await;
+
+        copy::unpack_dir(&archive, destination).unwrap_or_else(|err| {
+            panic!(
+                "failed to copy {path} of container {} to {}: {err}",
+                self.id,
+                destination.display()
+            )
+        });
+    }
+
+    /// Starts the container again after it was stopped, and executes the commands of
+    /// [`Image::exec_after_start`].
+    ///
+    /// The ready conditions of the image are not awaited again, as conditions on the logs would
+    /// already be met by the logs of the previous run.
     pub async fn start(&self) {
-        self.docker_client.start(&self.id).await;
-        for cmd in self
-            .image
-            .exec_after_start(ContainerState::new(self.ports().await))
-        {
-            self.exec(cmd).await;
+        runtime::compat(self.docker_client.start(&self.id)).await;
+        self.exec_after_start().await;
+    }
+
+    /// Blocks until the main process of the container exits, e.g. of a batch job or a
+    /// migration, and returns its exit status. Returns immediately if it already exited.
+    pub async fn wait(&self) -> ExitStatus {
+        ExitStatus::new(runtime::compat(self.docker_client.wait_for_exit(&self.id)).await)
+    }
+
+    /// Waits until the container exits, e.g. because it crashed, and returns its exit code and
+    /// the last lines of its logs.
+    ///
+    /// Racing the future against a test surfaces the crash of a dependency, instead of an
+    /// opaque connection error later on, see [`ContainerAsync::guard`].
+    pub async fn exited(&self) -> ContainerDied {
+        runtime::compat(async {
+            let exit_code = self.docker_client.wait_for_exit(&self.id).await;
+            log::debug!("Docker container {} exited with code {exit_code}", self.id);
+
+            ContainerDied {
+                container_id: self.id.clone(),
+                exit_code,
+                stdout_tail: StartupError::tail(
+                    &self
+                        .docker_client
+                        .collected_logs(&self.id, DesiredLogStream::Stdout)
+                        .await,
+                ),
+                stderr_tail: StartupError::tail(
+                    &self
+                        .docker_client
+                        .collected_logs(&self.id, DesiredLogStream::Stderr)
+                        .await,
+                ),
+            }
+        })
+        .await
+    }
+
+    /// Runs the future, e.g. the body of a test, while watching the container.
+    ///
+    /// Guards can be nested to watch several containers:
+    ///
+    /// ```rust,no_run
+    /// use testcontainers::{runners::AsyncRunner, GenericImage};
+    ///
+    /// async fn a_test() {
+    ///     let postgres = GenericImage::new("postgres", "16-alpine")
+    ///         .start()
+    ///         .await
+    ///         .unwrap();
+    ///     let kafka = GenericImage::new("apache/kafka", "3.7.0")
+    ///         .start()
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     postgres
+    ///         .guard(kafka.guard(async {
+    ///             // the test
+    ///         }))
+    ///         .await;
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics with the logs of the container if it exits before the future completes, which
+    /// includes an exit caused by the future, e.g. by stopping the container.
+    pub async fn guard<F: Future>(&self, future: F) -> F::Output {
+        let exited = self.exited();
+        futures::pin_mut!(future, exited);
+
+        match futures::future::select(future, exited).await {
+            Either::Left((output, _)) => output,
+            Either::Right((died, _)) => panic!("{died}"),
         }
     }
 
+    /// Blocks until the main process of the container exits, like [`ContainerAsync::wait`].
+    ///
+    /// # Panics
+    /// Panics if the container did not exit within the timeout.
+    pub async fn wait_with_timeout(&self, timeout: Duration) -> ExitStatus {
+        runtime::compat(async { tokio::time::timeout(timeout, self.wait()).await })
+            .await
+            .unwrap_or_else(|_| panic!("container {} did not exit within {timeout:?}", self.id))
+    }
+
+    /// Stops the container. Its filesystem, e.g. a data volume, is kept until it is removed.
     pub async fn stop(&self) {
         log::debug!("Stopping docker container {}", self.id);
 
-        self.docker_client.stop(&self.id).await
+        runtime::compat(self.docker_client.stop(&self.id, None)).await
+    }
+
+    /// Stops the container, killing it if it did not stop within the timeout (in whole
+    /// seconds), e.g. to test how a dependency that shuts down slowly is handled.
+    pub async fn stop_with_timeout(&self, timeout: Duration) {
+        log::debug!("Stopping docker container {} within {timeout:?}", self.id);
+
+        runtime::compat(self.docker_client.stop(&self.id, Some(timeout))).await
+    }
+
+    /// Creates the image `repo:tag` from the current filesystem and configuration of the
+    /// container, and returns it so that it can be started like any other image.
+    ///
+    /// Expensive initialization, like a schema migration or seeding data, can be done once and
+    /// the committed image reused across the test suite. Note that the content of volumes,
+    /// which many database images declare for their data directory, is not part of the image.
+    /// The image is not removed automatically.
+    pub async fn commit(&self, repo: impl Into<String>, tag: impl Into<String>) -> GenericImage {
+        let (repo, tag) = (repo.into(), tag.into());
+        log::debug!("Committing docker container {} to {repo}:{tag}", self.id);
+
+        runtime::compat(self.docker_client.commit(&self.id, &repo, &tag)).await;
+        GenericImage::new(repo, tag)
+    }
+
+    /// Commits the container to an image, from which fresh copies of it are started, e.g. a
+    /// database that was seeded once. See [`Snapshot`] for the details.
+    pub async fn snapshot(&self) -> Snapshot<I>
+    where
+        I: Clone,
+    {
+        let name = format!(
+            "testcontainers-snapshot-{}",
+            ulid::Ulid::new().to_string().to_lowercase()
+        );
+        log::debug!("Taking snapshot {name} of docker container {}", self.id);
+
+        runtime::compat(self.docker_client.commit(&self.id, &name, "latest")).await;
+        Snapshot::new(self.image.clone().into_snapshot(name, "latest"))
+    }
+
+    /// Returns the processes running in the container, e.g. to assert the number of forked
+    /// workers or to detect zombie processes.
+    pub async fn top(&self) -> ProcessList {
+        runtime::compat(self.docker_client.top(&self.id, None)).await
+    }
+
+    /// Returns the processes running in the container, listed by `ps` with the given
+    /// arguments, e.g. `-eo pid,stat,comm` to get the state of each process.
+    pub async fn top_with_ps_args(&self, ps_args: &str) -> ProcessList {
+        runtime::compat(self.docker_client.top(&self.id, Some(ps_args))).await
+    }
+
+    /// Updates the resource limits of the running container, e.g. to shrink its memory and
+    /// test how the application degrades under pressure.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the docker daemon rejects the limits, e.g. a memory limit above the swap
+    /// limit.
+    pub async fn update_limits(&self, limits: ResourceLimits) {
+        log::debug!(
+            "Updating limits of docker container {}: {limits:?}",
+            self.id
+        );
+
+        runtime::compat(self.docker_client.update(&self.id, &limits)).await
+    }
+
+    /// Sends the signal to the main process of the container, e.g. [`Signal::Hup`] to test
+    /// reloading the configuration, or [`Signal::Kill`] to test how a crash is handled.
+    pub async fn kill(&self, signal: Signal) {
+        log::debug!("Sending {signal} to docker container {}", self.id);
+
+        runtime::compat(self.docker_client.kill(&self.id, &signal)).await
+    }
+
+    /// Restarts the container, e.g. to test reconnection logic, and executes the commands of
+    /// [`Image::exec_after_start`]. The ready conditions are not awaited again, see
+    /// [`ContainerAsync::start`].
+    ///
+    /// Note that the host ports of the container may change, unless they are mapped
+    /// explicitly.
+    pub async fn restart(&self) {
+        log::debug!("Restarting docker container {}", self.id);
+
+        runtime::compat(self.docker_client.restart(&self.id, None)).await;
+        self.exec_after_start().await;
+    }
+
+    async fn exec_after_start(&self) {
+        for cmd in self
+            .image
+            .exec_after_start(ContainerState::new(self.ports().await))
+        {
+            self.exec(cmd).await;
+        }
     }
 
+    /// Removes the container, after executing its shutdown commands, without blocking the
+    /// thread of the caller like dropping it does. The container is removed regardless of its
+    /// [`RemovePolicy`].
     pub async fn rm(mut self) {
         log::debug!("Deleting docker container {}", self.id);
 
-        self.docker_client.rm(&self.id).await;
+        runtime::compat(async {
+            if self.log_dump().applies(false) {
+                logs::dump_to_file(&self.docker_client, &self.id).await;
+            }
+            shut_down(
+                &self.docker_client,
+                &self.id,
+                self.image.shutdown_commands(),
+            )
+            .await;
+
+            if self.image.auto_remove() {
+                self.docker_client.rm_if_exists(&self.id).await;
+            } else {
+                self.docker_client.rm(&self.id).await;
+            }
+        })
+        .await;
 
         #[cfg(feature = "watchdog")]
         crate::watchdog::unregister(&self.id);
@@ -228,10 +788,269 @@ where
         self.dropped = true;
     }
 
-    async fn block_until_ready(&self) {
-        self.docker_client
-            .block_until_ready(self.id(), &self.image().ready_conditions())
-            .await;
+    /// Runs the body with the container, and cleans the container up afterwards like dropping
+    /// it does, but without blocking the thread of the caller. If the body panics, the test is
+    /// considered failed, e.g. for the [`RemovePolicy`], and the panic is resumed once the
+    /// container is cleaned up.
+    ///
+    /// ```rust,no_run
+    /// use futures::FutureExt;
+    /// use testcontainers::{runners::AsyncRunner, GenericImage};
+    ///
+    /// #[tokio::test(flavor = "current_thread")]
+    /// async fn a_test() {
+    ///     let redis = GenericImage::new("redis", "7.2.4").start().await.unwrap();
+    ///     redis
+    ///         .remove_after(|redis| {
+    ///             async move {
+    ///                 let port = redis.get_host_port_ipv4(6379).await;
+    ///             }
+    ///             .boxed()
+    ///         })
+    ///         .await;
+    /// }
+    /// ```
+    pub async fn remove_after<T>(
+        mut self,
+        body: impl for<'a> FnOnce(&'a Self) -> BoxFuture<'a, T>,
+    ) -> T {
+        let result = AssertUnwindSafe(body(&self)).catch_unwind().await;
+
+        let clean_up = self.clean_up(result.is_err());
+        self.dropped = true;
+        runtime::compat(clean_up).await;
+
+        match result {
+            Ok(output) => output,
+            Err(payload) => panic::resume_unwind(payload),
+        }
+    }
+
+    /// Cleans the container up like dropping it does, after a test that failed or not, for
+    /// containers that are dropped on another thread than the one of the test.
+    pub(crate) fn drop_after(mut self, failed: bool) {
+        runtime::block_on(self.clean_up(failed));
+        self.dropped = true;
+    }
+
+    /// Blocks until the container with the given id meets the ready conditions of the image.
+    ///
+    /// Returns a [`StartupError`] if the container does not meet a ready condition, either
+    /// because the startup timeout (or the condition's own timeout, see
+    /// [`WaitFor::with_timeout`]) elapsed or because waiting for it failed.
+    pub(crate) async fn block_until_ready(
+        docker_client: &Client,
+        id: &str,
+        image: &RunnableImage<I>,
+    ) -> Result<(), StartupError> {
+        let timeout = image
+            .startup_timeout()
+            .unwrap_or_else(|| docker_client.config.startup_timeout());
+        block_until_ready(docker_client, id, image.ready_conditions(), timeout).await
+    }
+}
+
+/// Blocks until the container with the given id meets the ready conditions, see
+/// [`ContainerAsync::block_until_ready`].
+pub(crate) async fn block_until_ready(
+    docker_client: &Client,
+    id: &str,
+    ready_conditions: Vec<WaitFor>,
+    timeout: Duration,
+) -> Result<(), StartupError> {
+    let started_at = Instant::now();
+    // time spent on conditions with their own timeout doesn't count against the startup timeout
+    let mut exempt = Duration::ZERO;
+
+    log::debug!("Waiting for container {id} to be ready");
+
+    for condition in ready_conditions {
+        let condition_started_at = Instant::now();
+        // the nested condition of a timeout is awaited directly, so that only one timer limits it
+        let (waited, limit) = match &condition {
+            WaitFor::Timeout {
+                condition: nested,
+                timeout,
+            } => (nested.as_ref(), *timeout),
+            _ => (
+                &condition,
+                timeout.saturating_sub(started_at.elapsed() - exempt),
+            ),
+        };
+        let ready = waited.wait_until_ready(docker_client, id);
+
+        let reason = match tokio::time::timeout(limit, AssertUnwindSafe(ready).catch_unwind()).await
+        {
+            Ok(Ok(())) => {
+                if matches!(condition, WaitFor::Timeout { .. }) {
+                    exempt += condition_started_at.elapsed();
+                }
+                continue;
+            }
+            Ok(Err(panic)) => StartupFailure::ConditionFailed {
+                message: panic_message(panic.as_ref()),
+            },
+            Err(_) => StartupFailure::TimedOut { timeout: limit },
+        };
+
+        let error = StartupError {
+            container_id: id.to_string(),
+            condition,
+            reason,
+            elapsed: started_at.elapsed(),
+            exit_code: docker_client.exit_code(id).await,
+            stdout_tail: StartupError::tail(
+                &docker_client
+                    .collected_logs(id, DesiredLogStream::Stdout)
+                    .await,
+            ),
+            stderr_tail: StartupError::tail(
+                &docker_client
+                    .collected_logs(id, DesiredLogStream::Stderr)
+                    .await,
+            ),
+        };
+        return Err(error);
+    }
+
+    log::debug!("Container {id} is now ready!");
+    Ok(())
+}
+
+impl<I> ContainerAsync<I>
+where
+    I: Image,
+{
+    fn log_dump(&self) -> LogDump {
+        self.image
+            .log_dump()
+            .unwrap_or_else(|| self.docker_client.config.log_dump())
+    }
+
+    pub(crate) fn remove_policy(&self) -> RemovePolicy {
+        self.image
+            .remove_on_drop()
+            .unwrap_or_else(|| self.docker_client.config.remove_policy())
+    }
+
+    /// Returns the cleanup that is done once the container is dropped, after a test that failed
+    /// or not, according to the policies of the container.
+    fn clean_up(&self, failed: bool) -> impl Future<Output = ()> + Send + 'static {
+        let id = self.id.clone();
+        let client = self.docker_client.clone();
+        let auto_remove = self.image.auto_remove();
+        let dump_logs = self.log_dump().applies(failed);
+        let remove = self.remove_policy().removes(failed);
+        let shutdown_commands = self.image.shutdown_commands();
+
+        async move {
+            log::trace!("Drop was called for container {id}, cleaning up");
+            if dump_logs {
+                logs::dump_to_file(&client, &id).await;
+            }
+            if remove {
+                shut_down(&client, &id, shutdown_commands).await;
+            }
+            if remove && auto_remove {
+                client.rm_if_exists(&id).await;
+            } else if remove {
+                client.rm(&id).await;
+            } else if failed {
+                removal::report_kept(&client, &id).await;
+            }
+            #[cfg(feature = "watchdog")]
+            crate::watchdog::unregister(&id);
+
+            log::debug!("Container {id} was successfully dropped");
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "waiting for the condition panicked".to_string()
+    }
+}
+
+/// Reads the output of an executed command until it meets a log based condition, see
+/// [`ContainerAsync::exec`].
+async fn wait_for_output(
+    output: BoxStream<'static, Result<LogOutput, bollard::errors::Error>>,
+    condition: &WaitFor,
+) -> Result<(), WaitError> {
+    let (stdout, stderr) = match condition {
+        WaitFor::StdErrMessage { .. }
+        | WaitFor::StdErrMessageTimes { .. }
+        | WaitFor::StdErrMatches { .. } => (false, true),
+        WaitFor::EitherStreamMessage { .. } => (true, true),
+        _ => (true, false),
+    };
+    let output = output
+        .filter_map(move |chunk| {
+            futures::future::ready(match chunk {
+                Ok(LogOutput::StdOut { message } | LogOutput::Console { message }) if stdout => {
+                    Some(Ok(String::from_utf8_lossy(&message).into_owned()))
+                }
+                Ok(LogOutput::StdErr { message }) if stderr => {
+                    Some(Ok(String::from_utf8_lossy(&message).into_owned()))
+                }
+                Ok(_) => None,
+                Err(err) => Some(Err(io::Error::new(io::ErrorKind::Other, err))),
+            })
+        })
+        .boxed();
+    let output = LogStreamAsync::new(output);
+
+    match condition {
+        WaitFor::StdOutMessage { message }
+        | WaitFor::StdErrMessage { message }
+        | WaitFor::EitherStreamMessage { message } => output.wait_for_message(message).await,
+        WaitFor::StdOutMessageTimes { message, times }
+        | WaitFor::StdErrMessageTimes { message, times } => {
+            output.wait_for_message_times(message, *times).await
+        }
+        WaitFor::StdOutMatches { pattern } | WaitFor::StdErrMatches { pattern } => {
+            output.wait_for_match(&wait::compile_pattern(pattern)).await
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks the output of an executed command against a log based condition.
+fn output_meets_condition(result: &ExecResult, condition: &WaitFor) -> bool {
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    let matches = |output: &str, pattern: &str| {
+        let pattern = wait::compile_pattern(pattern);
+        output.lines().any(|line| pattern.is_match(line))
+    };
+
+    match condition {
+        WaitFor::StdOutMessage { message } => stdout.contains(message.as_str()),
+        WaitFor::StdErrMessage { message } => stderr.contains(message.as_str()),
+        WaitFor::EitherStreamMessage { message } => {
+            stdout.contains(message.as_str()) || stderr.contains(message.as_str())
+        }
+        WaitFor::StdOutMessageTimes { message, times } => {
+            stdout.matches(message.as_str()).count() >= *times
+        }
+        WaitFor::StdErrMessageTimes { message, times } => {
+            stderr.matches(message.as_str()).count() >= *times
+        }
+        WaitFor::StdOutMatches { pattern } => matches(&stdout, pattern),
+        WaitFor::StdErrMatches { pattern } => matches(&stderr, pattern),
+        _ => true,
+    }
+}
+
+fn check_copy_destination(destination: &str) {
+    // the container path is always a unix path, independent of the host
+    if !destination.starts_with('/') {
+        panic!("destination '{destination}' in the container must be absolute");
     }
 }
 
@@ -248,29 +1067,33 @@ where
     }
 }
 
+/// Executes the shutdown commands of a container that is about to be removed, if it has any,
+/// and stops it gracefully.
+async fn shut_down(client: &Client, id: &str, commands: Vec<ExecCommand>) {
+    if commands.is_empty() {
+        return;
+    }
+
+    let shutdown = async {
+        for cmd in &commands {
+            log::debug!("Executing shutdown command {:?} in container {id}", cmd.cmd);
+            client.exec(id, cmd).await;
+        }
+        client.stop(id, None).await;
+    };
+    // the container is removed anyway, and a panic must not escape a drop
+    if AssertUnwindSafe(shutdown).catch_unwind().await.is_err() {
+        log::warn!("Failed to shut down container {id} gracefully");
+    }
+}
+
 impl<I> Drop for ContainerAsync<I>
 where
     I: Image,
 {
     fn drop(&mut self) {
         if !self.dropped {
-            let id = self.id.clone();
-            let client = self.docker_client.clone();
-            let command = self.docker_client.config.command();
-
-            let drop_task = async move {
-                log::trace!("Drop was called for container {id}, cleaning up");
-                match command {
-                    env::Command::Remove => client.rm(&id).await,
-                    env::Command::Keep => {}
-                }
-                #[cfg(feature = "watchdog")]
-                crate::watchdog::unregister(&id);
-
-                log::debug!("Container {id} was successfully dropped");
-            };
-
-            macros::block_on!(drop_task, "failed to remove container on drop");
+            runtime::block_on(self.clean_up(std::thread::panicking()));
         }
     }
 }
@@ -0,0 +1,155 @@
+use std::{fmt, io, pin::Pin, sync::Arc};
+
+use bollard::container::LogOutput;
+use futures::{stream::BoxStream, StreamExt};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::core::{client::Client, runtime, ExecResult, LogSource};
+
+/// A command running in a container with its stdin attached, see
+/// [`ContainerAsync::exec_interactive`].
+///
+/// [`ContainerAsync::exec_interactive`]: crate::ContainerAsync::exec_interactive
+pub struct ExecSessionAsync {
+    id: String,
+    docker_client: Arc<Client>,
+    input: Option<Pin<Box<dyn AsyncWrite + Send>>>,
+    output: BoxStream<'static, Result<LogOutput, bollard::errors::Error>>,
+}
+
+impl fmt::Debug for ExecSessionAsync {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExecSessionAsync")
+            .field("id", &self.id)
+            .field("stdin_closed", &self.input.is_none())
+            .finish_non_exhaustive()
+    }
+}
+
+impl ExecSessionAsync {
+    pub(crate) fn new(
+        id: String,
+        docker_client: Arc<Client>,
+        output: BoxStream<'static, Result<LogOutput, bollard::errors::Error>>,
+        input: Pin<Box<dyn AsyncWrite + Send>>,
+    ) -> Self {
+        Self {
+            id,
+            docker_client,
+            input: Some(input),
+            output,
+        }
+    }
+
+    /// Returns the id of the exec.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Writes the data to the stdin of the command.
+    pub async fn write_stdin(&mut self, data: impl AsRef<[u8]>) -> io::Result<()> {
+        let input = self
+            .input
+            .as_mut()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::BrokenPipe, "stdin is closed"))?;
+
+        runtime::compat(async {
+            input.write_all(data.as_ref()).await?;
+            input.flush().await
+        })
+        .await
+    }
+
+    /// Closes the stdin of the command, which then reads the end of its input.
+    pub async fn close_stdin(&mut self) -> io::Result<()> {
+        match self.input.take() {
+            Some(mut input) => runtime::compat(input.shutdown()).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the next chunk of output of the command, or `None` once it has finished.
+    pub async fn read_output(&mut self) -> Option<io::Result<(LogSource, Vec<u8>)>> {
+        loop {
+            let output = match runtime::compat(self.output.next()).await? {
+                Ok(LogOutput::StdOut { message }) | Ok(LogOutput::Console { message }) => {
+                    (LogSource::StdOut, message.to_vec())
+                }
+                Ok(LogOutput::StdErr { message }) => (LogSource::StdErr, message.to_vec()),
+                Ok(LogOutput::StdIn { .. }) => continue,
+                Err(err) => return Some(Err(io::Error::new(io::ErrorKind::Other, err))),
+            };
+            return Some(Ok(output));
+        }
+    }
+
+    /// Closes the stdin of the command, waits for it to finish and returns the output that
+    /// was not read yet and its exit code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the output of the command can't be read.
+    pub async fn wait(mut self) -> ExecResult {
+        // the command may already have exited and closed its stdin
+        let _ = self.close_stdin().await;
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        while let Some(output) = self.read_output().await {
+            match output.expect("failed to read output of exec") {
+                (LogSource::StdOut, message) => stdout.extend(message),
+                (LogSource::StdErr, message) => stderr.extend(message),
+            }
+        }
+
+        ExecResult {
+            exit_code: runtime::compat(self.docker_client.exec_exit_code(&self.id)).await,
+            stdout,
+            stderr,
+        }
+    }
+}
+
+/// A command running in a container with its stdin attached, see
+/// [`Container::exec_interactive`].
+///
+/// [`Container::exec_interactive`]: crate::Container::exec_interactive
+#[cfg(feature = "blocking")]
+#[derive(Debug)]
+pub struct ExecSession<'a> {
+    rt: &'a tokio::runtime::Runtime,
+    async_impl: ExecSessionAsync,
+}
+
+#[cfg(feature = "blocking")]
+impl<'a> ExecSession<'a> {
+    pub(crate) fn new(rt: &'a tokio::runtime::Runtime, async_impl: ExecSessionAsync) -> Self {
+        Self { rt, async_impl }
+    }
+
+    /// Returns the id of the exec.
+    pub fn id(&self) -> &str {
+        self.async_impl.id()
+    }
+
+    /// Writes the data to the stdin of the command, see [`ExecSessionAsync::write_stdin`].
+    pub fn write_stdin(&mut self, data: impl AsRef<[u8]>) -> io::Result<()> {
+        self.rt.block_on(self.async_impl.write_stdin(data))
+    }
+
+    /// Closes the stdin of the command, see [`ExecSessionAsync::close_stdin`].
+    pub fn close_stdin(&mut self) -> io::Result<()> {
+        self.rt.block_on(self.async_impl.close_stdin())
+    }
+
+    /// Returns the next chunk of output of the command, see
+    /// [`ExecSessionAsync::read_output`].
+    pub fn read_output(&mut self) -> Option<io::Result<(LogSource, Vec<u8>)>> {
+        self.rt.block_on(self.async_impl.read_output())
+    }
+
+    /// Waits for the command to finish, see [`ExecSessionAsync::wait`].
+    pub fn wait(self) -> ExecResult {
+        self.rt.block_on(self.async_impl.wait())
+    }
+}
@@ -0,0 +1,389 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt,
+    net::IpAddr,
+};
+
+use async_trait::async_trait;
+
+use crate::{
+    core::{containers::set::Removable, TestcontainersError},
+    runners::AsyncRunner,
+    ContainerAsync, ContainerSet, Image, RunnableImage,
+};
+
+/// A set of containers that depend on each other, e.g. an application that depends on a
+/// database and a message broker.
+///
+/// The containers are started in the order of their dependencies and join a network of the
+/// group, on which every container can be reached by its name. Each container gets the address
+/// of each of its dependencies in a `<NAME>_HOST` environment variable, where the name is
+/// upper-cased and every character that is not alphanumeric is replaced by `_`. Once the
+/// started group is dropped, the containers are removed in the reverse order of their
+/// dependencies, like the ones of a [`ContainerSet`].
+///
+/// ```rust,no_run
+/// use testcontainers::{core::WaitFor, ContainerGroup, DependsOn, GenericImage};
+///
+/// async fn a_test() {
+///     let postgres = GenericImage::new("postgres", "16-alpine")
+///         .with_env_var(("POSTGRES_PASSWORD", "postgres"))
+///         .with_wait_for(WaitFor::message_on_stderr("ready to accept connections"));
+///     let app = GenericImage::new("my-app", "latest");
+///
+///     let group = ContainerGroup::new()
+///         .with_container("postgres", postgres, DependsOn::nothing())
+///         // the app reads the address of the database from `POSTGRES_HOST`
+///         .with_container("app", app, DependsOn::containers(["postgres"]))
+///         .start()
+///         .await
+///         .unwrap();
+///     let app = group.get::<GenericImage>("app").unwrap();
+/// }
+/// ```
+pub struct ContainerGroup {
+    members: Vec<Member>,
+}
+
+/// The names of the containers of a [`ContainerGroup`] that a container depends on.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DependsOn {
+    names: Vec<String>,
+}
+
+impl DependsOn {
+    /// The container does not depend on any other container of the group.
+    pub fn nothing() -> Self {
+        Self::default()
+    }
+
+    /// The container depends on the containers with the names, which are started before it.
+    pub fn containers(names: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            names: names.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    pub(crate) fn names(&self) -> &[String] {
+        &self.names
+    }
+}
+
+struct Member {
+    name: String,
+    image: Box<dyn Startable>,
+    depends_on: DependsOn,
+}
+
+impl ContainerGroup {
+    /// Creates an empty group.
+    pub fn new() -> Self {
+        Self {
+            members: Vec::new(),
+        }
+    }
+
+    /// Adds a container with the name to the group.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the group already contains a container with the name.
+    pub fn with_container<I: Image + 'static>(
+        mut self,
+        name: impl Into<String>,
+        image: impl Into<RunnableImage<I>>,
+        depends_on: DependsOn,
+    ) -> Self {
+        let name = name.into();
+        if self.members.iter().any(|member| member.name == name) {
+            panic!("container group already contains a container named {name}");
+        }
+
+        self.members.push(Member {
+            name,
+            image: Box::new(image.into()),
+            depends_on,
+        });
+        self
+    }
+
+    /// Starts the containers of the group in the order of their dependencies.
+    ///
+    /// If a container can't be started, the containers that were started before it are removed
+    /// and the error is returned. Returns [`TestcontainersError::InvalidConfiguration`] without
+    /// starting any container if a container depends on a container that is not part of the
+    /// group, or if the dependencies form a cycle.
+    pub async fn start(self) -> Result<StartedGroup, TestcontainersError> {
+        let order = startup_order(&self.members)?;
+        let mut members: HashMap<_, _> = self
+            .members
+            .into_iter()
+            .map(|member| (member.name.clone(), member))
+            .collect();
+
+        let mut started = StartedGroup {
+            network: format!(
+                "testcontainers-group-{}",
+                ulid::Ulid::new().to_string().to_lowercase()
+            ),
+            addresses: Vec::new(),
+            containers: ContainerSet::new(),
+        };
+        for name in order {
+            let member = members.remove(&name).expect("every member is started once");
+            let env_vars = member
+                .depends_on
+                .names
+                .iter()
+                .map(|dependency| {
+                    let (_, address) = started
+                        .addresses
+                        .iter()
+                        .find(|(name, _)| name == dependency)
+                        .expect("dependencies are started first");
+                    (
+                        format!("{}_HOST", env_var_prefix(dependency)),
+                        address.to_string(),
+                    )
+                })
+                .collect();
+
+            log::debug!("Starting container {name} of group {}", started.network);
+            let (address, container) = member
+                .image
+                .start(&name, &started.network, env_vars)
+                .await?;
+            started.addresses.push((name.clone(), address));
+            started.containers.push(name, member.depends_on, container);
+        }
+
+        Ok(started)
+    }
+}
+
+impl Default for ContainerGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for ContainerGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(
+                self.members
+                    .iter()
+                    .map(|member| (&member.name, &member.depends_on)),
+            )
+            .finish()
+    }
+}
+
+/// The started containers of a [`ContainerGroup`], which are removed in the reverse order of
+/// their dependencies once it is dropped.
+pub struct StartedGroup {
+    network: String,
+    /// The address of each container on the network, in the order they were started.
+    addresses: Vec<(String, IpAddr)>,
+    containers: ContainerSet,
+}
+
+impl StartedGroup {
+    /// Returns the container with the name, or `None` if there is none or it is not one of an
+    /// image of type `I`.
+    pub fn get<I: Image + 'static>(&self, name: &str) -> Option<&ContainerAsync<I>> {
+        self.containers.get(name)
+    }
+
+    /// Returns the name of the network the containers of the group are connected to.
+    pub fn network(&self) -> &str {
+        &self.network
+    }
+}
+
+impl fmt::Debug for StartedGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StartedGroup")
+            .field("network", &self.network)
+            .field("containers", &self.addresses)
+            .finish()
+    }
+}
+
+/// Starts an image of any type as a member of a group.
+#[async_trait]
+trait Startable: Send {
+    fn descriptor(&self) -> String;
+
+    /// Starts the container and returns its address on the network along with the container.
+    async fn start(
+        self: Box<Self>,
+        name: &str,
+        network: &str,
+        env_vars: Vec<(String, String)>,
+    ) -> Result<(IpAddr, Box<dyn Removable>), TestcontainersError>;
+}
+
+#[async_trait]
+impl<I: Image + 'static> Startable for RunnableImage<I> {
+    fn descriptor(&self) -> String {
+        RunnableImage::descriptor(self)
+    }
+
+    async fn start(
+        self: Box<Self>,
+        name: &str,
+        network: &str,
+        env_vars: Vec<(String, String)>,
+    ) -> Result<(IpAddr, Box<dyn Removable>), TestcontainersError> {
+        let image = env_vars.into_iter().fold(
+            self.with_network(network).with_network_alias(name),
+            |image, env_var| image.with_env_var(env_var),
+        );
+        let container = image.start().await?;
+        // the container is removed once it is dropped
+        let address = container.try_network_ip(network).await.map_err(|message| {
+            TestcontainersError::StartFailed {
+                container_id: container.id().to_string(),
+                message,
+            }
+        })?;
+
+        Ok((address, Box::new(container)))
+    }
+}
+
+/// Returns the names of the members in an order in which every member comes after its
+/// dependencies, and otherwise in the order they were added.
+fn startup_order(members: &[Member]) -> Result<Vec<String>, TestcontainersError> {
+    let invalid = |member: &Member, message| TestcontainersError::InvalidConfiguration {
+        image: member.image.descriptor(),
+        message,
+    };
+
+    let names: BTreeSet<_> = members.iter().map(|member| member.name.as_str()).collect();
+    for member in members {
+        for dependency in &member.depends_on.names {
+            if !names.contains(dependency.as_str()) {
+                return Err(invalid(
+                    member,
+                    format!(
+                        "container {} depends on {dependency}, which is not part of the group",
+                        member.name
+                    ),
+                ));
+            }
+        }
+    }
+
+    let mut order: Vec<String> = Vec::with_capacity(members.len());
+    while order.len() < members.len() {
+        let next = members.iter().find(|member| {
+            !order.contains(&member.name)
+                && member
+                    .depends_on
+                    .names
+                    .iter()
+                    .all(|dependency| order.contains(dependency))
+        });
+        match next {
+            Some(member) => order.push(member.name.clone()),
+            None => {
+                let cycle: Vec<_> = members
+                    .iter()
+                    .filter(|member| !order.contains(&member.name))
+                    .collect();
+                let names: Vec<_> = cycle.iter().map(|member| member.name.as_str()).collect();
+                return Err(invalid(
+                    cycle[0],
+                    format!(
+                        "the dependencies of the containers {} form a cycle",
+                        names.join(", ")
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(order)
+}
+
+/// Returns the prefix of the environment variables with the address of a container, e.g.
+/// `KAFKA_BROKER` for `kafka-broker`.
+fn env_var_prefix(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GenericImage;
+
+    fn group(members: &[(&str, &[&str])]) -> ContainerGroup {
+        members
+            .iter()
+            .fold(ContainerGroup::new(), |group, (name, depends_on)| {
+                group.with_container(
+                    *name,
+                    GenericImage::new("hello-world", "latest"),
+                    DependsOn::containers(depends_on.iter().copied()),
+                )
+            })
+    }
+
+    #[test]
+    fn starts_dependencies_first() {
+        let group = group(&[
+            ("app", &["postgres", "kafka"]),
+            ("kafka", &["zookeeper"]),
+            ("postgres", &[]),
+            ("zookeeper", &[]),
+        ]);
+
+        assert_eq!(
+            startup_order(&group.members).unwrap(),
+            vec!["postgres", "zookeeper", "kafka", "app"]
+        );
+    }
+
+    #[tokio::test]
+    async fn start_fails_on_cycles() {
+        let group = group(&[("a", &["b"]), ("b", &["a"]), ("c", &[])]);
+        let err = group.start().await.unwrap_err();
+        assert!(
+            matches!(
+                &err,
+                TestcontainersError::InvalidConfiguration { image, message }
+                    if image == "hello-world:latest"
+                        && message == "the dependencies of the containers a, b form a cycle"
+            ),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn start_fails_on_unknown_dependencies() {
+        let group = group(&[("app", &["redis"])]);
+        let err = group.start().await.unwrap_err();
+        assert!(
+            matches!(
+                &err,
+                TestcontainersError::InvalidConfiguration { message, .. }
+                    if message == "container app depends on redis, which is not part of the group"
+            ),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn env_var_prefix_is_upper_case() {
+        assert_eq!(env_var_prefix("kafka-broker.1"), "KAFKA_BROKER_1");
+    }
+}
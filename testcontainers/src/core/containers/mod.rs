@@ -1,9 +1,20 @@
 pub(crate) mod async_container;
+pub(crate) mod exec;
+pub(crate) mod group;
+pub(crate) mod set;
+pub(crate) mod shared_container;
 #[cfg(feature = "blocking")]
 pub(crate) mod sync_container;
 
 pub use async_container::ContainerAsync;
+pub use exec::ExecSessionAsync;
+pub use group::{ContainerGroup, DependsOn, StartedGroup};
+pub use set::ContainerSet;
+pub use shared_container::SharedContainer;
 
+#[cfg(feature = "blocking")]
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+pub use exec::ExecSession;
 #[cfg(feature = "blocking")]
 #[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
 pub use sync_container::Container;
@@ -0,0 +1,260 @@
+use std::{any::Any, collections::HashSet, fmt};
+
+use crate::{core::DependsOn, ContainerAsync, Image};
+
+/// A set of started containers that depend on each other, which are stopped and removed in the
+/// reverse order of their dependencies once the set is dropped.
+///
+/// Containers that are dropped at the end of a scope are removed in the order Rust drops them,
+/// so an application may still be running while the database it deregisters from on shutdown
+/// is already gone. A container of the set is only removed once all containers that depend on
+/// it are removed, including their [shutdown commands]. Containers that don't depend on each
+/// other are removed concurrently.
+///
+/// Unlike a [`ContainerGroup`], which starts the containers, the set takes containers that were
+/// started in any way.
+///
+/// ```rust,no_run
+/// use testcontainers::{runners::AsyncRunner, ContainerSet, DependsOn, GenericImage};
+///
+/// async fn a_test() {
+///     let postgres = GenericImage::new("postgres", "16-alpine")
+///         .start()
+///         .await
+///         .unwrap();
+///     let app = GenericImage::new("my-app", "latest").start().await.unwrap();
+///
+///     let containers = ContainerSet::new()
+///         .with_container("postgres", postgres, DependsOn::nothing())
+///         .with_container("app", app, DependsOn::containers(["postgres"]));
+///     let app = containers.get::<GenericImage>("app").unwrap();
+/// }
+/// ```
+///
+/// [shutdown commands]: crate::core::RunnableImage::with_shutdown_command
+/// [`ContainerGroup`]: crate::ContainerGroup
+pub struct ContainerSet {
+    containers: Vec<Member>,
+}
+
+struct Member {
+    name: String,
+    depends_on: DependsOn,
+    container: Box<dyn Removable>,
+}
+
+/// A started container of any image that is a member of a set.
+pub(crate) trait Removable: Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+
+    /// Cleans the container up like dropping it does, after a test that failed or not.
+    fn remove(self: Box<Self>, failed: bool);
+}
+
+impl<I: Image + 'static> Removable for ContainerAsync<I> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn remove(self: Box<Self>, failed: bool) {
+        self.drop_after(failed);
+    }
+}
+
+impl ContainerSet {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self {
+            containers: Vec::new(),
+        }
+    }
+
+    /// Adds the container with the name to the set. It is removed before the containers it
+    /// depends on, which may be added before or after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the set already contains a container with the name.
+    pub fn with_container<I: Image + 'static>(
+        mut self,
+        name: impl Into<String>,
+        container: ContainerAsync<I>,
+        depends_on: DependsOn,
+    ) -> Self {
+        self.push(name.into(), depends_on, Box::new(container));
+        self
+    }
+
+    /// Adds a container of any image, see [`ContainerSet::with_container`].
+    pub(crate) fn push(
+        &mut self,
+        name: String,
+        depends_on: DependsOn,
+        container: Box<dyn Removable>,
+    ) {
+        if self.contains(&name) {
+            panic!("container set already contains a container named {name}");
+        }
+
+        self.containers.push(Member {
+            name,
+            depends_on,
+            container,
+        });
+    }
+
+    /// Returns the container with the name, or `None` if there is none or it is not one of an
+    /// image of type `I`.
+    pub fn get<I: Image + 'static>(&self, name: &str) -> Option<&ContainerAsync<I>> {
+        self.containers
+            .iter()
+            .find(|member| member.name == name)
+            .and_then(|member| member.container.as_any().downcast_ref())
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.containers.iter().any(|member| member.name == name)
+    }
+}
+
+impl Default for ContainerSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for ContainerSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(
+                self.containers
+                    .iter()
+                    .map(|member| (&member.name, &member.depends_on)),
+            )
+            .finish()
+    }
+}
+
+impl Drop for ContainerSet {
+    fn drop(&mut self) {
+        // the members are removed on other threads, which don't know whether the test failed
+        let failed = std::thread::panicking();
+        let mut remaining = std::mem::take(&mut self.containers);
+        while !remaining.is_empty() {
+            let needed: HashSet<_> = remaining
+                .iter()
+                .flat_map(|member| member.depends_on.names().iter().cloned())
+                .collect();
+            let (removable, rest): (Vec<_>, Vec<_>) = remaining
+                .into_iter()
+                .partition(|member| !needed.contains(&member.name));
+
+            if removable.is_empty() {
+                let names: Vec<_> = rest.iter().map(|member| member.name.as_str()).collect();
+                log::warn!(
+                    "The dependencies of the containers {} of the set form a cycle, removing them in the reverse order they were added",
+                    names.join(", ")
+                );
+                for member in rest.into_iter().rev() {
+                    remove(member, failed);
+                }
+                return;
+            }
+
+            // no remaining container depends on these, so they are removed concurrently
+            std::thread::scope(|scope| {
+                for member in removable {
+                    scope.spawn(move || remove(member, failed));
+                }
+            });
+            remaining = rest;
+        }
+    }
+}
+
+fn remove(member: Member, failed: bool) {
+    log::debug!("Removing container {} of the set", member.name);
+    member.container.remove(failed);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    /// Records the name of the member once it is removed, and whether the test failed.
+    struct Removal {
+        name: &'static str,
+        removed: Arc<Mutex<Vec<(&'static str, bool)>>>,
+    }
+
+    impl Removable for Removal {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn remove(self: Box<Self>, failed: bool) {
+            self.removed.lock().unwrap().push((self.name, failed));
+        }
+    }
+
+    #[test]
+    fn removes_dependents_before_their_dependencies() {
+        let removed = Arc::new(Mutex::new(Vec::new()));
+        let mut set = ContainerSet::new();
+        // added in an order that is not one of their dependencies
+        for (name, depends_on) in [
+            ("app", DependsOn::containers(["postgres", "kafka"])),
+            ("postgres", DependsOn::nothing()),
+            ("kafka", DependsOn::containers(["zookeeper"])),
+            ("zookeeper", DependsOn::nothing()),
+        ] {
+            let removal = Removal {
+                name,
+                removed: removed.clone(),
+            };
+            set.push(name.to_string(), depends_on, Box::new(removal));
+        }
+
+        drop(set);
+
+        let removed = removed.lock().unwrap();
+        let position = |name| {
+            removed
+                .iter()
+                .position(|(removed, _)| *removed == name)
+                .unwrap()
+        };
+        assert_eq!(removed.len(), 4);
+        assert_eq!(removed[0], ("app", false));
+        assert!(position("kafka") < position("zookeeper"));
+    }
+
+    #[test]
+    fn removes_members_as_failed_when_dropped_during_a_panic() {
+        let removed = Arc::new(Mutex::new(Vec::new()));
+        let mut set = ContainerSet::new();
+        for (name, depends_on) in [
+            ("app", DependsOn::containers(["postgres"])),
+            ("postgres", DependsOn::nothing()),
+        ] {
+            let removal = Removal {
+                name,
+                removed: removed.clone(),
+            };
+            set.push(name.to_string(), depends_on, Box::new(removal));
+        }
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
+            let _set = set;
+            panic!("the test failed");
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(
+            *removed.lock().unwrap(),
+            vec![("app", true), ("postgres", true)]
+        );
+    }
+}
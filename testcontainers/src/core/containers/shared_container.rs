@@ -0,0 +1,126 @@
+use std::fmt;
+
+use tokio::sync::OnceCell;
+
+use crate::{
+    core::{client::Client, env, session, TestcontainersError},
+    reaper,
+    runners::AsyncRunner,
+    ContainerAsync, Image, RunnableImage,
+};
+
+/// A container that is started once and shared by all tests of a test binary, e.g. a database
+/// that is expensive to start.
+///
+/// The container is started by the first test that asks for it and handed out by reference to
+/// all others. Each test runs on a runtime of its own, but the container can be used from any
+/// of them.
+///
+/// As statics are never dropped, the container is removed by the resource reaper once the
+/// process exits, independent of the `reaper` feature, unless its [`RemovePolicy`] is
+/// [`RemovePolicy::Never`] or the reaper is disabled with `TESTCONTAINERS_RYUK_DISABLED`. A
+/// container that is left running can be removed with [`cleanup::remove_session`] or
+/// [`cleanup::remove_stale`], as it is labelled with the session.
+///
+/// ```rust,no_run
+/// use testcontainers::{core::WaitFor, GenericImage, SharedContainer};
+///
+/// static REDIS: SharedContainer<GenericImage> = SharedContainer::new();
+///
+/// #[tokio::test]
+/// async fn a_test() {
+///     let redis = REDIS
+///         .get_or_start(|| {
+///             GenericImage::new("redis", "7.2.4")
+///                 .with_exposed_port(6379)
+///                 .with_wait_for(WaitFor::message_on_stdout("Ready to accept connections"))
+///         })
+///         .await
+///         .unwrap();
+///     let port = redis.get_host_port_ipv4(6379).await;
+/// }
+/// ```
+///
+/// [`RemovePolicy`]: crate::core::RemovePolicy
+/// [`RemovePolicy::Never`]: crate::core::RemovePolicy::Never
+/// [`cleanup::remove_session`]: crate::cleanup::remove_session
+/// [`cleanup::remove_stale`]: crate::cleanup::remove_stale
+pub struct SharedContainer<I: Image> {
+    container: OnceCell<ContainerAsync<I>>,
+}
+
+impl<I: Image> SharedContainer<I> {
+    /// Creates a shared container that is not started yet, to be stored in a `static`.
+    pub const fn new() -> Self {
+        Self {
+            container: OnceCell::const_new(),
+        }
+    }
+
+    /// Returns the container, starting it from the image unless it was started before.
+    ///
+    /// If the container fails to start, the error is returned and the next call starts it again.
+    pub async fn get_or_start<F, T>(
+        &self,
+        image: F,
+    ) -> Result<&ContainerAsync<I>, TestcontainersError>
+    where
+        F: FnOnce() -> T,
+        T: Into<RunnableImage<I>> + Send,
+    {
+        self.container
+            .get_or_try_init(|| start_shared(image().into()))
+            .await
+    }
+
+    /// Returns the container if it was started.
+    pub fn get(&self) -> Option<&ContainerAsync<I>> {
+        self.container.get()
+    }
+}
+
+impl<I: Image> Default for SharedContainer<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I> fmt::Debug for SharedContainer<I>
+where
+    I: fmt::Debug + Image,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedContainer")
+            .field("container", &self.container.get())
+            .finish()
+    }
+}
+
+/// Starts a container that is never dropped, and makes the resource reaper remove it once the
+/// process exits, unless its policy keeps it.
+pub(crate) async fn start_shared<I: Image>(
+    image: RunnableImage<I>,
+) -> Result<ContainerAsync<I>, TestcontainersError> {
+    // a dry run fails before any client is created
+    if env::dry_run::<env::Os>() {
+        return image.start().await;
+    }
+
+    let client = Client::try_lazy_client().await?;
+    // no test fails as a whole, so only containers that are never removed are kept
+    let removed = image
+        .remove_on_drop()
+        .unwrap_or_else(|| client.config.remove_policy())
+        .removes(false);
+    if !removed {
+        return image.start().await;
+    }
+
+    let container = image
+        .with_label(session::SHARED_LABEL, "true")
+        .start()
+        .await?;
+    // the container is removed once it is dropped if the reaper can't be started
+    reaper::ensure_shared_containers_removed(&client).await?;
+    Ok(container)
+}
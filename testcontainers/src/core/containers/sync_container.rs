@@ -1,24 +1,38 @@
-use std::{fmt, net::IpAddr};
+use std::{
+    fmt,
+    io::{self, Write},
+    net::IpAddr,
+    path::Path,
+    time::Duration,
+};
+
+use futures::StreamExt;
 
 use crate::{
-    core::{env, ports::Ports, ExecCommand},
-    ContainerAsync, Image,
+    core::{
+        logs::LogLine, ports::Ports, runtime, ContainerEvent, ContainerInfo, ContainerStats,
+        ExecCommand, ExecResult, ExitStatus, FileChange, HealthStatus, PortError, ProcessList,
+        ResourceLimits, Signal, Snapshot,
+    },
+    ContainerAsync, ExecSession, GenericImage, Image,
 };
 
 /// Represents a running docker container.
 ///
-/// Containers have a [`custom destructor`][drop_impl] that removes them as soon as they go out of scope:
+/// Containers have a [`custom destructor`][drop_impl] that removes them as soon as they go out of scope,
+/// unless their [`RemovePolicy`] keeps them:
 ///
 /// ```rust,no_run
 /// use testcontainers::*;
 /// #[test]
 /// fn a_test() {
-///     let container = MyImage::default().start();
+///     let container = MyImage::default().start().unwrap();
 ///     // Docker container is stopped/removed at the end of this scope.
 /// }
 /// ```
 ///
 /// [drop_impl]: struct.Container.html#impl-Drop
+/// [`RemovePolicy`]: crate::core::RemovePolicy
 pub struct Container<I: Image> {
     inner: Option<ActiveContainer<I>>,
 }
@@ -80,6 +94,56 @@ where
         self.async_impl().image_args()
     }
 
+    /// Returns an iterator over the lines the container writes to stdout, from its start on.
+    ///
+    /// The iterator blocks until the next line is written, and ends once the container stops.
+    pub fn stdout_lines(&self) -> impl Iterator<Item = Result<LogLine, io::Error>> + '_ {
+        let mut lines = self.async_impl().stdout_lines().boxed();
+        std::iter::from_fn(move || self.rt().block_on(lines.next()))
+    }
+
+    /// Returns an iterator over the lines the container writes to stderr, from its start on.
+    ///
+    /// The iterator blocks until the next line is written, and ends once the container stops.
+    pub fn stderr_lines(&self) -> impl Iterator<Item = Result<LogLine, io::Error>> + '_ {
+        let mut lines = self.async_impl().stderr_lines().boxed();
+        std::iter::from_fn(move || self.rt().block_on(lines.next()))
+    }
+
+    /// Returns a snapshot of the resource usage of the container, see
+    /// [`ContainerAsync::stats`].
+    pub fn stats(&self) -> ContainerStats {
+        self.rt().block_on(self.async_impl().stats())
+    }
+
+    /// Returns an iterator over the resource usage of the container, sampled every second until
+    /// the container stops.
+    pub fn stats_stream(&self) -> impl Iterator<Item = Result<ContainerStats, io::Error>> + '_ {
+        let mut stats = self.async_impl().stats_stream().boxed();
+        std::iter::from_fn(move || self.rt().block_on(stats.next()))
+    }
+
+    /// Returns an iterator over the lifecycle events of the container that happen from now on,
+    /// see [`ContainerAsync::events`].
+    pub fn events(&self) -> impl Iterator<Item = Result<ContainerEvent, io::Error>> + '_ {
+        let mut events = self.async_impl().events().boxed();
+        std::iter::from_fn(move || self.rt().block_on(events.next()))
+    }
+
+    /// Returns how the container was actually created and its current state, see
+    /// [`ContainerAsync::inspect`].
+    pub fn inspect(&self) -> ContainerInfo {
+        self.rt().block_on(self.async_impl().inspect())
+    }
+
+    /// Returns the result of the healthcheck of the container, see
+    /// [`ContainerAsync::health`].
+    pub fn health(&self) -> HealthStatus {
+        self.rt().block_on(self.async_impl().health())
+    }
+
+    /// Returns all ports of the container that are mapped to ports on the host, see
+    /// [`ContainerAsync::ports`].
     pub fn ports(&self) -> Ports {
         self.rt().block_on(self.async_impl().ports())
     }
@@ -116,29 +180,176 @@ where
             .block_on(self.async_impl().get_host_port_ipv6(internal_port))
     }
 
+    /// Returns the mapped host port for an internal port of this docker container, on the host's
+    /// IPv4 interfaces, see [`ContainerAsync::try_get_host_port_ipv4`].
+    pub fn try_get_host_port_ipv4(&self, internal_port: u16) -> Result<u16, PortError> {
+        self.rt()
+            .block_on(self.async_impl().try_get_host_port_ipv4(internal_port))
+    }
+
+    /// Returns the mapped host port for an internal port of this docker container, on the host's
+    /// IPv6 interfaces, see [`ContainerAsync::try_get_host_port_ipv6`].
+    pub fn try_get_host_port_ipv6(&self, internal_port: u16) -> Result<u16, PortError> {
+        self.rt()
+            .block_on(self.async_impl().try_get_host_port_ipv6(internal_port))
+    }
+
     /// Returns the bridge ip address of docker container as specified in NetworkSettings.Networks.IPAddress
     pub fn get_bridge_ip_address(&self) -> IpAddr {
         self.rt()
             .block_on(self.async_impl().get_bridge_ip_address())
     }
 
+    /// Returns the ip address of the container in the given network, see
+    /// [`ContainerAsync::get_network_ip`].
+    pub fn get_network_ip(&self, network_name: &str) -> IpAddr {
+        self.rt()
+            .block_on(self.async_impl().get_network_ip(network_name))
+    }
+
+    /// Returns the ip address of the gateway of the network of the container, see
+    /// [`ContainerAsync::get_gateway_ip`].
+    pub fn get_gateway_ip(&self) -> IpAddr {
+        self.rt().block_on(self.async_impl().get_gateway_ip())
+    }
+
     /// Returns the host ip address of docker container
     pub fn get_host_ip_address(&self) -> IpAddr {
         self.rt().block_on(self.async_impl().get_host_ip_address())
     }
 
+    /// Executes the command inside the container and returns once it meets its ready
+    /// condition, see [`ContainerAsync::exec`].
     pub fn exec(&self, cmd: ExecCommand) {
         self.rt().block_on(self.async_impl().exec(cmd));
     }
 
+    /// Executes the command inside the container and returns its output and exit code once it
+    /// has finished, see [`ContainerAsync::exec_to_completion`].
+    pub fn exec_to_completion(&self, cmd: ExecCommand) -> ExecResult {
+        self.rt()
+            .block_on(self.async_impl().exec_to_completion(cmd))
+    }
+
+    /// Starts the command inside the container with its stdin attached, see
+    /// [`ContainerAsync::exec_interactive`].
+    pub fn exec_interactive(&self, cmd: ExecCommand) -> ExecSession<'_> {
+        let session = self.rt().block_on(self.async_impl().exec_interactive(cmd));
+        ExecSession::new(self.rt(), session)
+    }
+
+    /// Returns the paths in the filesystem of the container that differ from its image, see
+    /// [`ContainerAsync::changes`].
+    pub fn changes(&self) -> Vec<FileChange> {
+        self.rt().block_on(self.async_impl().changes())
+    }
+
+    /// Writes the whole filesystem of the container as a tar archive to the writer, see
+    /// [`ContainerAsync::export`].
+    pub fn export(&self, mut writer: impl Write) -> io::Result<()> {
+        let mut archive = runtime::compat(self.async_impl().docker_client.export(self.id()));
+        while let Some(chunk) = self.rt().block_on(archive.next()) {
+            writer.write_all(&chunk?)?;
+        }
+        writer.flush()
+    }
+
+    /// Copies a file of the host into the running container, see
+    /// [`ContainerAsync::copy_file_to`].
+    pub fn copy_file_to(&self, source: impl AsRef<Path>, destination: impl Into<String>) {
+        self.rt()
+            .block_on(self.async_impl().copy_file_to(source, destination));
+    }
+
+    /// Copies the content of a directory of the host into the running container, see
+    /// [`ContainerAsync::copy_dir_to`].
+    pub fn copy_dir_to(&self, source: impl AsRef<Path>, destination: impl Into<String>) {
+        self.rt()
+            .block_on(self.async_impl().copy_dir_to(source, destination));
+    }
+
+    /// Returns the content of a file of the container, see [`ContainerAsync::read_file`].
+    pub fn read_file(&self, path: impl AsRef<str>) -> Vec<u8> {
+        self.rt().block_on(self.async_impl().read_file(path))
+    }
+
+    /// Copies the content of a directory of the container to the host, see
+    /// [`ContainerAsync::copy_dir_from`].
+    pub fn copy_dir_from(&self, path: impl AsRef<str>, destination: impl AsRef<Path>) {
+        self.rt()
+            .block_on(self.async_impl().copy_dir_from(path, destination));
+    }
+
+    /// Blocks until the main process of the container exits, see [`ContainerAsync::wait`].
+    pub fn wait(&self) -> ExitStatus {
+        self.rt().block_on(self.async_impl().wait())
+    }
+
+    /// Blocks until the main process of the container exits within the timeout, see
+    /// [`ContainerAsync::wait_with_timeout`].
+    pub fn wait_with_timeout(&self, timeout: Duration) -> ExitStatus {
+        self.rt()
+            .block_on(self.async_impl().wait_with_timeout(timeout))
+    }
+
+    /// Stops the container, see [`ContainerAsync::stop`].
     pub fn stop(&self) {
         self.rt().block_on(self.async_impl().stop());
     }
 
+    /// Stops the container within the timeout, see [`ContainerAsync::stop_with_timeout`].
+    pub fn stop_with_timeout(&self, timeout: Duration) {
+        self.rt()
+            .block_on(self.async_impl().stop_with_timeout(timeout));
+    }
+
+    /// Starts the container again after it was stopped, see [`ContainerAsync::start`].
     pub fn start(&self) {
         self.rt().block_on(self.async_impl().start());
     }
 
+    /// Commits the container to an image, from which fresh copies of it are started, see
+    /// [`ContainerAsync::snapshot`].
+    pub fn snapshot(&self) -> Snapshot<I>
+    where
+        I: Clone,
+    {
+        self.rt().block_on(self.async_impl().snapshot())
+    }
+
+    /// Creates the image `repo:tag` from the container, see [`ContainerAsync::commit`].
+    pub fn commit(&self, repo: impl Into<String>, tag: impl Into<String>) -> GenericImage {
+        self.rt().block_on(self.async_impl().commit(repo, tag))
+    }
+
+    /// Returns the processes running in the container, see [`ContainerAsync::top`].
+    pub fn top(&self) -> ProcessList {
+        self.rt().block_on(self.async_impl().top())
+    }
+
+    /// Returns the processes running in the container, listed by `ps` with the given
+    /// arguments, see [`ContainerAsync::top_with_ps_args`].
+    pub fn top_with_ps_args(&self, ps_args: &str) -> ProcessList {
+        self.rt()
+            .block_on(self.async_impl().top_with_ps_args(ps_args))
+    }
+
+    /// Updates the resource limits of the running container, see
+    /// [`ContainerAsync::update_limits`].
+    pub fn update_limits(&self, limits: ResourceLimits) {
+        self.rt().block_on(self.async_impl().update_limits(limits));
+    }
+
+    /// Sends the signal to the main process of the container, see [`ContainerAsync::kill`].
+    pub fn kill(&self, signal: Signal) {
+        self.rt().block_on(self.async_impl().kill(signal));
+    }
+
+    /// Restarts the container, see [`ContainerAsync::restart`].
+    pub fn restart(&self) {
+        self.rt().block_on(self.async_impl().restart());
+    }
+
     pub fn rm(mut self) {
         if let Some(active) = self.inner.take() {
             active.runtime.block_on(active.async_impl.rm());
@@ -151,21 +362,16 @@ where
     }
 
     /// Returns reference to inner `ContainerAsync`. It's safe to unwrap because it's `Some` until `Container` is dropped.
-    fn async_impl(&self) -> &ContainerAsync<I> {
+    pub(crate) fn async_impl(&self) -> &ContainerAsync<I> {
         &self.inner.as_ref().unwrap().async_impl
     }
 }
 
 impl<I: Image> Drop for Container<I> {
     fn drop(&mut self) {
-        if let Some(mut active) = self.inner.take() {
-            active.runtime.block_on(async {
-                drop(active.async_impl.network.take());
-                match active.async_impl.docker_client.config.command() {
-                    env::Command::Remove => active.async_impl.rm().await,
-                    env::Command::Keep => {}
-                }
-            });
+        if let Some(active) = self.inner.take() {
+            // the async container is removed according to its `RemovePolicy` once dropped
+            drop(active.async_impl);
         }
     }
 }
@@ -0,0 +1,172 @@
+//! Tar archives used to copy files and directories into and out of containers.
+//!
+//! The archives are extracted at the root of the container, so the entries are named after
+//! their absolute target path without the leading `/`. Docker creates missing parent
+//! directories while extracting.
+//!
+//! Archives downloaded from a container contain a single entry named after the last
+//! component of the requested path, which is followed by the content of a directory.
+
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Component, Path, PathBuf},
+};
+
+/// Archives the file at the given host path, to be extracted at the target path.
+pub(crate) fn tar_file(source: &Path, target: &str) -> io::Result<Vec<u8>> {
+    let mut builder = tar::Builder::new(Vec::new());
+    builder.append_path_with_name(source, archive_path(target))?;
+    builder.into_inner()
+}
+
+/// Archives the content of the directory at the given host path, to be extracted into the
+/// target directory.
+pub(crate) fn tar_dir(source: &Path, target: &str) -> io::Result<Vec<u8>> {
+    let mut builder = tar::Builder::new(Vec::new());
+    builder.append_dir_all(archive_path(target), source)?;
+    builder.into_inner()
+}
+
+/// Returns the content of the file in an archive downloaded from a container.
+pub(crate) fn read_file(archive: &[u8]) -> io::Result<Vec<u8>> {
+    let mut archive = tar::Archive::new(archive);
+    let mut entry = archive
+        .entries()?
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "archive is empty"))??;
+    if !entry.header().entry_type().is_file() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "path is not a regular file",
+        ));
+    }
+
+    let mut content = Vec::new();
+    entry.read_to_end(&mut content)?;
+    Ok(content)
+}
+
+/// Extracts the content of the directory in an archive downloaded from a container into the
+/// given host directory, which is created if it does not exist.
+pub(crate) fn unpack_dir(archive: &[u8], destination: &Path) -> io::Result<()> {
+    fs::create_dir_all(destination)?;
+
+    for entry in tar::Archive::new(archive).entries()? {
+        let mut entry = entry?;
+        // strip the entry of the directory itself, its content is extracted into the destination
+        let path: PathBuf = entry.path()?.components().skip(1).collect();
+        if path.as_os_str().is_empty() {
+            continue;
+        }
+        if !path.components().all(|c| matches!(c, Component::Normal(_))) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("archive contains invalid path {}", path.display()),
+            ));
+        }
+
+        let target = destination.join(path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        entry.unpack(target)?;
+    }
+
+    Ok(())
+}
+
+fn archive_path(target: &str) -> &str {
+    match target.trim_start_matches('/') {
+        "" => ".",
+        path => path,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(archive: &[u8]) -> Vec<(PathBuf, String)> {
+        tar::Archive::new(archive)
+            .entries()
+            .unwrap()
+            .map(|entry| {
+                let mut entry = entry.unwrap();
+                let path = entry.path().unwrap().into_owned();
+                let mut content = String::new();
+                entry.read_to_string(&mut content).unwrap();
+                (path, content)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn archives_file_at_target_path() {
+        let source = std::env::temp_dir().join(format!("tc-copy-file-{}", std::process::id()));
+        fs::write(&source, "hello").unwrap();
+
+        let archive = tar_file(&source, "/etc/app/config.toml").unwrap();
+        fs::remove_file(&source).unwrap();
+
+        assert_eq!(
+            entries(&archive),
+            vec![(PathBuf::from("etc/app/config.toml"), "hello".to_string())]
+        );
+    }
+
+    #[test]
+    fn archives_directory_content_into_target_directory() {
+        let source = std::env::temp_dir().join(format!("tc-copy-dir-{}", std::process::id()));
+        fs::create_dir_all(source.join("nested")).unwrap();
+        fs::write(source.join("nested/data.sql"), "select 1;").unwrap();
+
+        let archive = tar_dir(&source, "/fixtures").unwrap();
+        fs::remove_dir_all(&source).unwrap();
+
+        let files: Vec<_> = entries(&archive)
+            .into_iter()
+            .filter(|(_, content)| !content.is_empty())
+            .collect();
+        assert_eq!(
+            files,
+            vec![(
+                PathBuf::from("fixtures/nested/data.sql"),
+                "select 1;".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn reads_file_of_downloaded_archive() {
+        let mut builder = tar::Builder::new(Vec::new());
+        let mut header = tar::Header::new_gnu();
+        header.set_size(6);
+        header.set_mode(0o644);
+        builder
+            .append_data(&mut header, "report.txt", "passed".as_bytes())
+            .unwrap();
+        let archive = builder.into_inner().unwrap();
+
+        assert_eq!(read_file(&archive).unwrap(), b"passed");
+    }
+
+    #[test]
+    fn unpacks_directory_of_downloaded_archive() {
+        let source = std::env::temp_dir().join(format!("tc-unpack-src-{}", std::process::id()));
+        fs::create_dir_all(source.join("nested")).unwrap();
+        fs::write(source.join("nested/out.log"), "done").unwrap();
+        // the archive of a container directory starts with the directory itself
+        let archive = tar_dir(&source, "/reports").unwrap();
+
+        let destination =
+            std::env::temp_dir().join(format!("tc-unpack-dest-{}", std::process::id()));
+        unpack_dir(&archive, &destination).unwrap();
+
+        let content = fs::read_to_string(destination.join("nested/out.log")).unwrap();
+        fs::remove_dir_all(&source).unwrap();
+        fs::remove_dir_all(&destination).unwrap();
+        assert_eq!(content, "done");
+        assert!(read_file(&archive).is_err());
+    }
+}
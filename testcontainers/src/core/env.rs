@@ -1,4 +1,5 @@
 mod config;
+mod context;
 
 pub(crate) use config::{Command, Config};
 
@@ -17,6 +18,17 @@ impl GetEnvValue for Os {
     }
 }
 
+/// Whether containers are planned instead of started, taken from the `TESTCONTAINERS_DRY_RUN`
+/// environment variable, see [`ContainerPlan`].
+///
+/// Read on its own rather than as part of the [`Config`], so that a dry run doesn't need a
+/// docker daemon.
+///
+/// [`ContainerPlan`]: crate::core::ContainerPlan
+pub(crate) fn dry_run<E: GetEnvValue>() -> bool {
+    E::get_env_value("TESTCONTAINERS_DRY_RUN").is_some_and(|v| v == "true" || v == "1")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -48,6 +60,24 @@ mod tests {
         assert_eq!(cmd.parse::<Command>(), Ok(Command::Keep))
     }
 
+    #[derive(Debug)]
+    struct FakeEnvDryRun;
+
+    impl GetEnvValue for FakeEnvDryRun {
+        fn get_env_value(key: &str) -> Option<String> {
+            match key {
+                "TESTCONTAINERS_DRY_RUN" => Some("true".to_owned()),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn dry_run_is_taken_from_testcontainers_env_variable() {
+        assert!(dry_run::<FakeEnvDryRun>());
+        assert!(!dry_run::<FakeEnvAlwaysKeep>());
+    }
+
     #[test]
     fn default_command_is_remove() {
         let cmd = Command::default();
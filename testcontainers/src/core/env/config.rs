@@ -1,9 +1,16 @@
-use crate::core::env::GetEnvValue;
+use crate::core::{
+    env::{
+        context::{self, DockerContext},
+        GetEnvValue,
+    },
+    LogDump, RemovePolicy,
+};
 use serde::Deserialize;
 use serde_with::serde_as;
 use std::{
     path::{Path, PathBuf},
     str::FromStr,
+    time::Duration,
 };
 use url::Url;
 
@@ -17,13 +24,29 @@ pub const DEFAULT_DOCKER_HOST: &str = "unix:///var/run/docker.sock";
 #[cfg(windows)]
 pub const DEFAULT_DOCKER_HOST: &str = "npipe:////./pipe/docker_engine";
 
+/// How long to wait for a container to become ready, unless configured otherwise.
+const DEFAULT_STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How often a pull that failed for a transient reason is retried, unless configured otherwise.
+const DEFAULT_PULL_RETRIES: u32 = 2;
+
 #[derive(Debug, Default)]
 pub(crate) struct Config {
     tc_host: Option<Url>,
     host: Option<Url>,
+    context: Option<DockerContext>,
+    discovered_socket: Option<Url>,
     tls_verify: Option<bool>,
     cert_path: Option<PathBuf>,
     command: Option<Command>,
+    startup_timeout: Option<Duration>,
+    log_dump: Option<LogDump>,
+    keep_on_failure: Option<bool>,
+    ryuk_disabled: Option<bool>,
+    ryuk_privileged: Option<bool>,
+    docker_socket_override: Option<String>,
+    pull_retries: Option<u32>,
+    registry_mirrors: Vec<String>,
 }
 
 #[serde_as]
@@ -62,16 +85,44 @@ impl Config {
         let properties = TestcontainersProperties::load().await.unwrap_or_default();
 
         // Environment variables take precedence over properties
-        Self {
+        let config = Self {
             tc_host: env_config.tc_host.or(properties.tc_host),
             host: env_config.host.or(properties.host),
+            context: env_config.context,
+            discovered_socket: env_config.discovered_socket,
             tls_verify: env_config.tls_verify.or(properties.tls_verify),
             cert_path: env_config.cert_path.or(properties.cert_path),
             command: env_config.command,
+            startup_timeout: env_config.startup_timeout,
+            log_dump: env_config.log_dump,
+            keep_on_failure: env_config.keep_on_failure,
+            ryuk_disabled: env_config.ryuk_disabled,
+            ryuk_privileged: env_config.ryuk_privileged,
+            docker_socket_override: env_config.docker_socket_override,
+            pull_retries: env_config.pull_retries,
+            registry_mirrors: env_config.registry_mirrors,
+        };
+
+        let known_sockets = known_sockets::<E>();
+        if config.tc_host.is_none()
+            && config.host.is_none()
+            && config.context.is_none()
+            && config.discovered_socket.is_none()
+            && !known_sockets.is_empty()
+        {
+            let known_sockets: Vec<_> = known_sockets
+                .iter()
+                .map(|socket| socket.display().to_string())
+                .collect();
+            log::warn!(
+                "No docker host is configured and none of the known docker sockets exists: {}. Start a docker daemon, or set DOCKER_HOST or the docker.host property in ~/{TESTCONTAINERS_PROPERTIES}",
+                known_sockets.join(", ")
+            );
         }
+        config
     }
 
-    fn load_from_env_config<E>() -> Self
+    pub(crate) fn load_from_env_config<E>() -> Self
     where
         E: GetEnvValue,
     {
@@ -80,16 +131,65 @@ impl Config {
             .map(FromStr::from_str)
             .transpose()
             .expect("Invalid DOCKER_HOST");
-        let tls_verify = E::get_env_value("DOCKER_TLS_VERIFY").map(|v| v == "1");
+        let context = context::current::<E>();
+        let discovered_socket = discover_socket::<E>(|path| path.exists());
+        let tls_verify = E::get_env_value("DOCKER_TLS_VERIFY").map(|v| v == "1" || v == "true");
         let cert_path = E::get_env_value("DOCKER_CERT_PATH").map(PathBuf::from);
         let command = E::get_env_value("TESTCONTAINERS_COMMAND").and_then(|v| v.parse().ok());
+        let startup_timeout = E::get_env_value("TESTCONTAINERS_STARTUP_TIMEOUT")
+            .and_then(|v| match v.parse() {
+                Ok(seconds) => Some(Duration::from_secs(seconds)),
+                Err(err) => {
+                    log::warn!(
+                        "Ignoring invalid TESTCONTAINERS_STARTUP_TIMEOUT '{v}', expected seconds: {err}"
+                    );
+                    None
+                }
+            });
+        let log_dump = E::get_env_value("TESTCONTAINERS_LOG_DUMP").and_then(|v| v.parse().ok());
+        let keep_on_failure =
+            E::get_env_value("TESTCONTAINERS_KEEP_ON_FAILURE").map(|v| v == "true" || v == "1");
+        let ryuk_disabled =
+            E::get_env_value("TESTCONTAINERS_RYUK_DISABLED").map(|v| v == "true" || v == "1");
+        let ryuk_privileged =
+            E::get_env_value("TESTCONTAINERS_RYUK_PRIVILEGED").map(|v| v == "true" || v == "1");
+        let docker_socket_override = E::get_env_value("TESTCONTAINERS_DOCKER_SOCKET_OVERRIDE");
+        let pull_retries =
+            E::get_env_value("TESTCONTAINERS_PULL_RETRIES").and_then(|v| match v.parse() {
+                Ok(retries) => Some(retries),
+                Err(err) => {
+                    log::warn!(
+                        "Ignoring invalid TESTCONTAINERS_PULL_RETRIES '{v}', expected a number: {err}"
+                    );
+                    None
+                }
+            });
+        let registry_mirrors = E::get_env_value("TESTCONTAINERS_REGISTRY_MIRRORS")
+            .map(|v| {
+                v.split(',')
+                    .map(str::trim)
+                    .filter(|mirror| !mirror.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
 
         Config {
             host,
             tc_host: None,
+            context,
+            discovered_socket,
             command,
             tls_verify,
             cert_path,
+            startup_timeout,
+            log_dump,
+            keep_on_failure,
+            ryuk_disabled,
+            ryuk_privileged,
+            docker_socket_override,
+            pull_retries,
+            registry_mirrors,
         }
     }
 
@@ -97,26 +197,180 @@ impl Config {
     ///  1. Docker host from the "tc.host" property in the ~/.testcontainers.properties file.
     ///  2. DOCKER_HOST environment variable.
     ///  3. Docker host from the "docker.host" property in the ~/.testcontainers.properties file.
-    ///  4. Else, the default Docker socket will be returned.
+    ///  4. The host of the docker context selected by the DOCKER_CONTEXT environment variable or
+    ///     `docker context use`, e.g. of Colima or Docker Desktop.
+    ///  5. The first of the known sockets of Docker, Docker Desktop, Colima, Rancher Desktop and
+    ///     Podman that exists, see [`known_sockets`].
+    ///  6. Else, the default Docker socket will be returned.
     pub(crate) fn docker_host(&self) -> Url {
         self.tc_host
             .as_ref()
             .or(self.host.as_ref())
+            .or(self.context().map(|context| &context.host))
+            .or(self.discovered_socket.as_ref())
             .cloned()
             .unwrap_or_else(|| Url::from_str(DEFAULT_DOCKER_HOST).unwrap())
     }
 
+    /// Returns the docker context, unless a docker host is configured, which takes precedence.
+    fn context(&self) -> Option<&DockerContext> {
+        if self.tc_host.is_some() || self.host.is_some() {
+            return None;
+        }
+        self.context.as_ref()
+    }
+
+    /// Whether the connection to the Docker host uses TLS, which is the case if the
+    /// `DOCKER_TLS_VERIFY` environment variable is set, or if the docker context has TLS
+    /// certificates.
     pub(crate) fn tls_verify(&self) -> bool {
-        self.tls_verify.unwrap_or_default()
+        self.tls_verify
+            .or_else(|| self.context().map(|context| context.tls_path.is_some()))
+            .unwrap_or_default()
     }
 
-    pub(crate) fn cert_path(&self) -> Option<&Path> {
-        self.cert_path.as_deref()
+    /// The directory with the `ca.pem`, `cert.pem` and `key.pem` files of a TLS connection to
+    /// the Docker host, taken from the `DOCKER_CERT_PATH` environment variable or the
+    /// "docker.cert.path" property, or else the certificates of the docker context. Defaults to
+    /// `~/.docker`, like for the docker CLI.
+    pub(crate) fn cert_path(&self) -> Option<PathBuf> {
+        self.cert_path
+            .clone()
+            .or_else(|| self.context().and_then(|context| context.tls_path.clone()))
+            .or_else(|| dirs::home_dir().map(|home| home.join(".docker")))
     }
 
     pub(crate) fn command(&self) -> Command {
         self.command.unwrap_or_default()
     }
+
+    /// The crate-wide startup timeout, taken from the `TESTCONTAINERS_STARTUP_TIMEOUT` environment
+    /// variable (in seconds). Defaults to 60 seconds.
+    pub(crate) fn startup_timeout(&self) -> Duration {
+        self.startup_timeout.unwrap_or(DEFAULT_STARTUP_TIMEOUT)
+    }
+
+    /// When the logs of containers are written to files, taken from the
+    /// `TESTCONTAINERS_LOG_DUMP` environment variable. Defaults to [`LogDump::OnFailure`].
+    pub(crate) fn log_dump(&self) -> LogDump {
+        self.log_dump.unwrap_or_default()
+    }
+
+    /// When containers are removed once they are dropped: never with the `keep` command, only if
+    /// their test succeeded if the `TESTCONTAINERS_KEEP_ON_FAILURE` environment variable is set,
+    /// and always otherwise.
+    pub(crate) fn remove_policy(&self) -> RemovePolicy {
+        match self.command() {
+            Command::Keep => RemovePolicy::Never,
+            Command::Remove if self.keep_on_failure.unwrap_or_default() => RemovePolicy::OnSuccess,
+            Command::Remove => RemovePolicy::Always,
+        }
+    }
+
+    /// How often a pull that failed for a transient reason, like a registry that responds with
+    /// a 5xx status or times out, is retried, with an exponential backoff. Taken from the
+    /// `TESTCONTAINERS_PULL_RETRIES` environment variable, and defaults to 2.
+    pub(crate) fn pull_retries(&self) -> u32 {
+        self.pull_retries.unwrap_or(DEFAULT_PULL_RETRIES)
+    }
+
+    /// The registry mirrors that Docker Hub images are pulled from once all retries of a pull
+    /// failed, taken from the comma-separated `TESTCONTAINERS_REGISTRY_MIRRORS` environment
+    /// variable, e.g. `mirror.gcr.io`.
+    pub(crate) fn registry_mirrors(&self) -> &[String] {
+        &self.registry_mirrors
+    }
+
+    /// Whether the resource reaper is disabled, taken from the `TESTCONTAINERS_RYUK_DISABLED`
+    /// environment variable, e.g. on CI runners that do not allow to mount the docker socket.
+    /// `None` if it is not set, in which case the default depends on the engine.
+    pub(crate) fn ryuk_disabled(&self) -> Option<bool> {
+        self.ryuk_disabled
+    }
+
+    /// Whether the resource reaper runs privileged, taken from the
+    /// `TESTCONTAINERS_RYUK_PRIVILEGED` environment variable, e.g. for SELinux enabled hosts.
+    /// `None` if it is not set, in which case the default depends on the engine.
+    pub(crate) fn ryuk_privileged(&self) -> Option<bool> {
+        self.ryuk_privileged
+    }
+
+    /// The path of the docker socket on the docker host, which is mounted into the resource
+    /// reaper and forwarded from an `ssh` docker host. Taken from the
+    /// `TESTCONTAINERS_DOCKER_SOCKET_OVERRIDE` environment variable, and defaults to the path
+    /// of a `unix` docker host and `/var/run/docker.sock` otherwise.
+    ///
+    /// Sockets in the home directory, like the ones of Docker Desktop, Colima and Rancher
+    /// Desktop, are forwarded from a virtual machine, in which the daemon listens on
+    /// `/var/run/docker.sock`.
+    pub(crate) fn docker_socket(&self) -> String {
+        if let Some(socket) = &self.docker_socket_override {
+            return socket.clone();
+        }
+
+        let host = self.docker_host();
+        match host.scheme() {
+            "unix" if !is_in_home_dir(host.path()) => host.path().to_string(),
+            _ => "/var/run/docker.sock".to_string(),
+        }
+    }
+}
+
+/// Returns the sockets that docker hosts listen on unless configured otherwise, in the order
+/// they are looked for: the default Docker socket, the ones of a rootless Docker, Docker
+/// Desktop, Colima and Rancher Desktop, and the ones of a rootless and a rootful Podman.
+#[cfg(unix)]
+fn known_sockets<E>() -> Vec<PathBuf>
+where
+    E: GetEnvValue,
+{
+    let runtime_dir = E::get_env_value("XDG_RUNTIME_DIR").map(PathBuf::from);
+
+    let mut sockets = vec![PathBuf::from(
+        DEFAULT_DOCKER_HOST.trim_start_matches("unix://"),
+    )];
+    sockets.extend(runtime_dir.as_ref().map(|dir| dir.join("docker.sock")));
+    if let Some(home_dir) = dirs::home_dir() {
+        sockets.extend(
+            [
+                ".docker/run/docker.sock",
+                ".docker/desktop/docker.sock",
+                ".colima/default/docker.sock",
+                ".colima/docker.sock",
+                ".rd/docker.sock",
+            ]
+            .map(|socket| home_dir.join(socket)),
+        );
+    }
+    sockets.extend(runtime_dir.map(|dir| dir.join("podman").join("podman.sock")));
+    sockets.push(PathBuf::from("/run/podman/podman.sock"));
+    sockets
+}
+
+#[cfg(not(unix))]
+fn known_sockets<E>() -> Vec<PathBuf>
+where
+    E: GetEnvValue,
+{
+    Vec::new()
+}
+
+/// Returns the first of the [`known_sockets`] that exists.
+fn discover_socket<E>(exists: impl Fn(&Path) -> bool) -> Option<Url>
+where
+    E: GetEnvValue,
+{
+    known_sockets::<E>()
+        .into_iter()
+        .find(|socket| exists(socket))
+        .map(|socket| {
+            Url::from_str(&format!("unix://{}", socket.display()))
+                .expect("socket path is a valid url")
+        })
+}
+
+fn is_in_home_dir(path: &str) -> bool {
+    dirs::home_dir().is_some_and(|home_dir| Path::new(path).starts_with(home_dir))
 }
 
 /// The commands available to the `TESTCONTAINERS_COMMAND` env variable.
@@ -140,3 +394,123 @@ impl FromStr for Command {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeEnvDockerInDocker;
+
+    impl GetEnvValue for FakeEnvDockerInDocker {
+        fn get_env_value(key: &str) -> Option<String> {
+            match key {
+                "DOCKER_HOST" => Some("tcp://docker:2376".to_owned()),
+                "DOCKER_TLS_VERIFY" => Some("1".to_owned()),
+                "DOCKER_CERT_PATH" => Some("/certs/client".to_owned()),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn tls_is_configured_by_docker_env_variables() {
+        let config = Config::load_from_env_config::<FakeEnvDockerInDocker>();
+
+        assert_eq!(config.docker_host().as_str(), "tcp://docker:2376");
+        assert!(config.tls_verify());
+        assert_eq!(config.cert_path(), Some(PathBuf::from("/certs/client")));
+    }
+
+    struct FakeEnvInvalidNumbers;
+
+    impl GetEnvValue for FakeEnvInvalidNumbers {
+        fn get_env_value(key: &str) -> Option<String> {
+            match key {
+                "TESTCONTAINERS_STARTUP_TIMEOUT" => Some("1m".to_owned()),
+                "TESTCONTAINERS_PULL_RETRIES" => Some("-1".to_owned()),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_startup_timeout_is_ignored() {
+        let config = Config::load_from_env_config::<FakeEnvInvalidNumbers>();
+
+        assert_eq!(config.startup_timeout(), DEFAULT_STARTUP_TIMEOUT);
+    }
+
+    #[test]
+    fn invalid_pull_retries_are_ignored() {
+        let config = Config::load_from_env_config::<FakeEnvInvalidNumbers>();
+
+        assert_eq!(config.pull_retries(), DEFAULT_PULL_RETRIES);
+    }
+
+    #[cfg(unix)]
+    struct FakeEnvRootless;
+
+    #[cfg(unix)]
+    impl GetEnvValue for FakeEnvRootless {
+        fn get_env_value(key: &str) -> Option<String> {
+            match key {
+                "XDG_RUNTIME_DIR" => Some("/run/user/1000".to_owned()),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn discovers_first_known_socket_that_exists() {
+        let home_dir = dirs::home_dir().unwrap();
+        let colima = home_dir.join(".colima/default/docker.sock");
+        let colima = colima.to_str().unwrap();
+        let discover_socket = |existing: &[&str]| {
+            discover_socket::<FakeEnvRootless>(|path| existing.iter().any(|e| Path::new(e) == path))
+                .map(|url| url.path().to_owned())
+        };
+
+        assert_eq!(
+            discover_socket(&["/var/run/docker.sock", "/run/podman/podman.sock"]),
+            Some("/var/run/docker.sock".to_owned())
+        );
+        assert_eq!(
+            discover_socket(&["/run/user/1000/docker.sock", colima]),
+            Some("/run/user/1000/docker.sock".to_owned())
+        );
+        assert_eq!(
+            discover_socket(&[colima, "/run/podman/podman.sock"]),
+            Some(colima.to_owned())
+        );
+        assert_eq!(
+            discover_socket(&[
+                "/run/user/1000/podman/podman.sock",
+                "/run/podman/podman.sock"
+            ]),
+            Some("/run/user/1000/podman/podman.sock".to_owned())
+        );
+        assert_eq!(discover_socket(&[]), None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn sockets_of_virtual_machines_are_mounted_from_default_path() {
+        let socket_of = |socket: &str| {
+            Config {
+                discovered_socket: Some(Url::from_str(&format!("unix://{socket}")).unwrap()),
+                ..Config::default()
+            }
+            .docker_socket()
+        };
+        let colima = dirs::home_dir()
+            .unwrap()
+            .join(".colima/default/docker.sock");
+
+        assert_eq!(socket_of(colima.to_str().unwrap()), "/var/run/docker.sock");
+        assert_eq!(
+            socket_of("/run/user/1000/docker.sock"),
+            "/run/user/1000/docker.sock"
+        );
+    }
+}
@@ -0,0 +1,273 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use serde::Deserialize;
+use url::Url;
+
+use crate::core::env::GetEnvValue;
+
+/// The name of the context that uses `DOCKER_HOST` or the default socket.
+const DEFAULT_CONTEXT: &str = "default";
+
+/// The docker endpoint of a context of the docker CLI, e.g. the one `docker context use`
+/// selected for Colima, Docker Desktop or a remote host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DockerContext {
+    pub(crate) name: String,
+    pub(crate) host: Url,
+    /// The directory with the `ca.pem`, `cert.pem` and `key.pem` files of the endpoint, if it
+    /// uses TLS.
+    pub(crate) tls_path: Option<PathBuf>,
+}
+
+/// The part of `config.json` of the docker CLI that selects the context.
+#[derive(Debug, Default, Deserialize)]
+struct CliConfig {
+    #[serde(rename = "currentContext")]
+    current_context: Option<String>,
+}
+
+/// The `meta.json` of a context.
+#[derive(Debug, Deserialize)]
+struct ContextMeta {
+    #[serde(rename = "Name")]
+    name: String,
+    #[serde(rename = "Endpoints", default)]
+    endpoints: HashMap<String, Endpoint>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Endpoint {
+    #[serde(rename = "Host")]
+    host: Option<String>,
+}
+
+/// Returns the context that is selected by the `DOCKER_CONTEXT` environment variable or else
+/// by `docker context use`, unless it is the default context.
+///
+/// The configuration of the docker CLI is read from `DOCKER_CONFIG`, or else from `~/.docker`.
+pub(crate) fn current<E>() -> Option<DockerContext>
+where
+    E: GetEnvValue,
+{
+    let config_dir = E::get_env_value("DOCKER_CONFIG")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".docker")))?;
+    let name = E::get_env_value("DOCKER_CONTEXT").or_else(|| {
+        let config = fs::read(config_dir.join("config.json")).ok()?;
+        serde_json::from_slice::<CliConfig>(&config)
+            .ok()?
+            .current_context
+    })?;
+    if name.is_empty() || name == DEFAULT_CONTEXT {
+        return None;
+    }
+
+    match find(&config_dir, &name) {
+        Some(context) => {
+            log::debug!("Using docker context {} at {}", context.name, context.host);
+            Some(context)
+        }
+        None => {
+            log::warn!(
+                "Docker context {name} was not found in {}",
+                config_dir.display()
+            );
+            None
+        }
+    }
+}
+
+/// Finds the context with the name among the contexts in the configuration directory, which
+/// are stored in directories named by the digest of their names.
+fn find(config_dir: &Path, name: &str) -> Option<DockerContext> {
+    let contexts = config_dir.join("contexts");
+    fs::read_dir(contexts.join("meta"))
+        .ok()?
+        .filter_map(Result::ok)
+        .find_map(|entry| {
+            let meta = fs::read(entry.path().join("meta.json")).ok()?;
+            let mut meta: ContextMeta = serde_json::from_slice(&meta).ok()?;
+            if meta.name != name {
+                return None;
+            }
+
+            let host = meta.endpoints.remove("docker")?.host?;
+            let host = match Url::from_str(&host) {
+                Ok(host) => host,
+                Err(err) => {
+                    log::warn!(
+                        "Ignoring docker context {name} with the invalid host {host}: {err}"
+                    );
+                    return None;
+                }
+            };
+            let tls_path = contexts.join("tls").join(entry.file_name()).join("docker");
+            Some(DockerContext {
+                name: meta.name,
+                host,
+                tls_path: tls_path.join("ca.pem").is_file().then_some(tls_path),
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::OnceLock;
+
+    use super::*;
+
+    /// Writes a configuration of the docker CLI with the contexts to a new directory.
+    fn config_dir(current_context: &str, contexts: &[(&str, &str, bool)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "testcontainers-docker-config-{}",
+            ulid::Ulid::new().to_string().to_lowercase()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("config.json"),
+            format!(r#"{{"auths": {{}}, "currentContext": "{current_context}"}}"#),
+        )
+        .unwrap();
+
+        for (index, (name, host, tls)) in contexts.iter().enumerate() {
+            let meta = dir.join("contexts/meta").join(index.to_string());
+            fs::create_dir_all(&meta).unwrap();
+            fs::write(
+                meta.join("meta.json"),
+                format!(
+                    r#"{{"Name": "{name}", "Metadata": {{}}, "Endpoints": {{"docker": {{"Host": "{host}", "SkipTLSVerify": false}}}}}}"#
+                ),
+            )
+            .unwrap();
+            if *tls {
+                let tls = dir
+                    .join("contexts/tls")
+                    .join(index.to_string())
+                    .join("docker");
+                fs::create_dir_all(&tls).unwrap();
+                fs::write(tls.join("ca.pem"), "").unwrap();
+            }
+        }
+        dir
+    }
+
+    #[test]
+    fn finds_context_by_name() {
+        let dir = config_dir(
+            "colima",
+            &[
+                ("remote", "tcp://build-server:2376", true),
+                (
+                    "colima",
+                    "unix:///Users/dev/.colima/default/docker.sock",
+                    false,
+                ),
+            ],
+        );
+
+        assert_eq!(
+            find(&dir, "colima"),
+            Some(DockerContext {
+                name: "colima".to_string(),
+                host: Url::from_str("unix:///Users/dev/.colima/default/docker.sock").unwrap(),
+                tls_path: None,
+            })
+        );
+        assert_eq!(
+            find(&dir, "remote").and_then(|context| context.tls_path),
+            Some(dir.join("contexts/tls/0/docker"))
+        );
+        assert_eq!(find(&dir, "missing"), None);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn skips_context_with_invalid_host() {
+        let dir = config_dir(
+            "broken",
+            &[
+                ("broken", "not a url", false),
+                ("colima", "unix:///Users/dev/.colima/docker.sock", false),
+            ],
+        );
+
+        assert_eq!(find(&dir, "broken"), None);
+        assert!(find(&dir, "colima").is_some());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    /// The configuration of the docker CLI that the fake environments below point to, which
+    /// selects the `colima` context.
+    static CONFIG_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+    fn fake_config_dir() -> Option<String> {
+        let dir = CONFIG_DIR.get_or_init(|| {
+            config_dir(
+                "colima",
+                &[
+                    ("remote", "tcp://build-server:2376", true),
+                    ("colima", "unix:///Users/dev/.colima/docker.sock", false),
+                ],
+            )
+        });
+        Some(dir.display().to_string())
+    }
+
+    #[derive(Debug)]
+    struct FakeEnvContextInConfig;
+
+    impl GetEnvValue for FakeEnvContextInConfig {
+        fn get_env_value(key: &str) -> Option<String> {
+            match key {
+                "DOCKER_CONFIG" => fake_config_dir(),
+                _ => None,
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct FakeEnvDockerContext;
+
+    impl GetEnvValue for FakeEnvDockerContext {
+        fn get_env_value(key: &str) -> Option<String> {
+            match key {
+                "DOCKER_CONFIG" => fake_config_dir(),
+                "DOCKER_CONTEXT" => Some("remote".to_string()),
+                _ => None,
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct FakeEnvDefaultContext;
+
+    impl GetEnvValue for FakeEnvDefaultContext {
+        fn get_env_value(key: &str) -> Option<String> {
+            match key {
+                "DOCKER_CONFIG" => fake_config_dir(),
+                "DOCKER_CONTEXT" => Some(DEFAULT_CONTEXT.to_string()),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn current_context_is_selected_by_env_variable_or_else_by_config() {
+        assert_eq!(
+            current::<FakeEnvContextInConfig>().map(|context| context.name),
+            Some("colima".to_string())
+        );
+        assert_eq!(
+            current::<FakeEnvDockerContext>().map(|context| context.host),
+            Some(Url::from_str("tcp://build-server:2376").unwrap())
+        );
+        assert_eq!(current::<FakeEnvDefaultContext>(), None);
+    }
+}
@@ -0,0 +1,133 @@
+//! Parsing of environment files in the dotenv format, as used by `docker compose`.
+
+/// Parses the variables of an environment file, in the order they are defined.
+///
+/// Supports comments, empty lines, an optional `export` prefix and single or double quoted
+/// values. Double quoted values may span multiple lines and contain the escape sequences
+/// `\n`, `\t`, `\"` and `\\`.
+pub(crate) fn parse(content: &str) -> Result<Vec<(String, String)>, String> {
+    let mut variables = Vec::new();
+    let mut lines = content.lines().enumerate();
+
+    while let Some((index, line)) = lines.next() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| format!("line {}: expected KEY=VALUE", index + 1))?;
+        let key = key.trim();
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return Err(format!("line {}: invalid key '{key}'", index + 1));
+        }
+
+        let value = value.trim_start();
+        let value = if let Some(quoted) = value.strip_prefix('"') {
+            let mut quoted = quoted.to_string();
+            // a double quoted value continues until the closing quote
+            loop {
+                if let Some(value) = parse_double_quoted(&quoted) {
+                    break value;
+                }
+                let (_, next) = lines
+                    .next()
+                    .ok_or_else(|| format!("line {}: unterminated quoted value", index + 1))?;
+                quoted.push('\n');
+                quoted.push_str(next);
+            }
+        } else if let Some(quoted) = value.strip_prefix('\'') {
+            let end = quoted
+                .find('\'')
+                .ok_or_else(|| format!("line {}: unterminated quoted value", index + 1))?;
+            quoted[..end].to_string()
+        } else {
+            // unquoted values end at an inline comment
+            let end = value.find(" #").unwrap_or(value.len());
+            value[..end].trim_end().to_string()
+        };
+
+        variables.push((key.to_string(), value));
+    }
+
+    Ok(variables)
+}
+
+/// Returns the unescaped content up to the closing quote, or `None` if it is missing.
+fn parse_double_quoted(quoted: &str) -> Option<String> {
+    let mut value = String::new();
+    let mut chars = quoted.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return Some(value),
+            '\\' => match chars.next()? {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                other @ ('"' | '\\') => value.push(other),
+                other => {
+                    value.push('\\');
+                    value.push(other);
+                }
+            },
+            c => value.push(c),
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_variables() {
+        let content = r#"
+# database settings
+POSTGRES_USER=postgres
+export POSTGRES_DB = test # inline comment
+POSTGRES_PASSWORD='sec#ret'
+GREETING="hello\n\"world\""
+CERT="-----BEGIN-----
+abc
+-----END-----"
+EMPTY=
+"#;
+
+        let variables = parse(content).unwrap();
+
+        assert_eq!(
+            variables,
+            vec![
+                ("POSTGRES_USER".to_string(), "postgres".to_string()),
+                ("POSTGRES_DB".to_string(), "test".to_string()),
+                ("POSTGRES_PASSWORD".to_string(), "sec#ret".to_string()),
+                ("GREETING".to_string(), "hello\n\"world\"".to_string()),
+                (
+                    "CERT".to_string(),
+                    "-----BEGIN-----\nabc\n-----END-----".to_string()
+                ),
+                ("EMPTY".to_string(), String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_lines() {
+        assert_eq!(
+            parse("FOO=bar\nBAZ").unwrap_err(),
+            "line 2: expected KEY=VALUE"
+        );
+        assert_eq!(
+            parse("FOO=\"bar").unwrap_err(),
+            "line 1: unterminated quoted value"
+        );
+        assert_eq!(
+            parse("MY KEY=bar").unwrap_err(),
+            "line 1: invalid key 'MY KEY'"
+        );
+    }
+}
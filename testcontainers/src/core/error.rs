@@ -0,0 +1,283 @@
+use std::{fmt, time::Duration};
+
+use crate::core::{ContainerPlan, WaitFor};
+
+/// Returned when a container does not meet its ready conditions.
+///
+/// Describes the condition that was not met and contains the last lines the container has
+/// written to its logs, as they usually explain why.
+#[derive(Debug, Clone)]
+pub struct StartupError {
+    pub container_id: String,
+    /// The ready condition that was not met.
+    pub condition: WaitFor,
+    pub reason: StartupFailure,
+    /// The time that elapsed since the container started to wait for its ready conditions.
+    pub elapsed: Duration,
+    /// The exit code of the container, if it is not running anymore.
+    pub exit_code: Option<i64>,
+    pub stdout_tail: String,
+    pub stderr_tail: String,
+}
+
+/// Returned when a container does not become ready within its startup timeout.
+///
+/// Timeouts are reported as a [`StartupError`] with [`StartupFailure::TimedOut`] as its reason.
+#[deprecated(note = "use `StartupError`, which also reports conditions that failed")]
+pub type StartupTimeoutError = StartupError;
+
+/// The reason why a container did not meet a ready condition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StartupFailure {
+    /// The startup timeout elapsed while waiting for the condition.
+    TimedOut { timeout: Duration },
+    /// Waiting for the condition failed, e.g. because the container exited.
+    ConditionFailed { message: String },
+}
+
+impl StartupError {
+    /// The number of log lines that are kept of each stream.
+    pub(crate) const TAIL_LINES: usize = 50;
+
+    /// Returns the last [`StartupError::TAIL_LINES`] lines of the given logs.
+    pub(crate) fn tail(logs: &str) -> String {
+        let lines = logs.lines().collect::<Vec<_>>();
+        let start = lines.len().saturating_sub(Self::TAIL_LINES);
+
+        lines[start..].join("\n")
+    }
+}
+
+impl fmt::Display for StartupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.reason {
+            StartupFailure::TimedOut { timeout } => write!(
+                f,
+                "container {} did not become ready within {timeout:?}",
+                self.container_id
+            )?,
+            StartupFailure::ConditionFailed { message } => write!(
+                f,
+                "container {} failed to become ready after {:?}: {message}",
+                self.container_id, self.elapsed
+            )?,
+        }
+        writeln!(f)?;
+        writeln!(f, "waiting for: {:?}", self.condition)?;
+        if let Some(exit_code) = self.exit_code {
+            writeln!(f, "container exited with code {exit_code}")?;
+        }
+        writeln!(f, "--- stdout (tail) ---\n{}", self.stdout_tail)?;
+        write!(f, "--- stderr (tail) ---\n{}", self.stderr_tail)
+    }
+}
+
+impl std::error::Error for StartupError {}
+
+/// Returned when a container can't be started, see [`AsyncRunner::start`].
+///
+/// [`AsyncRunner::start`]: crate::runners::AsyncRunner::start
+#[derive(Debug)]
+pub enum TestcontainersError {
+    /// The Docker daemon could not be reached, e.g. because it is not running.
+    DaemonUnreachable { message: String },
+    /// The image could not be pulled from its registry.
+    PullFailed { image: String, message: String },
+    /// The configuration of the container is invalid, e.g. because of options that can't be
+    /// combined, so it was not created.
+    InvalidConfiguration { image: String, message: String },
+    /// The daemon refused to create the container, or its network or volumes, e.g. because of
+    /// an invalid option.
+    CreateFailed { image: String, message: String },
+    /// The container was created, but could not be started, or a command of
+    /// [`Image::exec_after_start`] failed.
+    ///
+    /// [`Image::exec_after_start`]: crate::Image::exec_after_start
+    StartFailed {
+        container_id: String,
+        message: String,
+    },
+    /// The container was started, but did not meet its ready conditions.
+    Startup(StartupError),
+    /// A `docker compose` command failed, e.g. because the compose file is invalid.
+    ComposeFailed { project: String, message: String },
+    /// The resources of sessions could not be listed or read, see [`cleanup`].
+    ///
+    /// [`cleanup`]: crate::cleanup
+    CleanupFailed { message: String },
+    /// The container was not started, as the `TESTCONTAINERS_DRY_RUN` environment variable is
+    /// set. Contains the plan the container would have been started with.
+    DryRun(Box<ContainerPlan>),
+}
+
+impl TestcontainersError {
+    /// Classifies an error of the daemon, which is reported as [`DaemonUnreachable`] if the
+    /// daemon could not be reached at all and as the given error otherwise.
+    ///
+    /// [`DaemonUnreachable`]: TestcontainersError::DaemonUnreachable
+    pub(crate) fn from_client(
+        err: bollard::errors::Error,
+        otherwise: impl FnOnce(String) -> Self,
+    ) -> Self {
+        match err {
+            bollard::errors::Error::IOError { .. }
+            | bollard::errors::Error::HyperResponseError { .. }
+            | bollard::errors::Error::HyperLegacyError { .. }
+            | bollard::errors::Error::RequestTimeoutError => Self::DaemonUnreachable {
+                message: err.to_string(),
+            },
+            err => otherwise(err.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for TestcontainersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TestcontainersError::DaemonUnreachable { message } => {
+                write!(f, "failed to reach the docker daemon: {message}")
+            }
+            TestcontainersError::PullFailed { image, message } => {
+                write!(f, "failed to pull the image {image}: {message}")
+            }
+            TestcontainersError::InvalidConfiguration { image, message } => {
+                write!(
+                    f,
+                    "invalid configuration of a container of {image}: {message}"
+                )
+            }
+            TestcontainersError::CreateFailed { image, message } => {
+                write!(f, "failed to create a container of {image}: {message}")
+            }
+            TestcontainersError::StartFailed {
+                container_id,
+                message,
+            } => write!(f, "failed to start container {container_id}: {message}"),
+            TestcontainersError::Startup(error) => fmt::Display::fmt(error, f),
+            TestcontainersError::ComposeFailed { project, message } => {
+                write!(f, "docker compose failed for project {project}: {message}")
+            }
+            TestcontainersError::CleanupFailed { message } => {
+                write!(f, "failed to clean up sessions: {message}")
+            }
+            TestcontainersError::DryRun(plan) => {
+                write!(f, "dry run, the container was not started:\n{plan}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TestcontainersError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TestcontainersError::Startup(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<StartupError> for TestcontainersError {
+    fn from(error: StartupError) -> Self {
+        TestcontainersError::Startup(error)
+    }
+}
+
+/// Returned when a container exits while a test depends on it, see [`ContainerAsync::exited`].
+///
+/// Contains the last lines the container has written to its logs, as they usually explain why
+/// it exited.
+///
+/// [`ContainerAsync::exited`]: crate::ContainerAsync::exited
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerDied {
+    pub container_id: String,
+    pub exit_code: i64,
+    pub stdout_tail: String,
+    pub stderr_tail: String,
+}
+
+impl fmt::Display for ContainerDied {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "container {} exited unexpectedly with code {}",
+            self.container_id, self.exit_code
+        )?;
+        writeln!(f, "--- stdout (tail) ---\n{}", self.stdout_tail)?;
+        write!(f, "--- stderr (tail) ---\n{}", self.stderr_tail)
+    }
+}
+
+impl std::error::Error for ContainerDied {}
+
+/// Returned when an internal port of a container is not mapped to a port on the host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PortError {
+    /// The port is not mapped on the host's IPv4 interfaces.
+    NotMappedIpv4 { port: u16 },
+    /// The port is not mapped on the host's IPv6 interfaces.
+    NotMappedIpv6 { port: u16 },
+}
+
+impl fmt::Display for PortError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (port, interfaces) = match self {
+            PortError::NotMappedIpv4 { port } => (port, "IPv4"),
+            PortError::NotMappedIpv6 { port } => (port, "IPv6"),
+        };
+        write!(
+            f,
+            "port {port} is not mapped on the host's {interfaces} interfaces, \
+             expose it in `Image::expose_ports` or map it with `RunnableImage::with_mapped_port`"
+        )
+    }
+}
+
+impl std::error::Error for PortError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tail_keeps_last_lines() {
+        let logs = (0..100).map(|i| format!("line {i}\n")).collect::<String>();
+
+        let tail = StartupError::tail(&logs);
+
+        assert_eq!(tail.lines().count(), StartupError::TAIL_LINES);
+        assert!(tail.starts_with("line 50\n"));
+        assert!(tail.ends_with("line 99"));
+        assert_eq!(StartupError::tail("a\nb\n"), "a\nb");
+    }
+
+    #[test]
+    fn classifies_client_errors() {
+        let unreachable = TestcontainersError::from_client(
+            std::io::Error::from(std::io::ErrorKind::ConnectionRefused).into(),
+            |message| TestcontainersError::CreateFailed {
+                image: "redis:7".to_string(),
+                message,
+            },
+        );
+        assert!(matches!(
+            unreachable,
+            TestcontainersError::DaemonUnreachable { .. }
+        ));
+
+        let conflict = TestcontainersError::from_client(
+            bollard::errors::Error::DockerResponseServerError {
+                status_code: 409,
+                message: "name is already in use".to_string(),
+            },
+            |message| TestcontainersError::CreateFailed {
+                image: "redis:7".to_string(),
+                message,
+            },
+        );
+        assert_eq!(
+            conflict.to_string(),
+            "failed to create a container of redis:7: Docker responded with status code 409: name is already in use"
+        );
+    }
+}
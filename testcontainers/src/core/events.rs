@@ -0,0 +1,85 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, SystemTime},
+};
+
+use bollard_stubs::models::EventMessage;
+
+use crate::core::HealthState;
+
+/// A lifecycle event of a container, as reported by `docker events`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerEvent {
+    pub kind: ContainerEventKind,
+    pub time: SystemTime,
+    /// The attributes of the event, which include the labels of the container
+    pub attributes: BTreeMap<String, String>,
+}
+
+/// The kind of a [`ContainerEvent`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContainerEventKind {
+    Start,
+    /// The main process of the container has exited.
+    Die {
+        exit_code: Option<i64>,
+    },
+    /// A process of the container was killed because it ran out of memory.
+    Oom,
+    /// A signal was sent to the container, by number.
+    Kill {
+        signal: Option<String>,
+    },
+    Stop,
+    Restart,
+    Pause,
+    Unpause,
+    /// The healthcheck of the container changed its state.
+    HealthStatus(HealthState),
+    /// Any other action, e.g. `exec_start: ls` or `destroy`.
+    Other(String),
+}
+
+impl From<EventMessage> for ContainerEvent {
+    fn from(message: EventMessage) -> Self {
+        let attributes: BTreeMap<_, _> = message
+            .actor
+            .and_then(|actor| actor.attributes)
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+
+        let action = message.action.unwrap_or_default();
+        let kind = match action.as_str() {
+            "start" => ContainerEventKind::Start,
+            "die" => ContainerEventKind::Die {
+                exit_code: attributes
+                    .get("exitCode")
+                    .and_then(|code| code.parse().ok()),
+            },
+            "oom" => ContainerEventKind::Oom,
+            "kill" => ContainerEventKind::Kill {
+                signal: attributes.get("signal").cloned(),
+            },
+            "stop" => ContainerEventKind::Stop,
+            "restart" => ContainerEventKind::Restart,
+            "pause" => ContainerEventKind::Pause,
+            "unpause" => ContainerEventKind::Unpause,
+            "health_status: starting" => ContainerEventKind::HealthStatus(HealthState::Starting),
+            "health_status: healthy" => ContainerEventKind::HealthStatus(HealthState::Healthy),
+            "health_status: unhealthy" => ContainerEventKind::HealthStatus(HealthState::Unhealthy),
+            _ => ContainerEventKind::Other(action),
+        };
+
+        let time_nano = message
+            .time_nano
+            .or_else(|| message.time.map(|time| time * 1_000_000_000))
+            .unwrap_or_default();
+
+        Self {
+            kind,
+            time: SystemTime::UNIX_EPOCH + Duration::from_nanos(time_nano.max(0) as u64),
+            attributes,
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use std::time::Duration;
+
+use bollard_stubs::models::{Health, HealthConfig, HealthStatusEnum, HealthcheckResult};
+
+/// A healthcheck of a container, overriding the `HEALTHCHECK` of its image.
+///
+/// Allows to use [`WaitFor::healthcheck`] with images that don't define a healthcheck.
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use testcontainers::{
+///     core::{Healthcheck, RunnableImage, WaitFor},
+///     GenericImage,
+/// };
+///
+/// let image = GenericImage::new("postgres", "16-alpine").with_wait_for(WaitFor::healthcheck());
+/// let runnable_image = RunnableImage::from(image).with_healthcheck(
+///     Healthcheck::cmd(["pg_isready", "-U", "postgres"])
+///         .with_interval(Duration::from_secs(1))
+///         .with_retries(10),
+/// );
+/// ```
+///
+/// [`WaitFor::healthcheck`]: crate::core::WaitFor::healthcheck
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Healthcheck {
+    test: Vec<String>,
+    interval: Option<Duration>,
+    timeout: Option<Duration>,
+    retries: Option<u32>,
+    start_period: Option<Duration>,
+    start_interval: Option<Duration>,
+}
+
+impl Healthcheck {
+    /// Creates a healthcheck that executes the given command directly, e.g. `["pg_isready"]`.
+    ///
+    /// The container is healthy if the command exits with code `0`.
+    pub fn cmd<I, S>(cmd: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let test = std::iter::once("CMD".to_string())
+            .chain(cmd.into_iter().map(Into::into))
+            .collect();
+
+        Self::new(test)
+    }
+
+    /// Creates a healthcheck that executes the given command with the default shell of the
+    /// container, e.g. `"curl -f http://localhost/ || exit 1"`.
+    ///
+    /// The container is healthy if the command exits with code `0`.
+    pub fn cmd_shell(cmd: impl Into<String>) -> Self {
+        Self::new(vec!["CMD-SHELL".to_string(), cmd.into()])
+    }
+
+    /// Disables the healthcheck of the image.
+    pub fn none() -> Self {
+        Self::new(vec!["NONE".to_string()])
+    }
+
+    fn new(test: Vec<String>) -> Self {
+        Self {
+            test,
+            interval: None,
+            timeout: None,
+            retries: None,
+            start_period: None,
+            start_interval: None,
+        }
+    }
+
+    /// Sets the time to wait between two checks. Defaults to 30 seconds.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Sets the time after which a check is considered to have failed. Defaults to 30 seconds.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the number of consecutive failed checks after which the container is considered
+    /// unhealthy. Defaults to `3`.
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = Some(retries);
+        self
+    }
+
+    /// Sets the time the container has to initialize, during which failed checks are not
+    /// counted towards the retries.
+    pub fn with_start_period(mut self, start_period: Duration) -> Self {
+        self.start_period = Some(start_period);
+        self
+    }
+
+    /// Sets the time to wait between two checks during the start period.
+    pub fn with_start_interval(mut self, start_interval: Duration) -> Self {
+        self.start_interval = Some(start_interval);
+        self
+    }
+}
+
+impl From<Healthcheck> for HealthConfig {
+    fn from(healthcheck: Healthcheck) -> Self {
+        // docker expects durations in nanoseconds, where `0` means inherited from the image
+        let nanos = |duration: Option<Duration>| duration.map(|d| d.as_nanos() as i64);
+
+        HealthConfig {
+            test: Some(healthcheck.test),
+            interval: nanos(healthcheck.interval),
+            timeout: nanos(healthcheck.timeout),
+            retries: healthcheck.retries.map(i64::from),
+            start_period: nanos(healthcheck.start_period),
+            start_interval: nanos(healthcheck.start_interval),
+        }
+    }
+}
+
+/// The result of the healthcheck of a container, see [`ContainerAsync::health`].
+///
+/// [`ContainerAsync::health`]: crate::ContainerAsync::health
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthStatus {
+    pub state: HealthState,
+    /// The number of consecutive failed checks
+    pub failing_streak: i64,
+    /// The most recent check, if any check has finished yet
+    pub last_probe: Option<HealthProbe>,
+}
+
+/// The state of the healthcheck of a container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthState {
+    /// The container has no healthcheck.
+    None,
+    /// The container has not passed a check yet.
+    Starting,
+    Healthy,
+    /// The checks have failed more often in a row than the retries allow.
+    Unhealthy,
+}
+
+/// A single check of the healthcheck of a container.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthProbe {
+    /// `0` if the check passed, `1` if it failed
+    pub exit_code: i64,
+    /// The output of the check command, which is truncated by docker
+    pub output: String,
+    /// The time the check started, in RFC 3339 format
+    pub start: Option<String>,
+    /// The time the check ended, in RFC 3339 format
+    pub end: Option<String>,
+}
+
+impl From<Health> for HealthStatus {
+    fn from(health: Health) -> Self {
+        let state = match health.status {
+            Some(HealthStatusEnum::STARTING) => HealthState::Starting,
+            Some(HealthStatusEnum::HEALTHY) => HealthState::Healthy,
+            Some(HealthStatusEnum::UNHEALTHY) => HealthState::Unhealthy,
+            Some(HealthStatusEnum::NONE | HealthStatusEnum::EMPTY) | None => HealthState::None,
+        };
+
+        Self {
+            state,
+            failing_streak: health.failing_streak.unwrap_or_default(),
+            // the log contains the last few checks, oldest first
+            last_probe: health
+                .log
+                .and_then(|mut log| log.pop())
+                .map(HealthProbe::from),
+        }
+    }
+}
+
+impl From<HealthcheckResult> for HealthProbe {
+    fn from(result: HealthcheckResult) -> Self {
+        Self {
+            exit_code: result.exit_code.unwrap_or_default(),
+            output: result.output.unwrap_or_default(),
+            start: result.start,
+            end: result.end,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_into_health_config() {
+        let config = HealthConfig::from(
+            Healthcheck::cmd(["pg_isready", "-U", "postgres"])
+                .with_interval(Duration::from_secs(1))
+                .with_retries(5),
+        );
+
+        assert_eq!(
+            config.test,
+            Some(vec![
+                "CMD".to_string(),
+                "pg_isready".to_string(),
+                "-U".to_string(),
+                "postgres".to_string()
+            ])
+        );
+        assert_eq!(config.interval, Some(1_000_000_000));
+        assert_eq!(config.retries, Some(5));
+        assert_eq!(config.timeout, None);
+    }
+}
@@ -1,12 +1,19 @@
 use std::{
     collections::BTreeMap,
-    env::var,
     fmt::{Debug, Display},
-    net::IpAddr,
+    fs,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    path::Path,
+    sync::Arc,
     time::Duration,
 };
 
-use super::ports::Ports;
+use super::{
+    env_file,
+    logs::{LogConsumer, LogDump},
+    ports::Ports,
+    ContainerPlan, Healthcheck, Mount, PortError, RemovePolicy, TestcontainersError, WaitFor,
+};
 
 /// Represents a docker image.
 ///
@@ -99,13 +106,26 @@ where
     fn exec_after_start(&self, cs: ContainerState) -> Vec<ExecCommand> {
         Default::default()
     }
+
+    /// Returns the commands that are executed before a container is removed, e.g. to flush
+    /// data to a volume that is reused by later containers.
+    ///
+    /// Once the commands ran, the container is stopped gracefully before it is removed, see
+    /// [`RunnableImage::with_shutdown_command`].
+    fn exec_before_shutdown(&self) -> Vec<ExecCommand> {
+        Default::default()
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ExecCommand {
     pub(super) cmd: Vec<String>,
     pub(super) cmd_ready_condition: WaitFor,
     pub(super) container_ready_conditions: Vec<WaitFor>,
+    pub(super) user: Option<String>,
+    pub(super) env_vars: BTreeMap<String, String>,
+    pub(super) working_dir: Option<String>,
+    pub(super) detach: bool,
 }
 
 impl ExecCommand {
@@ -115,9 +135,44 @@ impl ExecCommand {
             cmd,
             cmd_ready_condition: WaitFor::Nothing,
             container_ready_conditions: vec![],
+            user: None,
+            env_vars: BTreeMap::default(),
+            working_dir: None,
+            detach: false,
         }
     }
 
+    /// Runs the command as the given user (name or UID, optionally with a group, e.g.
+    /// `postgres` or `1000:1000`) instead of the user of the container.
+    pub fn with_user(mut self, user: impl Into<String>) -> Self {
+        self.user = Some(user.into());
+        self
+    }
+
+    /// Sets an environment variable for the command, in addition to the ones of the container.
+    pub fn with_env_var(mut self, (key, value): (impl Into<String>, impl Into<String>)) -> Self {
+        self.env_vars.insert(key.into(), value.into());
+        self
+    }
+
+    /// Runs the command in the given directory instead of the working directory of the
+    /// container.
+    pub fn with_working_dir(mut self, working_dir: impl Into<String>) -> Self {
+        self.working_dir = Some(working_dir.into());
+        self
+    }
+
+    /// Starts the command in the background without waiting for it to finish, e.g. a process
+    /// that keeps running for the rest of the test.
+    ///
+    /// The output and exit code of a detached command are not available: its ready condition
+    /// can't be based on its output, and the [`ExecResult::exit_code`] is `None`. Container
+    /// ready conditions are still awaited, e.g. for the process to log that it has started.
+    pub fn with_detach(mut self, detach: bool) -> Self {
+        self.detach = detach;
+        self
+    }
+
     /// Conditions to be checked on related container
     pub fn with_container_ready_conditions(mut self, ready_conditions: Vec<WaitFor>) -> Self {
         self.container_ready_conditions = ready_conditions;
@@ -137,6 +192,50 @@ impl Default for ExecCommand {
     }
 }
 
+/// The output and exit code of a command executed in a container, see
+/// [`ContainerAsync::exec_to_completion`].
+///
+/// [`ContainerAsync::exec_to_completion`]: crate::ContainerAsync::exec_to_completion
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ExecResult {
+    /// The exit code of the command, or `None` if it was started detached (see
+    /// [`ExecCommand::with_detach`]) or was still running when the result was taken, e.g.
+    /// because it closed its output before exiting.
+    pub exit_code: Option<i64>,
+    /// Everything the command has written to stdout
+    pub stdout: Vec<u8>,
+    /// Everything the command has written to stderr
+    pub stderr: Vec<u8>,
+}
+
+/// The exit status of a container whose main process has terminated.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ExitStatus {
+    code: i64,
+}
+
+impl ExitStatus {
+    pub(crate) fn new(code: i64) -> Self {
+        Self { code }
+    }
+
+    /// Returns the exit code of the main process of the container.
+    pub fn code(&self) -> i64 {
+        self.code
+    }
+
+    /// Returns whether the main process of the container exited successfully.
+    pub fn success(&self) -> bool {
+        self.code == 0
+    }
+}
+
+impl Display for ExitStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "exit code {}", self.code)
+    }
+}
+
 #[derive(Debug)]
 pub struct ContainerState {
     ports: Ports,
@@ -148,15 +247,33 @@ impl ContainerState {
     }
 
     pub fn host_port_ipv4(&self, internal_port: u16) -> u16 {
+        self.try_host_port_ipv4(internal_port)
+            .unwrap_or_else(|_| panic!("Container does not have a mapped port for {internal_port}"))
+    }
+
+    pub fn host_port_ipv6(&self, internal_port: u16) -> u16 {
+        self.try_host_port_ipv6(internal_port)
+            .unwrap_or_else(|_| panic!("Container does not have a mapped port for {internal_port}"))
+    }
+
+    /// Returns the host port for the given internal port on the host's IPv4 interfaces, or an
+    /// error if it is not mapped.
+    pub fn try_host_port_ipv4(&self, internal_port: u16) -> Result<u16, PortError> {
         self.ports
             .map_to_host_port_ipv4(internal_port)
-            .unwrap_or_else(|| panic!("Container does not have a mapped port for {internal_port}",))
+            .ok_or(PortError::NotMappedIpv4 {
+                port: internal_port,
+            })
     }
 
-    pub fn host_port_ipv6(&self, internal_port: u16) -> u16 {
+    /// Returns the host port for the given internal port on the host's IPv6 interfaces, or an
+    /// error if it is not mapped.
+    pub fn try_host_port_ipv6(&self, internal_port: u16) -> Result<u16, PortError> {
         self.ports
             .map_to_host_port_ipv6(internal_port)
-            .unwrap_or_else(|| panic!("Container does not have a mapped port for {internal_port}",))
+            .ok_or(PortError::NotMappedIpv6 {
+                port: internal_port,
+            })
     }
 }
 
@@ -185,6 +302,15 @@ impl Display for Host {
     }
 }
 
+/// The cgroup namespace of a container.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CgroupnsMode {
+    /// The container runs in its own, private cgroup namespace.
+    Private,
+    /// The container runs in the cgroup namespace of the host.
+    Host,
+}
+
 #[must_use]
 #[derive(Debug, Clone)]
 pub struct RunnableImage<I: Image> {
@@ -195,11 +321,58 @@ pub struct RunnableImage<I: Image> {
     container_name: Option<String>,
     network: Option<String>,
     env_vars: BTreeMap<String, String>,
+    /// Why environment files could not be added, which is reported once the container starts.
+    env_file_errors: Vec<String>,
     hosts: BTreeMap<String, Host>,
     volumes: BTreeMap<String, String>,
     ports: Option<Vec<Port>>,
     privileged: bool,
     shm_size: Option<u64>,
+    cmd: Option<Vec<String>>,
+    user: Option<String>,
+    working_dir: Option<String>,
+    labels: BTreeMap<String, String>,
+    memory_limit: Option<u64>,
+    memory_swap: Option<i64>,
+    cpus: Option<f64>,
+    cpu_shares: Option<u64>,
+    ulimits: BTreeMap<String, (i64, i64)>,
+    cap_add: Vec<String>,
+    cap_drop: Vec<String>,
+    security_opts: Vec<String>,
+    readonly_rootfs: bool,
+    devices: Vec<(String, String, String)>,
+    network_aliases: Vec<String>,
+    network_subnets: Vec<String>,
+    ipv4_address: Option<Ipv4Addr>,
+    ipv6_address: Option<Ipv6Addr>,
+    platform: Option<String>,
+    stop_signal: Option<String>,
+    stop_timeout: Option<Duration>,
+    healthcheck: Option<Healthcheck>,
+    log_driver: Option<String>,
+    log_options: BTreeMap<String, String>,
+    gpus: Option<String>,
+    sysctls: BTreeMap<String, String>,
+    init: bool,
+    pid_mode: Option<String>,
+    ipc_mode: Option<String>,
+    userns_mode: Option<String>,
+    mounts: Vec<Mount>,
+    entrypoint: Option<Vec<String>>,
+    group_add: Vec<String>,
+    cgroup_parent: Option<String>,
+    cgroupns_mode: Option<CgroupnsMode>,
+    auto_remove: bool,
+    oom_kill_disable: bool,
+    oom_score_adj: Option<i32>,
+    network_mode: Option<String>,
+    log_consumers: Vec<Arc<dyn LogConsumer>>,
+    log_dump: Option<LogDump>,
+    remove_on_drop: Option<RemovePolicy>,
+    startup_timeout: Option<Duration>,
+    startup_attempts: u32,
+    shutdown_commands: Vec<ExecCommand>,
 }
 
 impl<I: Image> RunnableImage<I> {
@@ -223,6 +396,11 @@ impl<I: Image> RunnableImage<I> {
         Box::new(self.image.env_vars().chain(self.env_vars.iter()))
     }
 
+    /// Returns why the environment files of [`RunnableImage::with_env_file`] could not be added.
+    pub(crate) fn env_file_errors(&self) -> &[String] {
+        &self.env_file_errors
+    }
+
     pub fn hosts(&self) -> Box<dyn Iterator<Item = (&String, &Host)> + '_> {
         Box::new(self.hosts.iter())
     }
@@ -244,8 +422,234 @@ impl<I: Image> RunnableImage<I> {
         self.shm_size
     }
 
+    /// The command of the container, if overridden.
+    pub fn cmd(&self) -> Option<&[String]> {
+        self.cmd.as_deref()
+    }
+
+    /// The user the container runs as, if overridden.
+    pub fn user(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+
+    /// The working directory of the container, if overridden.
+    pub fn working_dir(&self) -> Option<&str> {
+        self.working_dir.as_deref()
+    }
+
+    pub fn labels(&self) -> Box<dyn Iterator<Item = (&String, &String)> + '_> {
+        Box::new(self.labels.iter())
+    }
+
+    /// Memory limit in bytes
+    pub fn memory_limit(&self) -> Option<u64> {
+        self.memory_limit
+    }
+
+    /// Total memory limit (memory and swap) in bytes, `-1` for unlimited swap
+    pub fn memory_swap(&self) -> Option<i64> {
+        self.memory_swap
+    }
+
+    /// Number of CPUs the container can use
+    pub fn cpus(&self) -> Option<f64> {
+        self.cpus
+    }
+
+    /// Relative CPU weight of the container
+    pub fn cpu_shares(&self) -> Option<u64> {
+        self.cpu_shares
+    }
+
+    /// The ulimits of the container, as `(name, (soft, hard))`
+    pub fn ulimits(&self) -> Box<dyn Iterator<Item = (&String, &(i64, i64))> + '_> {
+        Box::new(self.ulimits.iter())
+    }
+
+    /// Kernel capabilities added to the container
+    pub fn cap_add(&self) -> &[String] {
+        &self.cap_add
+    }
+
+    /// Kernel capabilities dropped from the container
+    pub fn cap_drop(&self) -> &[String] {
+        &self.cap_drop
+    }
+
+    /// Security options of the container, e.g. `seccomp=unconfined`
+    pub fn security_opts(&self) -> &[String] {
+        &self.security_opts
+    }
+
+    pub fn readonly_rootfs(&self) -> bool {
+        self.readonly_rootfs
+    }
+
+    /// Devices of the host passed through to the container, as
+    /// `(host_path, container_path, permissions)`
+    pub fn devices(&self) -> &[(String, String, String)] {
+        &self.devices
+    }
+
+    /// Aliases of the container on its network
+    pub fn network_aliases(&self) -> &[String] {
+        &self.network_aliases
+    }
+
+    /// Subnets of the network, if it is created for the container
+    pub fn network_subnets(&self) -> &[String] {
+        &self.network_subnets
+    }
+
+    /// Static IPv4 address of the container on its network
+    pub fn ipv4_address(&self) -> Option<Ipv4Addr> {
+        self.ipv4_address
+    }
+
+    /// Static IPv6 address of the container on its network
+    pub fn ipv6_address(&self) -> Option<Ipv6Addr> {
+        self.ipv6_address
+    }
+
+    /// The platform of the image, if overridden.
+    pub fn platform(&self) -> Option<&str> {
+        self.platform.as_deref()
+    }
+
+    /// The signal sent to the container to stop it, if overridden.
+    pub fn stop_signal(&self) -> Option<&str> {
+        self.stop_signal.as_deref()
+    }
+
+    /// Time to wait for the container to stop before it is killed, if overridden.
+    pub fn stop_timeout(&self) -> Option<Duration> {
+        self.stop_timeout
+    }
+
+    /// The healthcheck of the container, if overridden.
+    pub fn healthcheck(&self) -> Option<&Healthcheck> {
+        self.healthcheck.as_ref()
+    }
+
+    /// The logging driver of the container, if overridden.
+    pub fn log_driver(&self) -> Option<&str> {
+        self.log_driver.as_deref()
+    }
+
+    pub fn log_options(&self) -> Box<dyn Iterator<Item = (&String, &String)> + '_> {
+        Box::new(self.log_options.iter())
+    }
+
+    /// The GPUs the container can use, if any.
+    pub fn gpus(&self) -> Option<&str> {
+        self.gpus.as_deref()
+    }
+
+    pub fn sysctls(&self) -> Box<dyn Iterator<Item = (&String, &String)> + '_> {
+        Box::new(self.sysctls.iter())
+    }
+
+    pub fn init(&self) -> bool {
+        self.init
+    }
+
+    /// The PID namespace mode of the container, if overridden.
+    pub fn pid_mode(&self) -> Option<&str> {
+        self.pid_mode.as_deref()
+    }
+
+    /// The IPC namespace mode of the container, if overridden.
+    pub fn ipc_mode(&self) -> Option<&str> {
+        self.ipc_mode.as_deref()
+    }
+
+    /// The user namespace mode of the container, if overridden.
+    pub fn userns_mode(&self) -> Option<&str> {
+        self.userns_mode.as_deref()
+    }
+
+    pub fn mounts(&self) -> &[Mount] {
+        &self.mounts
+    }
+
+    /// Supplementary groups of the user of the container
+    pub fn group_add(&self) -> &[String] {
+        &self.group_add
+    }
+
+    /// The parent cgroup of the container, if overridden.
+    pub fn cgroup_parent(&self) -> Option<&str> {
+        self.cgroup_parent.as_deref()
+    }
+
+    /// The cgroup namespace mode of the container, if overridden.
+    pub fn cgroupns_mode(&self) -> Option<CgroupnsMode> {
+        self.cgroupns_mode
+    }
+
+    /// Whether the docker daemon removes the container once it exits.
+    pub fn auto_remove(&self) -> bool {
+        self.auto_remove
+    }
+
+    /// Whether the kernel is prevented from killing the container when it runs out of memory.
+    pub fn oom_kill_disable(&self) -> bool {
+        self.oom_kill_disable
+    }
+
+    /// How the OOM killer of the kernel prioritizes the container, if overridden.
+    pub fn oom_score_adj(&self) -> Option<i32> {
+        self.oom_score_adj
+    }
+
+    /// The network mode of the container, if overridden, e.g. `host`.
+    pub fn network_mode(&self) -> Option<&str> {
+        self.network_mode.as_deref()
+    }
+
+    /// Consumers the logs of the container are forwarded to
+    pub fn log_consumers(&self) -> &[Arc<dyn LogConsumer>] {
+        &self.log_consumers
+    }
+
+    /// When the logs of the container are written to a file, if overridden.
+    pub fn log_dump(&self) -> Option<LogDump> {
+        self.log_dump
+    }
+
+    /// When the container is removed once it is dropped, if overridden.
+    pub fn remove_on_drop(&self) -> Option<RemovePolicy> {
+        self.remove_on_drop
+    }
+
+    /// Maximum time to wait for the container to become ready, if overridden.
+    pub fn startup_timeout(&self) -> Option<Duration> {
+        self.startup_timeout
+    }
+
+    /// How many times the container is created before failing to start it.
+    pub fn startup_attempts(&self) -> u32 {
+        self.startup_attempts
+    }
+
+    /// The executable of the entrypoint of the container, either overridden or the one of the
+    /// [`Image`]. An empty string means the entrypoint of the image is cleared.
+    ///
+    /// See [`RunnableImage::entrypoint_args`] for the whole entrypoint, including the arguments
+    /// of an overridden one.
     pub fn entrypoint(&self) -> Option<String> {
-        self.image.entrypoint()
+        match &self.entrypoint {
+            Some(entrypoint) => entrypoint.first().cloned(),
+            None => self.image.entrypoint(),
+        }
+    }
+
+    /// The entrypoint of the container with its arguments, e.g. `["sh", "-c"]`, either
+    /// overridden or the one of the [`Image`].
+    pub fn entrypoint_args(&self) -> Option<Vec<String>> {
+        self.entrypoint
+            .clone()
+            .or_else(|| self.image.entrypoint().map(|entrypoint| vec![entrypoint]))
     }
 
     pub fn descriptor(&self) -> String {
@@ -269,6 +673,25 @@ impl<I: Image> RunnableImage<I> {
     pub fn exec_after_start(&self, cs: ContainerState) -> Vec<ExecCommand> {
         self.image.exec_after_start(cs)
     }
+
+    /// The commands that are executed before the container is removed, the ones of the
+    /// [`Image`] followed by the ones added with [`RunnableImage::with_shutdown_command`].
+    pub fn shutdown_commands(&self) -> Vec<ExecCommand> {
+        let mut commands = self.image.exec_before_shutdown();
+        commands.extend(self.shutdown_commands.iter().cloned());
+        commands
+    }
+
+    /// Returns the request a container of the image would be created with, and the equivalent
+    /// `docker run` command, without touching Docker.
+    ///
+    /// Fails with [`TestcontainersError::InvalidConfiguration`] if the configuration is
+    /// invalid, like starting the container would.
+    ///
+    /// [`TestcontainersError::InvalidConfiguration`]: crate::core::TestcontainersError::InvalidConfiguration
+    pub fn plan(&self) -> Result<ContainerPlan, TestcontainersError> {
+        ContainerPlan::new(self)
+    }
 }
 
 impl<I: Image> RunnableImage<I> {
@@ -313,6 +736,17 @@ impl<I: Image> RunnableImage<I> {
         }
     }
 
+    /// Returns the image to start copies of a container from the image committed from it,
+    /// without the name of the container, which has to be unique.
+    pub(crate) fn into_snapshot(self, name: impl Into<String>, tag: impl Into<String>) -> Self {
+        Self {
+            image_name: Some(name.into()),
+            image_tag: Some(tag.into()),
+            container_name: None,
+            ..self
+        }
+    }
+
     pub fn with_container_name(self, name: impl Into<String>) -> Self {
         Self {
             container_name: Some(name.into()),
@@ -320,6 +754,12 @@ impl<I: Image> RunnableImage<I> {
         }
     }
 
+    /// Connects the container to a [`Network`] or the network with the name.
+    ///
+    /// The network is created if it does not exist yet, and a network that was created for
+    /// containers is removed once the last of them is dropped.
+    ///
+    /// [`Network`]: crate::core::Network
     pub fn with_network(self, network: impl Into<String>) -> Self {
         Self {
             network: Some(network.into()),
@@ -333,12 +773,47 @@ impl<I: Image> RunnableImage<I> {
         Self { env_vars, ..self }
     }
 
+    /// Adds the variables of an environment file in the dotenv format, e.g. the `.env` file
+    /// used by `docker compose`, to the environment of the container.
+    ///
+    /// If the file can't be read or is not a valid environment file, starting the container
+    /// fails with [`TestcontainersError::InvalidConfiguration`].
+    pub fn with_env_file(self, path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        let variables = fs::read_to_string(path)
+            .map_err(|err| format!("failed to read env file {}: {err}", path.display()))
+            .and_then(|content| {
+                env_file::parse(&content)
+                    .map_err(|err| format!("invalid env file {}: {err}", path.display()))
+            });
+
+        match variables {
+            Ok(variables) => {
+                let mut env_vars = self.env_vars;
+                env_vars.extend(variables);
+                Self { env_vars, ..self }
+            }
+            Err(message) => {
+                let mut env_file_errors = self.env_file_errors;
+                env_file_errors.push(message);
+                Self {
+                    env_file_errors,
+                    ..self
+                }
+            }
+        }
+    }
+
     pub fn with_host(self, key: impl Into<String>, value: impl Into<Host>) -> Self {
         let mut hosts = self.hosts;
         hosts.insert(key.into(), value.into());
         Self { hosts, ..self }
     }
 
+    /// Mounts a host path or a named volume into the container.
+    ///
+    /// Prefer [`RunnableImage::with_mount`], which distinguishes the kinds of mounts and
+    /// validates their paths.
     pub fn with_volume(self, (orig, dest): (impl Into<String>, impl Into<String>)) -> Self {
         let mut volumes = self.volumes;
         volumes.insert(orig.into(), dest.into());
@@ -365,6 +840,487 @@ impl<I: Image> RunnableImage<I> {
             ..self
         }
     }
+
+    /// Overrides the command of the container, instead of deriving it from the [`Image::Args`].
+    ///
+    /// ```
+    /// use testcontainers::{core::RunnableImage, GenericImage};
+    ///
+    /// let runnable_image =
+    ///     RunnableImage::from(GenericImage::new("alpine", "3.19")).with_cmd(["sleep", "infinity"]);
+    ///
+    /// assert_eq!(runnable_image.cmd().unwrap(), ["sleep", "infinity"]);
+    /// ```
+    pub fn with_cmd(self, cmd: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            cmd: Some(cmd.into_iter().map(Into::into).collect()),
+            ..self
+        }
+    }
+
+    /// Sets the user the container runs as, in any of the formats supported by
+    /// `docker run --user`, e.g. `"1000:1000"` or `"postgres"`.
+    ///
+    /// Useful to avoid files owned by `root` appearing in bind-mounted host directories.
+    pub fn with_user(self, user: impl Into<String>) -> Self {
+        Self {
+            user: Some(user.into()),
+            ..self
+        }
+    }
+
+    /// Sets the directory the command of the container is executed in, e.g. a mounted
+    /// project directory for toolchain images like `node` or `python`.
+    pub fn with_working_dir(self, working_dir: impl Into<String>) -> Self {
+        Self {
+            working_dir: Some(working_dir.into()),
+            ..self
+        }
+    }
+
+    /// Adds a label to the container, e.g. to identify containers of a test suite.
+    pub fn with_label(self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        let mut labels = self.labels;
+        labels.insert(key.into(), value.into());
+        Self { labels, ..self }
+    }
+
+    /// Limits the memory the container can use, in bytes.
+    pub fn with_memory_limit(self, bytes: u64) -> Self {
+        Self {
+            memory_limit: Some(bytes),
+            ..self
+        }
+    }
+
+    /// Limits the total of memory and swap the container can use, in bytes.
+    ///
+    /// Has to be at least the memory limit, or `-1` to allow unlimited swap.
+    pub fn with_memory_swap(self, bytes: i64) -> Self {
+        Self {
+            memory_swap: Some(bytes),
+            ..self
+        }
+    }
+
+    /// Limits the number of CPUs the container can use, e.g. `1.5`.
+    pub fn with_cpus(self, cpus: f64) -> Self {
+        Self {
+            cpus: Some(cpus),
+            ..self
+        }
+    }
+
+    /// Sets the CPU weight of the container relative to other containers (default `1024`),
+    /// which only takes effect when CPU cycles are constrained.
+    pub fn with_cpu_shares(self, shares: u64) -> Self {
+        Self {
+            cpu_shares: Some(shares),
+            ..self
+        }
+    }
+
+    /// Sets the soft and hard limit of the ulimit with the given name, e.g. `nofile` or
+    /// `memlock`. `-1` means unlimited.
+    pub fn with_ulimit(self, name: impl Into<String>, soft: i64, hard: i64) -> Self {
+        let mut ulimits = self.ulimits;
+        ulimits.insert(name.into(), (soft, hard));
+        Self { ulimits, ..self }
+    }
+
+    /// Adds a kernel capability to the container, e.g. `NET_ADMIN`, without having to run it
+    /// in privileged mode.
+    pub fn with_cap_add(self, capability: impl Into<String>) -> Self {
+        let mut cap_add = self.cap_add;
+        cap_add.push(capability.into());
+        Self { cap_add, ..self }
+    }
+
+    /// Drops a kernel capability from the container, e.g. `MKNOD`.
+    pub fn with_cap_drop(self, capability: impl Into<String>) -> Self {
+        let mut cap_drop = self.cap_drop;
+        cap_drop.push(capability.into());
+        Self { cap_drop, ..self }
+    }
+
+    /// Adds a security option to the container, in the format of `docker run --security-opt`,
+    /// e.g. `seccomp=unconfined` or `apparmor=my-profile`.
+    pub fn with_security_opt(self, security_opt: impl Into<String>) -> Self {
+        let mut security_opts = self.security_opts;
+        security_opts.push(security_opt.into());
+        Self {
+            security_opts,
+            ..self
+        }
+    }
+
+    /// Mounts the root filesystem of the container as read-only, e.g. to verify that an image
+    /// runs the same way it does on a Kubernetes cluster that enforces it.
+    pub fn with_readonly_rootfs(self, readonly_rootfs: bool) -> Self {
+        Self {
+            readonly_rootfs,
+            ..self
+        }
+    }
+
+    /// Passes a device of the host through to the container, e.g. `/dev/fuse`.
+    ///
+    /// The permissions are a combination of `r` (read), `w` (write) and `m` (mknod),
+    /// e.g. `"rwm"`.
+    pub fn with_device(
+        self,
+        host_path: impl Into<String>,
+        container_path: impl Into<String>,
+        permissions: impl Into<String>,
+    ) -> Self {
+        let mut devices = self.devices;
+        devices.push((host_path.into(), container_path.into(), permissions.into()));
+        Self { devices, ..self }
+    }
+
+    /// Adds a DNS alias the container can be reached by from other containers on its network.
+    ///
+    /// Aliases are only supported on user-defined networks, so the container has to join one
+    /// via [`RunnableImage::with_network`].
+    pub fn with_network_alias(self, alias: impl Into<String>) -> Self {
+        let mut network_aliases = self.network_aliases;
+        network_aliases.push(alias.into());
+        Self {
+            network_aliases,
+            ..self
+        }
+    }
+
+    /// Adds a subnet in CIDR notation (e.g. `172.28.0.0/16` or `fd00:dead:beef::/48`) to the
+    /// network of the container.
+    ///
+    /// Only applies if the network is created for the container, i.e. if it does not exist yet.
+    /// Static IP addresses require the network to have a subnet that contains them, and an
+    /// IPv6 subnet enables IPv6 on the network.
+    pub fn with_network_subnet(self, subnet: impl Into<String>) -> Self {
+        let mut network_subnets = self.network_subnets;
+        network_subnets.push(subnet.into());
+        Self {
+            network_subnets,
+            ..self
+        }
+    }
+
+    /// Assigns a static IPv4 address to the container on its network, see
+    /// [`RunnableImage::with_network_subnet`].
+    pub fn with_ip_address(self, ip_address: Ipv4Addr) -> Self {
+        Self {
+            ipv4_address: Some(ip_address),
+            ..self
+        }
+    }
+
+    /// Assigns a static IPv6 address to the container on its network, see
+    /// [`RunnableImage::with_network_subnet`].
+    pub fn with_ipv6_address(self, ip_address: Ipv6Addr) -> Self {
+        Self {
+            ipv6_address: Some(ip_address),
+            ..self
+        }
+    }
+
+    /// Sets the platform of the image in the format `os[/arch[/variant]]`, e.g. `linux/amd64`.
+    ///
+    /// The image is pulled and the container is created for the given platform, which allows
+    /// running images that are not available for the architecture of the host under emulation.
+    pub fn with_platform(self, platform: impl Into<String>) -> Self {
+        Self {
+            platform: Some(platform.into()),
+            ..self
+        }
+    }
+
+    /// Sets the signal that is sent to the container to stop it, e.g. `SIGINT`.
+    pub fn with_stop_signal(self, signal: impl Into<String>) -> Self {
+        Self {
+            stop_signal: Some(signal.into()),
+            ..self
+        }
+    }
+
+    /// Sets how long to wait for the container to stop gracefully before it is killed, with a
+    /// granularity of seconds. Defaults to 10 seconds.
+    pub fn with_stop_timeout(self, timeout: Duration) -> Self {
+        Self {
+            stop_timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// Sets the healthcheck of the container, overriding the one of the image, if any.
+    ///
+    /// See [`Healthcheck`] for an example.
+    pub fn with_healthcheck(self, healthcheck: Healthcheck) -> Self {
+        Self {
+            healthcheck: Some(healthcheck),
+            ..self
+        }
+    }
+
+    /// Sets the logging driver of the container and its options, e.g. `json-file` with
+    /// `max-size` to cap the disk space used by the logs of a chatty container.
+    ///
+    /// Ready conditions that wait for log messages require a driver that supports reading
+    /// logs, like `json-file` or `local`.
+    pub fn with_log_driver<K, V>(
+        self,
+        driver: impl Into<String>,
+        options: impl IntoIterator<Item = (K, V)>,
+    ) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        Self {
+            log_driver: Some(driver.into()),
+            log_options: options
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+            ..self
+        }
+    }
+
+    /// Gives the container access to GPUs of the host, in the format of `docker run --gpus`:
+    /// `"all"`, a number of GPUs like `"2"`, or specific devices like `"device=0,1"`.
+    ///
+    /// Requires the NVIDIA Container Toolkit to be installed on the docker host. Starting the
+    /// container fails with [`TestcontainersError::InvalidConfiguration`] if the GPUs are not
+    /// in one of these formats.
+    ///
+    /// [`TestcontainersError::InvalidConfiguration`]: crate::core::TestcontainersError::InvalidConfiguration
+    pub fn with_gpus(self, gpus: impl Into<String>) -> Self {
+        Self {
+            gpus: Some(gpus.into()),
+            ..self
+        }
+    }
+
+    /// Sets a namespaced kernel parameter of the container, e.g. `net.core.somaxconn`.
+    pub fn with_sysctl(self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        let mut sysctls = self.sysctls;
+        sysctls.insert(key.into(), value.into());
+        Self { sysctls, ..self }
+    }
+
+    /// Runs an init process (`tini`) as PID 1 of the container, which forwards signals and
+    /// reaps zombie processes.
+    pub fn with_init(self, init: bool) -> Self {
+        Self { init, ..self }
+    }
+
+    /// Sets the PID namespace of the container, e.g. `host` or `container:<name|id>` to see the
+    /// processes of the host or of another container.
+    pub fn with_pid_mode(self, pid_mode: impl Into<String>) -> Self {
+        Self {
+            pid_mode: Some(pid_mode.into()),
+            ..self
+        }
+    }
+
+    /// Sets the IPC namespace of the container, e.g. `host`, `shareable` or
+    /// `container:<name|id>`.
+    pub fn with_ipc_mode(self, ipc_mode: impl Into<String>) -> Self {
+        Self {
+            ipc_mode: Some(ipc_mode.into()),
+            ..self
+        }
+    }
+
+    /// Sets the user namespace of the container, e.g. `host` to disable user namespace
+    /// remapping for it.
+    pub fn with_userns_mode(self, userns_mode: impl Into<String>) -> Self {
+        Self {
+            userns_mode: Some(userns_mode.into()),
+            ..self
+        }
+    }
+
+    /// Adds a mount to the container, see [`Mount`] for the supported kinds of mounts.
+    pub fn with_mount(self, mount: Mount) -> Self {
+        let mut mounts = self.mounts;
+        mounts.push(mount);
+        Self { mounts, ..self }
+    }
+
+    /// Overrides the entrypoint of the image, e.g. `["sh", "-c"]`.
+    pub fn with_entrypoint(self, entrypoint: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            entrypoint: Some(entrypoint.into_iter().map(Into::into).collect()),
+            ..self
+        }
+    }
+
+    /// Clears the entrypoint of the image, so the command of the container is executed directly.
+    pub fn with_entrypoint_cleared(self) -> Self {
+        // an empty string is how `docker run --entrypoint ""` resets the entrypoint
+        Self {
+            entrypoint: Some(vec![String::new()]),
+            ..self
+        }
+    }
+
+    /// Adds a supplementary group (name or GID) to the user of the container, e.g. the group
+    /// that owns a mounted `/var/run/docker.sock`.
+    pub fn with_group_add(self, group: impl Into<String>) -> Self {
+        let mut group_add = self.group_add;
+        group_add.push(group.into());
+        Self { group_add, ..self }
+    }
+
+    /// Sets the parent cgroup of the container, e.g. a slice that CI runners are allowed to
+    /// place containers in.
+    pub fn with_cgroup_parent(self, cgroup_parent: impl Into<String>) -> Self {
+        Self {
+            cgroup_parent: Some(cgroup_parent.into()),
+            ..self
+        }
+    }
+
+    /// Sets the cgroup namespace of the container.
+    pub fn with_cgroupns_mode(self, cgroupns_mode: CgroupnsMode) -> Self {
+        Self {
+            cgroupns_mode: Some(cgroupns_mode),
+            ..self
+        }
+    }
+
+    /// Lets the docker daemon remove the container as soon as it exits, e.g. for short-lived
+    /// helper containers that should not be left behind even if the test process is killed.
+    ///
+    /// Note that the container can't be inspected anymore once it has exited, e.g. after
+    /// [`ContainerAsync::stop`].
+    ///
+    /// [`ContainerAsync::stop`]: crate::ContainerAsync::stop
+    pub fn with_auto_remove(self, auto_remove: bool) -> Self {
+        Self {
+            auto_remove,
+            ..self
+        }
+    }
+
+    /// Prevents the kernel from killing the processes of the container when it runs out of
+    /// memory. Should only be used together with [`RunnableImage::with_memory_limit`].
+    ///
+    /// Not supported by hosts that use cgroup v2, where docker ignores it.
+    pub fn with_oom_kill_disable(self, oom_kill_disable: bool) -> Self {
+        Self {
+            oom_kill_disable,
+            ..self
+        }
+    }
+
+    /// Adjusts how likely the kernel kills the container when the host runs out of memory,
+    /// from `-1000` (never) to `1000` (first).
+    ///
+    /// If the score is outside of this range, starting the container fails with
+    /// [`TestcontainersError::InvalidConfiguration`].
+    pub fn with_oom_score_adj(self, oom_score_adj: i32) -> Self {
+        Self {
+            oom_score_adj: Some(oom_score_adj),
+            ..self
+        }
+    }
+
+    /// Sets the network mode of the container, e.g. `host` to use the network stack of the
+    /// host, or `none` to disable networking.
+    ///
+    /// With `host` networking, ports are not mapped: the container binds directly to the ports
+    /// of the host, so [`ContainerAsync::get_host_port_ipv4`] returns the given port as-is. The
+    /// same applies to `none` and `container:<name|id>`, while the ports of a container in any
+    /// other mode, e.g. `bridge`, are mapped as usual. Use [`RunnableImage::with_network`] to
+    /// join a user-defined network instead.
+    ///
+    /// `host` networking is only supported on Linux, as Docker Desktop runs containers in a
+    /// virtual machine whose network is not the one of the host. Elsewhere, starting the
+    /// container fails with [`TestcontainersError::InvalidConfiguration`].
+    ///
+    /// [`ContainerAsync::get_host_port_ipv4`]: crate::ContainerAsync::get_host_port_ipv4
+    /// [`TestcontainersError::InvalidConfiguration`]: crate::core::TestcontainersError::InvalidConfiguration
+    pub fn with_network_mode(self, network_mode: impl Into<String>) -> Self {
+        Self {
+            network_mode: Some(network_mode.into()),
+            ..self
+        }
+    }
+
+    /// Forwards the logs of the container to the consumer as they are written, from its start
+    /// on. Useful to debug failures that only occur on CI.
+    pub fn with_log_consumer(self, consumer: impl LogConsumer + 'static) -> Self {
+        let mut log_consumers = self.log_consumers;
+        log_consumers.push(Arc::new(consumer));
+        Self {
+            log_consumers,
+            ..self
+        }
+    }
+
+    /// Sets when the logs of the container are written to
+    /// `target/testcontainers/<container-name>.log`, overriding the `TESTCONTAINERS_LOG_DUMP`
+    /// environment variable. Combine with [`RunnableImage::with_container_name`] for a
+    /// predictable file name.
+    pub fn with_log_dump(self, log_dump: LogDump) -> Self {
+        Self {
+            log_dump: Some(log_dump),
+            ..self
+        }
+    }
+
+    /// Sets when the container is removed once it is dropped, overriding the
+    /// `TESTCONTAINERS_KEEP_ON_FAILURE` environment variable. With [`RemovePolicy::OnSuccess`],
+    /// the container of a failed test is left running so that it can be inspected. Unless the
+    /// policy is [`RemovePolicy::Always`], neither the resource reaper nor the watchdog remove
+    /// the container.
+    pub fn with_remove_on_drop(self, policy: RemovePolicy) -> Self {
+        Self {
+            remove_on_drop: Some(policy),
+            ..self
+        }
+    }
+
+    /// Sets how long to wait for the container to meet its ready conditions.
+    ///
+    /// If the container is not ready in time, starting it fails with a [`StartupError`]
+    /// that contains the logs of the container. Defaults to the value of the
+    /// `TESTCONTAINERS_STARTUP_TIMEOUT` environment variable (in seconds), or 60 seconds.
+    ///
+    /// [`StartupError`]: crate::core::StartupError
+    pub fn with_startup_timeout(self, timeout: Duration) -> Self {
+        Self {
+            startup_timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// Sets how many times the container is created before failing to start it.
+    ///
+    /// If a container does not become ready, it is removed and a new one is created in its place,
+    /// until the number of attempts is exhausted. Useful for images that are known to fail
+    /// sporadically during their startup. Defaults to `1`, and `0` is treated as `1`.
+    pub fn with_startup_attempts(self, attempts: u32) -> Self {
+        Self {
+            startup_attempts: attempts.max(1),
+            ..self
+        }
+    }
+
+    /// Adds a command that is executed before the container is removed, e.g. to let a database
+    /// checkpoint its data, so that a volume that is reused by later containers is not left
+    /// corrupted.
+    ///
+    /// Once the commands ran, the container is stopped gracefully with its stop signal, see
+    /// [`RunnableImage::with_stop_signal`] and [`RunnableImage::with_stop_timeout`], before it
+    /// is removed. A command that fails is logged and doesn't prevent the removal. The commands
+    /// don't run for a container that is kept, see [`RunnableImage::with_remove_on_drop`].
+    pub fn with_shutdown_command(mut self, cmd: ExecCommand) -> Self {
+        self.shutdown_commands.push(cmd);
+        self
+    }
 }
 
 impl<I> From<I> for RunnableImage<I>
@@ -387,11 +1343,57 @@ impl<I: Image> From<(I, I::Args)> for RunnableImage<I> {
             container_name: None,
             network: None,
             env_vars: BTreeMap::default(),
+            env_file_errors: Vec::new(),
             hosts: BTreeMap::default(),
             volumes: BTreeMap::default(),
             ports: None,
             privileged: false,
             shm_size: None,
+            cmd: None,
+            user: None,
+            working_dir: None,
+            labels: BTreeMap::default(),
+            memory_limit: None,
+            memory_swap: None,
+            cpus: None,
+            cpu_shares: None,
+            ulimits: BTreeMap::default(),
+            cap_add: Vec::new(),
+            cap_drop: Vec::new(),
+            security_opts: Vec::new(),
+            readonly_rootfs: false,
+            devices: Vec::new(),
+            network_aliases: Vec::new(),
+            network_subnets: Vec::new(),
+            ipv4_address: None,
+            ipv6_address: None,
+            platform: None,
+            stop_signal: None,
+            stop_timeout: None,
+            healthcheck: None,
+            log_driver: None,
+            log_options: BTreeMap::default(),
+            gpus: None,
+            sysctls: BTreeMap::default(),
+            init: false,
+            pid_mode: None,
+            ipc_mode: None,
+            userns_mode: None,
+            mounts: Vec::new(),
+            entrypoint: None,
+            group_add: Vec::new(),
+            cgroup_parent: None,
+            cgroupns_mode: None,
+            auto_remove: false,
+            oom_kill_disable: false,
+            oom_score_adj: None,
+            network_mode: None,
+            log_consumers: Vec::new(),
+            log_dump: None,
+            remove_on_drop: None,
+            startup_timeout: None,
+            startup_attempts: 1,
+            shutdown_commands: Vec::new(),
         }
     }
 }
@@ -403,60 +1405,6 @@ pub struct Port {
     pub internal: u16,
 }
 
-/// Represents a condition that needs to be met before a container is considered ready.
-#[derive(Debug, Eq, PartialEq, Clone)]
-pub enum WaitFor {
-    /// An empty condition. Useful for default cases or fallbacks.
-    Nothing,
-    /// Wait for a message on the stdout stream of the container's logs.
-    StdOutMessage { message: String },
-    /// Wait for a message on the stderr stream of the container's logs.
-    StdErrMessage { message: String },
-    /// Wait for a certain amount of time.
-    Duration { length: Duration },
-    /// Wait for the container's status to become `healthy`.
-    Healthcheck,
-}
-
-impl WaitFor {
-    pub fn message_on_stdout<S: Into<String>>(message: S) -> WaitFor {
-        WaitFor::StdOutMessage {
-            message: message.into(),
-        }
-    }
-
-    pub fn message_on_stderr<S: Into<String>>(message: S) -> WaitFor {
-        WaitFor::StdErrMessage {
-            message: message.into(),
-        }
-    }
-
-    pub fn seconds(length: u64) -> WaitFor {
-        WaitFor::Duration {
-            length: Duration::from_secs(length),
-        }
-    }
-
-    pub fn millis(length: u64) -> WaitFor {
-        WaitFor::Duration {
-            length: Duration::from_millis(length),
-        }
-    }
-
-    pub fn millis_in_env_var(name: &'static str) -> WaitFor {
-        let additional_sleep_period = var(name).map(|value| value.parse());
-
-        (|| {
-            let length = additional_sleep_period.ok()?.ok()?;
-
-            Some(WaitFor::Duration {
-                length: Duration::from_millis(length),
-            })
-        })()
-        .unwrap_or(WaitFor::Nothing)
-    }
-}
-
 impl From<(u16, u16)> for Port {
     fn from((local, internal): (u16, u16)) -> Self {
         Port { local, internal }
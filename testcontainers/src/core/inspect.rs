@@ -0,0 +1,179 @@
+use std::{collections::BTreeMap, net::IpAddr};
+
+use bollard_stubs::models::{ContainerInspectResponse, EndpointSettings, MountPoint};
+
+/// How a container was actually created and its current state, as reported by
+/// `docker inspect`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerInfo {
+    pub id: String,
+    /// The name of the container, without the leading `/`
+    pub name: String,
+    /// The ID of the image the container was created from
+    pub image_id: String,
+    /// The time the container was created, in RFC 3339 format
+    pub created: String,
+    /// How often the container was restarted by its restart policy
+    pub restart_count: i64,
+    pub state: StateInfo,
+    pub mounts: Vec<MountInfo>,
+    /// The networks the container is connected to, by name
+    pub networks: BTreeMap<String, NetworkInfo>,
+    pub config: ConfigInfo,
+}
+
+/// The state of a container, see [`ContainerInfo::state`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StateInfo {
+    /// One of `created`, `running`, `paused`, `restarting`, `removing`, `exited` or `dead`
+    pub status: String,
+    pub running: bool,
+    pub paused: bool,
+    /// Whether a process of the container was killed because it ran out of memory
+    pub oom_killed: bool,
+    /// The process ID of the main process, if it is running
+    pub pid: Option<i64>,
+    /// The exit code of the last run of the container
+    pub exit_code: Option<i64>,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    /// One of `starting`, `healthy` or `unhealthy`, if the container has a healthcheck
+    pub health: Option<String>,
+}
+
+/// A mount of a container, see [`ContainerInfo::mounts`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MountInfo {
+    /// One of `bind`, `volume`, `tmpfs`, `npipe` or `cluster`
+    pub kind: String,
+    /// The name of the volume, for volume mounts
+    pub name: Option<String>,
+    /// The path on the host, or of the volume
+    pub source: Option<String>,
+    /// The path in the container
+    pub destination: String,
+    pub read_only: bool,
+}
+
+/// The settings of a container in a network, see [`ContainerInfo::networks`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkInfo {
+    pub ip_address: Option<IpAddr>,
+    pub gateway: Option<IpAddr>,
+    pub aliases: Vec<String>,
+    pub mac_address: Option<String>,
+}
+
+/// The configuration a container was created with, see [`ContainerInfo::config`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigInfo {
+    /// The image as it was requested, e.g. `redis:7`
+    pub image: String,
+    pub hostname: Option<String>,
+    pub user: Option<String>,
+    /// The environment variables, including the ones defined by the image
+    pub env: Vec<(String, String)>,
+    pub entrypoint: Vec<String>,
+    pub cmd: Vec<String>,
+    pub working_dir: Option<String>,
+    pub labels: BTreeMap<String, String>,
+}
+
+impl From<ContainerInspectResponse> for ContainerInfo {
+    fn from(response: ContainerInspectResponse) -> Self {
+        let state = response.state.unwrap_or_default();
+        let running = state.running.unwrap_or_default();
+        let state = StateInfo {
+            status: state.status.map(|s| s.to_string()).unwrap_or_default(),
+            running,
+            paused: state.paused.unwrap_or_default(),
+            oom_killed: state.oom_killed.unwrap_or_default(),
+            pid: state.pid.filter(|_| running),
+            exit_code: state.exit_code,
+            started_at: non_empty(state.started_at),
+            finished_at: non_empty(state.finished_at),
+            health: non_empty(
+                state
+                    .health
+                    .and_then(|health| health.status)
+                    .map(|s| s.to_string()),
+            )
+            .filter(|status| status != "none"),
+        };
+
+        let config = response.config.unwrap_or_default();
+        let config = ConfigInfo {
+            image: config.image.unwrap_or_default(),
+            hostname: non_empty(config.hostname),
+            user: non_empty(config.user),
+            env: config
+                .env
+                .unwrap_or_default()
+                .into_iter()
+                .map(|var| match var.split_once('=') {
+                    Some((key, value)) => (key.to_string(), value.to_string()),
+                    None => (var, String::new()),
+                })
+                .collect(),
+            entrypoint: config.entrypoint.unwrap_or_default(),
+            cmd: config.cmd.unwrap_or_default(),
+            working_dir: non_empty(config.working_dir),
+            labels: config.labels.unwrap_or_default().into_iter().collect(),
+        };
+
+        Self {
+            id: response.id.unwrap_or_default(),
+            name: response
+                .name
+                .unwrap_or_default()
+                .trim_start_matches('/')
+                .to_string(),
+            image_id: response.image.unwrap_or_default(),
+            created: response.created.unwrap_or_default(),
+            restart_count: response.restart_count.unwrap_or_default(),
+            state,
+            mounts: response
+                .mounts
+                .unwrap_or_default()
+                .into_iter()
+                .map(MountInfo::from)
+                .collect(),
+            networks: response
+                .network_settings
+                .and_then(|settings| settings.networks)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(name, endpoint)| (name, NetworkInfo::from(endpoint)))
+                .collect(),
+            config,
+        }
+    }
+}
+
+impl From<MountPoint> for MountInfo {
+    fn from(mount: MountPoint) -> Self {
+        Self {
+            kind: mount.typ.map(|typ| typ.to_string()).unwrap_or_default(),
+            name: non_empty(mount.name),
+            source: non_empty(mount.source),
+            destination: mount.destination.unwrap_or_default(),
+            read_only: !mount.rw.unwrap_or(true),
+        }
+    }
+}
+
+impl From<EndpointSettings> for NetworkInfo {
+    fn from(endpoint: EndpointSettings) -> Self {
+        Self {
+            ip_address: endpoint.ip_address.and_then(|ip| ip.parse().ok()),
+            gateway: endpoint.gateway.and_then(|ip| ip.parse().ok()),
+            aliases: endpoint.aliases.unwrap_or_default(),
+            mac_address: non_empty(endpoint.mac_address),
+        }
+    }
+}
+
+/// Docker reports unset values as empty strings.
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|value| !value.is_empty())
+}
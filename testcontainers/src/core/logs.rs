@@ -1,5 +1,249 @@
+use crate::core::{
+    client::{Client, DesiredLogStream},
+    runtime,
+};
 use futures::{stream::BoxStream, StreamExt};
-use std::{fmt, io};
+use regex::Regex;
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
+
+/// A line a container has written to its logs.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LogLine {
+    /// When the line was written, in RFC 3339 format with nanoseconds, e.g.
+    /// `2024-04-01T12:00:00.123456789Z`
+    pub timestamp: String,
+    /// The content of the line, without the line break
+    pub message: String,
+}
+
+impl LogLine {
+    /// Parses a log line of docker, which is prefixed with its timestamp. Containers may write
+    /// anything to their logs, so invalid UTF-8 is replaced with `U+FFFD`.
+    pub(crate) fn parse(bytes: &[u8]) -> Self {
+        let line = String::from_utf8_lossy(bytes);
+        let line = line.trim_end_matches(['\n', '\r']);
+        let (timestamp, message) = line.split_once(' ').unwrap_or((line, ""));
+
+        Self {
+            timestamp: timestamp.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Joins the frames of the logs of one stream of a container into lines.
+///
+/// The daemon splits lines longer than 16 KiB into several frames, each prefixed with a
+/// timestamp of its own, so a line is only complete once a frame ends with a line break.
+#[derive(Debug, Default)]
+pub(crate) struct LineBuffer {
+    /// The first frame of the incomplete line, followed by the content of the next ones
+    pending: Vec<u8>,
+}
+
+impl LineBuffer {
+    /// Adds a frame, and returns the line it completes, if any.
+    pub(crate) fn push(&mut self, frame: &[u8]) -> Option<LogLine> {
+        if self.pending.is_empty() {
+            self.pending.extend_from_slice(frame);
+        } else {
+            // only the timestamp of the first frame is kept
+            let content = frame
+                .iter()
+                .position(|&byte| byte == b' ')
+                .map_or(&[][..], |space| &frame[space + 1..]);
+            self.pending.extend_from_slice(content);
+        }
+
+        if self.pending.ends_with(b"\n") {
+            self.finish()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the incomplete line, e.g. once the logs ended.
+    pub(crate) fn finish(&mut self) -> Option<LogLine> {
+        if self.pending.is_empty() {
+            return None;
+        }
+
+        Some(LogLine::parse(&std::mem::take(&mut self.pending)))
+    }
+}
+
+/// The stream a container has written a log line to.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum LogSource {
+    StdOut,
+    StdErr,
+}
+
+/// Receives the lines a container writes to its logs as they are written, e.g. to forward them
+/// to `tracing`, to a file or to a buffer of the test.
+///
+/// Implemented for closures:
+///
+/// ```rust
+/// use testcontainers::{
+///     core::{LogLine, LogSource, RunnableImage},
+///     GenericImage,
+/// };
+///
+/// let image = RunnableImage::from(GenericImage::new("redis", "7.2.4")).with_log_consumer(
+///     |source: LogSource, line: &LogLine| println!("[redis {source:?}] {}", line.message),
+/// );
+/// ```
+pub trait LogConsumer: Send + Sync {
+    /// Called for each line, in the order they are written to each stream.
+    fn accept(&self, source: LogSource, line: &LogLine);
+}
+
+impl<F> LogConsumer for F
+where
+    F: Fn(LogSource, &LogLine) + Send + Sync,
+{
+    fn accept(&self, source: LogSource, line: &LogLine) {
+        self(source, line)
+    }
+}
+
+impl fmt::Debug for dyn LogConsumer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogConsumer").finish_non_exhaustive()
+    }
+}
+
+/// Forwards the logs of the container to the consumers until the container stops.
+///
+/// The logs are read in the background, so they are forwarded in real time independent of the
+/// runtime the container was started with. Each consumer is called on a blocking task of its
+/// own, so a slow consumer neither holds up the others nor the runtime.
+pub(crate) fn forward_to_consumers(id: String, consumers: Vec<Arc<dyn LogConsumer>>) {
+    let senders = consumers
+        .into_iter()
+        .map(|consumer| {
+            let (tx, rx) = std::sync::mpsc::channel::<(LogSource, LogLine)>();
+            runtime::spawn_blocking(move || {
+                for (source, line) in rx {
+                    consumer.accept(source, &line);
+                }
+            });
+            tx
+        })
+        .collect::<Vec<_>>();
+
+    runtime::spawn(async move {
+        let client = Client::lazy_client().await;
+        let mut lines = client.log_lines(&id, DesiredLogStream::Both);
+
+        while let Some(line) = lines.next().await {
+            match line {
+                Ok((source, line)) => {
+                    for tx in &senders {
+                        // the consumer only stops receiving if it panicked
+                        let _ = tx.send((source, line.clone()));
+                    }
+                }
+                // the stream ends by itself if the logs can't be read anymore
+                Err(err) => log::warn!("Failed to forward logs of container {id}: {err}"),
+            }
+        }
+    });
+}
+
+/// When the logs of a container are written to `target/testcontainers/<container-name>.log`.
+///
+/// Configured per container with [`RunnableImage::with_log_dump`], or for all containers with
+/// the `TESTCONTAINERS_LOG_DUMP` environment variable (`never`, `on_failure` or `always`).
+///
+/// [`RunnableImage::with_log_dump`]: crate::core::RunnableImage::with_log_dump
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum LogDump {
+    /// The logs are never written.
+    Never,
+    /// The logs are written if the container is dropped while the thread is panicking, e.g.
+    /// because an assertion of the test failed, or if the container fails to start.
+    #[default]
+    OnFailure,
+    /// The logs are always written when the container is dropped.
+    Always,
+}
+
+impl LogDump {
+    pub(crate) fn applies(self, failed: bool) -> bool {
+        match self {
+            LogDump::Never => false,
+            LogDump::OnFailure => failed,
+            LogDump::Always => true,
+        }
+    }
+}
+
+impl FromStr for LogDump {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "never" => Ok(LogDump::Never),
+            "on_failure" => Ok(LogDump::OnFailure),
+            "always" => Ok(LogDump::Always),
+            other => {
+                panic!("unknown log dump mode '{other}' provided via TESTCONTAINERS_LOG_DUMP env variable")
+            }
+        }
+    }
+}
+
+/// Writes the stdout and stderr of the container to `<target>/testcontainers/<name>.log`, where
+/// the name is the one of the container, or its id if it can't be inspected anymore.
+pub(crate) async fn dump_to_file(client: &Client, id: &str) {
+    let name = client
+        .bollard
+        .inspect_container(id, None)
+        .await
+        .ok()
+        .and_then(|container| container.name)
+        .map(|name| name.trim_start_matches('/').to_string())
+        .unwrap_or_else(|| id.to_string());
+    let logs = client.collected_logs(id, DesiredLogStream::Both).await;
+
+    let dir = target_dir().join("testcontainers");
+    let path = dir.join(format!("{name}.log"));
+    let result = match tokio::fs::create_dir_all(&dir).await {
+        Ok(()) => tokio::fs::write(&path, logs).await,
+        Err(err) => Err(err),
+    };
+    match result {
+        Ok(()) => log::info!("Logs of container {id} written to {}", path.display()),
+        Err(err) => log::warn!(
+            "Failed to write logs of container {id} to {}: {err}",
+            path.display()
+        ),
+    }
+}
+
+/// Returns the target directory of cargo, which contains the running test executable.
+fn target_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("CARGO_TARGET_DIR") {
+        return PathBuf::from(dir);
+    }
+
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| {
+            exe.ancestors()
+                .find(|dir| dir.file_name().is_some_and(|name| name == "target"))
+                .map(Path::to_path_buf)
+        })
+        .unwrap_or_else(|| PathBuf::from("target"))
+}
 
 pub(crate) struct LogStreamAsync<'d> {
     inner: BoxStream<'d, Result<String, io::Error>>,
@@ -16,21 +260,111 @@ impl<'d> LogStreamAsync<'d> {
         Self { inner: stream }
     }
 
-    pub async fn wait_for_message(mut self, message: &str) -> Result<(), WaitError> {
+    /// Waits for the message, which may span several lines.
+    pub async fn wait_for_message(self, message: &str) -> Result<(), WaitError> {
+        let message = message.strip_suffix('\n').unwrap_or(message);
+
+        self.wait_for_line(line_count(message), |lines| lines.contains(message))
+            .await
+    }
+
+    pub async fn wait_for_message_times(
+        self,
+        message: &str,
+        times: usize,
+    ) -> Result<(), WaitError> {
+        let message = message.strip_suffix('\n').unwrap_or(message);
+        let mut occurrences = 0;
+
+        // an occurrence spans all the lines it is matched against, so it is only counted once
+        self.wait_for_line(line_count(message), |lines| {
+            occurrences += lines.matches(message).count();
+            occurrences >= times
+        })
+        .await
+    }
+
+    pub async fn wait_for_match(self, pattern: &Regex) -> Result<(), WaitError> {
+        self.wait_for_line(1, |line| pattern.is_match(line)).await
+    }
+
+    /// Waits until the stream does not yield anything for the given duration, or ends.
+    pub async fn wait_for_silence(mut self, duration: Duration) -> Result<(), io::Error> {
+        while let Ok(chunk) = tokio::time::timeout(duration, self.inner.next()).await {
+            match chunk {
+                Some(chunk) => {
+                    chunk?;
+                }
+                None => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads the stream until its end and returns everything that was read.
+    pub async fn read_to_string(mut self) -> Result<String, io::Error> {
+        let mut output = String::new();
+
+        while let Some(chunk) = self.inner.next().await.transpose()? {
+            output.push_str(&chunk);
+        }
+
+        Ok(output)
+    }
+
+    /// Matches each line, joined with the `window - 1` lines before it.
+    async fn wait_for_line(
+        mut self,
+        window: usize,
+        mut matches: impl FnMut(&str) -> bool,
+    ) -> Result<(), WaitError> {
         let mut lines = vec![];
+        let mut pending = String::new();
+
+        // Docker hands out the logs in chunks that neither start nor end on line boundaries, so
+        // they are buffered into complete lines before being matched.
+        while let Some(chunk) = self.inner.next().await.transpose()? {
+            pending.push_str(&chunk);
+
+            while let Some(end) = pending.find('\n') {
+                let line = pending[..end].trim_end_matches('\r').to_string();
+                pending.drain(..=end);
 
-        while let Some(line) = self.inner.next().await.transpose()? {
-            if handle_line(line, message, &mut lines) {
-                return Ok(());
+                if handle_line(line, window, &mut matches, &mut lines) {
+                    return Ok(());
+                }
             }
         }
 
+        if !pending.is_empty() && handle_line(pending, window, &mut matches, &mut lines) {
+            return Ok(());
+        }
+
         Err(end_of_stream(lines))
     }
 }
 
-fn handle_line(line: String, message: &str, lines: &mut Vec<String>) -> bool {
-    if line.contains(message) {
+/// Returns the number of lines the message spans.
+fn line_count(message: &str) -> usize {
+    message.matches('\n').count() + 1
+}
+
+fn handle_line(
+    line: String,
+    window: usize,
+    matches: &mut impl FnMut(&str) -> bool,
+    lines: &mut Vec<String>,
+) -> bool {
+    let start = lines.len().saturating_sub(window - 1);
+    let recent = lines[start..]
+        .iter()
+        .map(String::as_str)
+        .chain([line.as_str()])
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if matches(&recent) {
         log::info!("Found message after comparing {} lines", lines.len());
 
         return true;
@@ -69,6 +403,62 @@ impl From<io::Error> for WaitError {
 mod tests {
     use super::*;
 
+    #[test]
+    fn log_dump_applies_depending_on_failure() {
+        assert!(!LogDump::Never.applies(true));
+        assert!(!LogDump::OnFailure.applies(false));
+        assert!(LogDump::OnFailure.applies(true));
+        assert!(LogDump::Always.applies(false));
+        assert_eq!("on_failure".parse(), Ok(LogDump::OnFailure));
+    }
+
+    #[test]
+    fn parses_log_line_with_timestamp() {
+        let line = LogLine::parse(b"2024-04-01T12:00:00.123456789Z server is ready\n");
+
+        assert_eq!(
+            line,
+            LogLine {
+                timestamp: "2024-04-01T12:00:00.123456789Z".to_string(),
+                message: "server is ready".to_string(),
+            }
+        );
+        assert_eq!(
+            LogLine::parse(b"2024-04-01T12:00:00.123456789Z \n").message,
+            ""
+        );
+        assert_eq!(
+            LogLine::parse(b"2024-04-01T12:00:00.123456789Z caf\xe9\n").message,
+            "caf\u{fffd}"
+        );
+    }
+
+    #[test]
+    fn joins_frames_of_a_split_line() {
+        let mut buffer = LineBuffer::default();
+
+        assert_eq!(buffer.push(b"2024-04-01T12:00:00.1Z first half, "), None);
+        assert_eq!(
+            buffer.push(b"2024-04-01T12:00:00.2Z second half\n"),
+            Some(LogLine {
+                timestamp: "2024-04-01T12:00:00.1Z".to_string(),
+                message: "first half, second half".to_string(),
+            })
+        );
+        // a character may be split as well
+        assert_eq!(buffer.push(b"2024-04-01T12:00:00.3Z caf\xc3"), None);
+        assert_eq!(
+            buffer
+                .push(b"2024-04-01T12:00:00.4Z \xa9\n")
+                .unwrap()
+                .message,
+            "caf\u{e9}"
+        );
+        assert_eq!(buffer.push(b"2024-04-01T12:00:00.5Z incomplete"), None);
+        assert_eq!(buffer.finish().unwrap().message, "incomplete");
+        assert_eq!(buffer.finish(), None);
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn given_logs_when_line_contains_message_should_find_it() {
         let log_stream = LogStreamAsync::new(Box::pin(futures::stream::iter([Ok(r"
@@ -82,4 +472,126 @@ mod tests {
 
         assert!(result.is_ok())
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn given_logs_when_message_spans_lines_should_find_it() {
+        let log_stream = LogStreamAsync::new(Box::pin(futures::stream::iter([
+            Ok("starting\r\nDatabase system is\n".to_string()),
+            Ok("ready to accept connections\n".to_string()),
+        ])));
+
+        let result = log_stream
+            .wait_for_message("system is\nready to accept connections\n")
+            .await;
+
+        assert!(result.is_ok())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn given_logs_when_message_spans_lines_should_count_each_occurrence_once() {
+        let found = |times| {
+            LogStreamAsync::new(Box::pin(futures::stream::iter([Ok(
+                "listening\nready\nlistening\nready\n".to_string(),
+            )])))
+            .wait_for_message_times("listening\nready", times)
+        };
+
+        assert!(found(2).await.is_ok());
+        assert!(matches!(found(3).await, Err(WaitError::EndOfStream(_))));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn given_logs_when_line_matches_pattern_should_find_it() {
+        let log_stream = LogStreamAsync::new(Box::pin(futures::stream::iter([Ok(r"
+            Listening on port 49153
+            Ready in 1234ms
+        "
+        .to_string())])));
+
+        let pattern = Regex::new(r"Ready in \d+ms").unwrap();
+        let result = log_stream.wait_for_match(&pattern).await;
+
+        assert!(result.is_ok())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn given_logs_when_line_is_split_across_chunks_should_match_whole_line() {
+        let log_stream = LogStreamAsync::new(Box::pin(futures::stream::iter([
+            Ok("Listening on port 49153\nReady in 12".to_string()),
+            Ok("34ms\n".to_string()),
+        ])));
+
+        let pattern = Regex::new(r"^Ready in \d+ms$").unwrap();
+        let result = log_stream.wait_for_match(&pattern).await;
+
+        assert!(result.is_ok())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn given_logs_when_pattern_spans_lines_should_not_find_it() {
+        let log_stream = LogStreamAsync::new(Box::pin(futures::stream::iter([Ok(
+            "Ready in\n1234ms\n".to_string(),
+        )])));
+
+        let pattern = Regex::new(r"Ready in\s+\d+ms").unwrap();
+        let result = log_stream.wait_for_match(&pattern).await;
+
+        assert!(matches!(
+            result,
+            Err(WaitError::EndOfStream(lines)) if lines == ["Ready in", "1234ms"]
+        ))
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn given_logs_when_message_occurs_less_often_than_expected_should_not_find_it() {
+        let log_stream = LogStreamAsync::new(Box::pin(futures::stream::iter([
+            Ok("started\n".to_string()),
+            Ok("elected leader\nstarted\n".to_string()),
+        ])));
+
+        let result = log_stream.wait_for_message_times("started", 3).await;
+
+        assert!(matches!(result, Err(WaitError::EndOfStream(_))))
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn given_logs_when_message_occurs_expected_times_should_find_it() {
+        let log_stream = LogStreamAsync::new(Box::pin(futures::stream::iter([
+            Ok("started\n".to_string()),
+            Ok("elected leader\nstarted\n".to_string()),
+        ])));
+
+        let result = log_stream.wait_for_message_times("started", 2).await;
+
+        assert!(result.is_ok())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn given_logs_when_message_is_split_across_chunks_should_count_it() {
+        let log_stream = LogStreamAsync::new(Box::pin(futures::stream::iter([
+            Ok("star".to_string()),
+            Ok("ted\nelected leader\nsta".to_string()),
+            Ok("rted\n".to_string()),
+        ])));
+
+        let result = log_stream.wait_for_message_times("started", 2).await;
+
+        assert!(result.is_ok())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn given_logs_when_no_more_output_appears_should_detect_silence() {
+        let log_stream = LogStreamAsync::new(Box::pin(
+            futures::stream::iter([Ok("starting\n".to_string()), Ok("started\n".to_string())])
+                .chain(futures::stream::pending()),
+        ));
+
+        let result = tokio::time::timeout(
+            Duration::from_secs(5),
+            log_stream.wait_for_silence(Duration::from_millis(50)),
+        )
+        .await;
+
+        assert!(matches!(result, Ok(Ok(()))))
+    }
 }
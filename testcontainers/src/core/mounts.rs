@@ -0,0 +1,254 @@
+use std::path::Path;
+
+use crate::core::Volume;
+
+/// Describes how a host path, a volume or a tmpfs is mounted into a container.
+///
+/// ```rust
+/// use testcontainers::core::{Mount, SelinuxRelabel};
+///
+/// let fixtures = Mount::bind_ro("/home/me/project/fixtures", "/fixtures")
+///     .with_selinux_relabel(SelinuxRelabel::Private);
+/// let data = Mount::volume("postgres-data", "/var/lib/postgresql/data");
+/// let scratch = Mount::tmpfs("/tmp").with_tmpfs_size(64 * 1024 * 1024);
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Mount {
+    kind: MountKind,
+    container_path: String,
+    read_only: bool,
+    selinux_relabel: Option<SelinuxRelabel>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum MountKind {
+    Bind {
+        host_path: String,
+    },
+    Volume {
+        name: String,
+        // keeps a managed volume alive as long as the mount
+        managed: Option<Volume>,
+    },
+    Tmpfs {
+        size: Option<u64>,
+    },
+}
+
+/// How the SELinux label of a mounted host path or volume is changed, so the container is
+/// allowed to access it.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SelinuxRelabel {
+    /// The content is shared with other containers (`z`).
+    Shared,
+    /// The content is private to the container (`Z`).
+    Private,
+}
+
+impl Mount {
+    /// Mounts the given path of the host into the container.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either path is not absolute, as docker would treat a relative host path as
+    /// the name of a volume.
+    pub fn bind(host_path: impl Into<String>, container_path: impl Into<String>) -> Self {
+        let host_path = host_path.into();
+        if !Path::new(&host_path).is_absolute() {
+            panic!("host path '{host_path}' of bind mount must be absolute");
+        }
+
+        Self::new(MountKind::Bind { host_path }, container_path.into())
+    }
+
+    /// Mounts the given path of the host into the container as read-only.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either path is not absolute, see [`Mount::bind`].
+    pub fn bind_ro(host_path: impl Into<String>, container_path: impl Into<String>) -> Self {
+        Self::bind(host_path, container_path).with_read_only(true)
+    }
+
+    /// Mounts the named volume into the container. The volume is created if it does not exist.
+    ///
+    /// See [`Volume`] for volumes that are removed automatically.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the container path is not absolute.
+    pub fn volume(name: impl Into<String>, container_path: impl Into<String>) -> Self {
+        let kind = MountKind::Volume {
+            name: name.into(),
+            managed: None,
+        };
+        Self::new(kind, container_path.into())
+    }
+
+    pub(crate) fn managed_volume(volume: Volume, container_path: impl Into<String>) -> Self {
+        let kind = MountKind::Volume {
+            name: volume.name().to_string(),
+            managed: Some(volume),
+        };
+        Self::new(kind, container_path.into())
+    }
+
+    /// Mounts a temporary, in-memory filesystem into the container.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the container path is not absolute.
+    pub fn tmpfs(container_path: impl Into<String>) -> Self {
+        Self::new(MountKind::Tmpfs { size: None }, container_path.into())
+    }
+
+    fn new(kind: MountKind, container_path: String) -> Self {
+        // the container path is always a unix path, independent of the host
+        if !container_path.starts_with('/') {
+            panic!("container path '{container_path}' of mount must be absolute");
+        }
+
+        Self {
+            kind,
+            container_path,
+            read_only: false,
+            selinux_relabel: None,
+        }
+    }
+
+    /// Mounts the content as read-only.
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Relabels the mounted host path or volume for SELinux. Has no effect on tmpfs mounts.
+    pub fn with_selinux_relabel(mut self, relabel: SelinuxRelabel) -> Self {
+        self.selinux_relabel = Some(relabel);
+        self
+    }
+
+    /// Limits the size of a tmpfs mount, in bytes. Has no effect on other mounts.
+    pub fn with_tmpfs_size(mut self, bytes: u64) -> Self {
+        if let MountKind::Tmpfs { size } = &mut self.kind {
+            *size = Some(bytes);
+        }
+        self
+    }
+
+    /// The path the content is mounted at inside the container.
+    pub fn container_path(&self) -> &str {
+        &self.container_path
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Returns the bind specification (`source:target[:options]`) of a bind or volume mount.
+    pub(crate) fn bind_spec(&self) -> Option<String> {
+        let source = match &self.kind {
+            MountKind::Bind { host_path } => host_path,
+            MountKind::Volume { name, .. } => name,
+            MountKind::Tmpfs { .. } => return None,
+        };
+
+        let mut options = Vec::new();
+        if self.read_only {
+            options.push("ro");
+        }
+        match self.selinux_relabel {
+            Some(SelinuxRelabel::Shared) => options.push("z"),
+            Some(SelinuxRelabel::Private) => options.push("Z"),
+            None => {}
+        }
+
+        let mut spec = format!("{source}:{}", self.container_path);
+        if !options.is_empty() {
+            spec.push(':');
+            spec.push_str(&options.join(","));
+        }
+
+        Some(spec)
+    }
+
+    /// Returns the target and the options of a tmpfs mount.
+    /// Returns the name of the volume, if it is a [`Volume`] that testcontainers manages.
+    pub(crate) fn managed_volume_name(&self) -> Option<&str> {
+        match &self.kind {
+            MountKind::Volume {
+                name,
+                managed: Some(_),
+            } => Some(name),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn tmpfs_spec(&self) -> Option<(String, String)> {
+        let MountKind::Tmpfs { size } = &self.kind else {
+            return None;
+        };
+
+        let mut options = Vec::new();
+        if self.read_only {
+            options.push("ro".to_string());
+        }
+        if let Some(size) = size {
+            options.push(format!("size={size}"));
+        }
+
+        Some((self.container_path.clone(), options.join(",")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bind_mounts_are_converted_into_bind_specs() {
+        assert_eq!(
+            Mount::bind("/data", "/var/data").bind_spec().as_deref(),
+            Some("/data:/var/data")
+        );
+        assert_eq!(
+            Mount::bind_ro("/data", "/var/data")
+                .with_selinux_relabel(SelinuxRelabel::Private)
+                .bind_spec()
+                .as_deref(),
+            Some("/data:/var/data:ro,Z")
+        );
+        assert_eq!(
+            Mount::volume("data", "/var/data")
+                .with_selinux_relabel(SelinuxRelabel::Shared)
+                .bind_spec()
+                .as_deref(),
+            Some("data:/var/data:z")
+        );
+        assert_eq!(Mount::tmpfs("/tmp").bind_spec(), None);
+    }
+
+    #[test]
+    fn tmpfs_mounts_are_converted_into_tmpfs_specs() {
+        assert_eq!(
+            Mount::tmpfs("/tmp")
+                .with_read_only(true)
+                .with_tmpfs_size(1024)
+                .tmpfs_spec(),
+            Some(("/tmp".to_string(), "ro,size=1024".to_string()))
+        );
+        assert_eq!(Mount::bind("/data", "/var/data").tmpfs_spec(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "host path 'data' of bind mount must be absolute")]
+    fn bind_mount_panics_on_relative_host_path() {
+        Mount::bind("data", "/var/data");
+    }
+
+    #[test]
+    #[should_panic(expected = "container path 'var/data' of mount must be absolute")]
+    fn mount_panics_on_relative_container_path() {
+        Mount::volume("data", "var/data");
+    }
+}
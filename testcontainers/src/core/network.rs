@@ -1,37 +1,120 @@
-use crate::core::{client::Client, env, macros};
+use crate::core::{client::Client, env, runtime};
 use std::{
     collections::HashMap,
     fmt,
     sync::{Arc, OnceLock, Weak},
 };
-use tokio::{runtime::RuntimeFlavor, sync::Mutex};
+use tokio::sync::Mutex;
+
+/// A user-defined bridge network for the containers of a test.
+///
+/// The network is created when the first container that joins it via
+/// [`RunnableImage::with_network`] is started, and removed once the last of them is dropped,
+/// so a test can have a network of its own without leaking it.
+///
+/// ```rust,no_run
+/// use testcontainers::{
+///     core::{Network, RunnableImage},
+///     runners::AsyncRunner,
+///     GenericImage,
+/// };
+///
+/// # async fn example() {
+/// let network = Network::new();
+///
+/// let _redis = RunnableImage::from(GenericImage::new("redis", "7.2.4"))
+///     .with_network(&network)
+///     .with_network_alias("redis")
+///     .start()
+///     .await
+///     .unwrap();
+/// let _app = RunnableImage::from(GenericImage::new("my-app", "latest"))
+///     .with_network(&network)
+///     .with_env_var(("REDIS_URL", "redis://redis:6379"))
+///     .start()
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+///
+/// [`RunnableImage::with_network`]: crate::core::RunnableImage::with_network
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Network {
+    name: String,
+}
+
+impl Network {
+    /// Creates a handle to a new network with a unique name.
+    pub fn new() -> Self {
+        Self::named(format!(
+            "testcontainers-network-{}",
+            ulid::Ulid::new().to_string().to_lowercase()
+        ))
+    }
+
+    /// Creates a handle to the network with the name.
+    ///
+    /// A network with the name that already exists when the first container joins it, e.g. one
+    /// created by another tool, is used as it is and not removed.
+    pub fn named(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+
+    /// Returns the name of the network.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
 
-pub(crate) static CREATED_NETWORKS: OnceLock<Mutex<HashMap<String, Weak<Network>>>> =
+impl Default for Network {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Network> for String {
+    fn from(network: Network) -> Self {
+        network.name
+    }
+}
+
+impl From<&Network> for String {
+    fn from(network: &Network) -> Self {
+        network.name.clone()
+    }
+}
+
+pub(crate) static CREATED_NETWORKS: OnceLock<Mutex<HashMap<String, Weak<CreatedNetwork>>>> =
     OnceLock::new();
 
-fn created_networks() -> &'static Mutex<HashMap<String, Weak<Network>>> {
+fn created_networks() -> &'static Mutex<HashMap<String, Weak<CreatedNetwork>>> {
     CREATED_NETWORKS.get_or_init(|| Mutex::new(HashMap::new()))
 }
 
-pub(crate) struct Network {
+/// A network that was created for containers, which is removed once the last of them is dropped.
+pub(crate) struct CreatedNetwork {
     name: String,
     id: Option<String>,
     client: Arc<Client>,
 }
 
-impl Network {
-    pub(crate) async fn new(name: impl Into<String>, client: Arc<Client>) -> Option<Arc<Self>> {
+impl CreatedNetwork {
+    pub(crate) async fn new(
+        name: impl Into<String>,
+        subnets: &[String],
+        client: Arc<Client>,
+    ) -> Result<Option<Arc<Self>>, bollard::errors::Error> {
         let name = name.into();
         let mut guard = created_networks().lock().await;
         let network = if let Some(network) = guard.get(&name).and_then(Weak::upgrade) {
             network
         } else {
-            if client.network_exists(&name).await {
+            if client.network_exists(&name).await? {
                 // Networks already exists and created outside the testcontainers
-                return None;
+                return Ok(None);
             }
 
-            let id = client.create_network(&name).await;
+            let id = client.create_network(&name, subnets).await?;
 
             let created = Arc::new(Self {
                 name: name.clone(),
@@ -44,11 +127,11 @@ impl Network {
             created
         };
 
-        Some(network)
+        Ok(Some(network))
     }
 }
 
-impl Drop for Network {
+impl Drop for CreatedNetwork {
     fn drop(&mut self) {
         if self.client.config.command() == env::Command::Remove {
             let client = self.client.clone();
@@ -74,14 +157,14 @@ impl Drop for Network {
                 }
             };
 
-            macros::block_on!(drop_task, "failed to remove network on drop");
+            runtime::block_on(drop_task);
         }
     }
 }
 
-impl fmt::Debug for Network {
+impl fmt::Debug for CreatedNetwork {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Network")
+        f.debug_struct("CreatedNetwork")
             .field("id", &self.id)
             .field("name", &self.name)
             .finish()
@@ -0,0 +1,409 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
+
+use bollard::{
+    container::Config,
+    models::{HostConfig, HostConfigCgroupnsModeEnum},
+};
+
+use crate::{
+    core::TestcontainersError, runners::async_runner::create_request, Image, RunnableImage,
+};
+
+/// The request a container of an image would be created with, and the equivalent `docker run`
+/// command, without touching Docker.
+///
+/// Useful to debug a configuration that was built through many layers of builders. A plan is
+/// returned by [`RunnableImage::plan`], and by every start once the `TESTCONTAINERS_DRY_RUN`
+/// environment variable is set (`1` or `true`), which fails with
+/// [`TestcontainersError::DryRun`] instead of starting the container.
+///
+/// ```rust
+/// use testcontainers::{core::RunnableImage, GenericImage};
+///
+/// let plan = RunnableImage::from(GenericImage::new("redis", "7.2.4"))
+///     .with_env_var(("REDIS_ARGS", "--save 60 1"))
+///     .plan()
+///     .unwrap();
+/// assert!(plan
+///     .docker_run_command()
+///     .contains("--env 'REDIS_ARGS=--save 60 1'"));
+/// ```
+///
+/// [`TestcontainersError::DryRun`]: crate::core::TestcontainersError::DryRun
+#[derive(Debug, Clone)]
+pub struct ContainerPlan {
+    name: Option<String>,
+    platform: Option<String>,
+    config: Config<String>,
+}
+
+impl ContainerPlan {
+    pub(crate) fn new<I: Image>(image: &RunnableImage<I>) -> Result<Self, TestcontainersError> {
+        let (options, config) = create_request(image)?;
+        let (name, platform) = match options {
+            Some(options) => (
+                Some(options.name).filter(|name| !name.is_empty()),
+                options.platform,
+            ),
+            None => (None, None),
+        };
+
+        Ok(Self {
+            name,
+            platform,
+            config,
+        })
+    }
+
+    /// Returns the name of the container, if it is set.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the platform of the container, if it is set.
+    pub fn platform(&self) -> Option<&str> {
+        self.platform.as_deref()
+    }
+
+    /// Returns the body of the request that creates the container, as JSON.
+    pub fn create_request(&self) -> String {
+        serde_json::to_string_pretty(&self.config).expect("the create request is valid JSON")
+    }
+
+    /// Returns the arguments of the equivalent `docker run` command, without `docker run`.
+    pub fn docker_run_args(&self) -> Vec<String> {
+        let config = &self.config;
+        let default_host_config = HostConfig::default();
+        let host_config = config.host_config.as_ref().unwrap_or(&default_host_config);
+        let mut args = Args::default();
+
+        args.opt("--name", self.name.as_deref());
+        args.opt("--platform", self.platform.as_deref());
+        args.flag("--privileged", host_config.privileged);
+        args.flag("--rm", host_config.auto_remove);
+        args.flag("--init", host_config.init);
+        args.flag("--read-only", host_config.readonly_rootfs);
+
+        // environment and labels
+        for env in config.env.iter().flatten() {
+            args.push("--env", env);
+        }
+        for (key, value) in sorted(config.labels.as_ref()) {
+            args.push("--label", format!("{key}={value}"));
+        }
+
+        // user and working directory
+        args.opt("--user", config.user.as_deref());
+        args.opt("--workdir", config.working_dir.as_deref());
+
+        // networking
+        args.opt("--network", host_config.network_mode.as_deref());
+        if let Some(networking_config) = &config.networking_config {
+            for endpoint in networking_config.endpoints_config.values() {
+                for alias in endpoint.aliases.iter().flatten() {
+                    args.push("--network-alias", alias);
+                }
+                if let Some(ipam) = &endpoint.ipam_config {
+                    args.opt("--ip", ipam.ipv4_address.as_deref());
+                    args.opt("--ip6", ipam.ipv6_address.as_deref());
+                }
+            }
+        }
+        for host in host_config.extra_hosts.iter().flatten() {
+            args.push("--add-host", host);
+        }
+        for port in sorted(config.exposed_ports.as_ref()).into_keys() {
+            args.push("--expose", port);
+        }
+        args.flag("--publish-all", host_config.publish_all_ports);
+        for (port, bindings) in sorted(host_config.port_bindings.as_ref()) {
+            for binding in bindings.iter().flatten() {
+                let publish = match binding.host_port.as_deref() {
+                    Some(host_port) if !host_port.is_empty() => format!("{host_port}:{port}"),
+                    _ => port.clone(),
+                };
+                args.push("--publish", publish);
+            }
+        }
+
+        // storage
+        for bind in host_config.binds.iter().flatten() {
+            args.push("--volume", bind);
+        }
+        for (path, options) in sorted(host_config.tmpfs.as_ref()) {
+            if options.is_empty() {
+                args.push("--tmpfs", path);
+            } else {
+                args.push("--tmpfs", format!("{path}:{options}"));
+            }
+        }
+        args.opt(
+            "--shm-size",
+            host_config.shm_size.map(|bytes| bytes.to_string()),
+        );
+
+        // resources
+        args.opt(
+            "--memory",
+            host_config.memory.map(|bytes| bytes.to_string()),
+        );
+        args.opt(
+            "--memory-swap",
+            host_config.memory_swap.map(|bytes| bytes.to_string()),
+        );
+        args.opt(
+            "--cpus",
+            host_config
+                .nano_cpus
+                .map(|nanos| (nanos as f64 / 1_000_000_000.0).to_string()),
+        );
+        args.opt(
+            "--cpu-shares",
+            host_config.cpu_shares.map(|shares| shares.to_string()),
+        );
+        for ulimit in host_config.ulimits.iter().flatten() {
+            if let (Some(name), Some(soft), Some(hard)) = (&ulimit.name, ulimit.soft, ulimit.hard) {
+                args.push("--ulimit", format!("{name}={soft}:{hard}"));
+            }
+        }
+        for device in host_config.devices.iter().flatten() {
+            let mapping = [
+                device.path_on_host.as_deref(),
+                device.path_in_container.as_deref(),
+                device.cgroup_permissions.as_deref(),
+            ];
+            args.push(
+                "--device",
+                mapping.into_iter().flatten().collect::<Vec<_>>().join(":"),
+            );
+        }
+        for request in host_config.device_requests.iter().flatten() {
+            let gpus = match (&request.device_ids, request.count) {
+                (Some(ids), _) => format!("\"device={}\"", ids.join(",")),
+                (None, Some(-1)) => "all".to_string(),
+                (None, Some(count)) => count.to_string(),
+                (None, None) => continue,
+            };
+            args.push("--gpus", gpus);
+        }
+        for (key, value) in sorted(host_config.sysctls.as_ref()) {
+            args.push("--sysctl", format!("{key}={value}"));
+        }
+        args.flag("--oom-kill-disable", host_config.oom_kill_disable);
+        args.opt(
+            "--oom-score-adj",
+            host_config.oom_score_adj.map(|adj| adj.to_string()),
+        );
+
+        // security and namespaces
+        for capability in host_config.cap_add.iter().flatten() {
+            args.push("--cap-add", capability);
+        }
+        for capability in host_config.cap_drop.iter().flatten() {
+            args.push("--cap-drop", capability);
+        }
+        for option in host_config.security_opt.iter().flatten() {
+            args.push("--security-opt", option);
+        }
+        for group in host_config.group_add.iter().flatten() {
+            args.push("--group-add", group);
+        }
+        args.opt("--pid", host_config.pid_mode.as_deref());
+        args.opt("--ipc", host_config.ipc_mode.as_deref());
+        args.opt("--userns", host_config.userns_mode.as_deref());
+        args.opt("--cgroup-parent", host_config.cgroup_parent.as_deref());
+        args.opt(
+            "--cgroupns",
+            host_config.cgroupns_mode.and_then(|mode| match mode {
+                HostConfigCgroupnsModeEnum::PRIVATE => Some("private"),
+                HostConfigCgroupnsModeEnum::HOST => Some("host"),
+                HostConfigCgroupnsModeEnum::EMPTY => None,
+            }),
+        );
+
+        // lifecycle
+        args.opt("--stop-signal", config.stop_signal.as_deref());
+        args.opt(
+            "--stop-timeout",
+            config.stop_timeout.map(|seconds| seconds.to_string()),
+        );
+        if let Some(healthcheck) = &config.healthcheck {
+            match healthcheck.test.as_deref() {
+                Some([kind, ..]) if kind == "NONE" => args.flag("--no-healthcheck", Some(true)),
+                Some([kind, cmd]) if kind == "CMD-SHELL" => args.push("--health-cmd", cmd),
+                Some([kind, cmd @ ..]) if kind == "CMD" => {
+                    let cmd: Vec<_> = cmd.iter().map(|arg| quote(arg)).collect();
+                    args.push("--health-cmd", cmd.join(" "));
+                }
+                _ => {}
+            }
+            args.opt("--health-interval", duration(healthcheck.interval));
+            args.opt("--health-timeout", duration(healthcheck.timeout));
+            args.opt(
+                "--health-retries",
+                healthcheck.retries.map(|retries| retries.to_string()),
+            );
+            args.opt("--health-start-period", duration(healthcheck.start_period));
+            args.opt(
+                "--health-start-interval",
+                duration(healthcheck.start_interval),
+            );
+        }
+        if let Some(log_config) = &host_config.log_config {
+            args.opt("--log-driver", log_config.typ.as_deref());
+            for (key, value) in sorted(log_config.config.as_ref()) {
+                args.push("--log-opt", format!("{key}={value}"));
+            }
+        }
+
+        // `--entrypoint` takes a single executable, its arguments precede the command
+        let mut cmd = Vec::new();
+        if let Some(entrypoint) = &config.entrypoint {
+            let mut entrypoint = entrypoint.iter();
+            args.push("--entrypoint", entrypoint.next().map_or("", String::as_str));
+            cmd.extend(entrypoint.cloned());
+        }
+        cmd.extend(config.cmd.iter().flatten().cloned());
+
+        args.args.extend(config.image.clone());
+        args.args.extend(cmd);
+        args.args
+    }
+
+    /// Returns the equivalent `docker run` command, quoted for a POSIX shell.
+    pub fn docker_run_command(&self) -> String {
+        let args: Vec<_> = self
+            .docker_run_args()
+            .iter()
+            .map(|arg| quote(arg))
+            .collect();
+        format!("docker run --detach {}", args.join(" "))
+    }
+}
+
+impl fmt::Display for ContainerPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let query: Vec<_> = [("name", &self.name), ("platform", &self.platform)]
+            .into_iter()
+            .filter_map(|(key, value)| value.as_ref().map(|value| format!("{key}={value}")))
+            .collect();
+        if query.is_empty() {
+            writeln!(f, "POST /containers/create")?;
+        } else {
+            writeln!(f, "POST /containers/create?{}", query.join("&"))?;
+        }
+        writeln!(f, "{}", self.create_request())?;
+        write!(f, "{}", self.docker_run_command())
+    }
+}
+
+/// The arguments of a `docker run` command.
+#[derive(Default)]
+struct Args {
+    args: Vec<String>,
+}
+
+impl Args {
+    fn push(&mut self, option: &str, value: impl Into<String>) {
+        self.args.push(option.to_string());
+        self.args.push(value.into());
+    }
+
+    fn opt(&mut self, option: &str, value: Option<impl Into<String>>) {
+        if let Some(value) = value {
+            self.push(option, value);
+        }
+    }
+
+    fn flag(&mut self, option: &str, value: Option<bool>) {
+        if value == Some(true) {
+            self.args.push(option.to_string());
+        }
+    }
+}
+
+/// Returns the entries of a map of the request ordered by key, for a stable command line.
+fn sorted<K: Ord + Clone, V: Clone>(map: Option<&HashMap<K, V>>) -> BTreeMap<K, V> {
+    map.into_iter()
+        .flatten()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+/// Formats a duration of a healthcheck, given in nanoseconds where `0` means inherited.
+fn duration(nanos: Option<i64>) -> Option<String> {
+    match nanos? {
+        0 => None,
+        nanos if nanos % 1_000_000_000 == 0 => Some(format!("{}s", nanos / 1_000_000_000)),
+        nanos => Some(format!("{}ms", nanos / 1_000_000)),
+    }
+}
+
+/// Quotes the argument for a POSIX shell, unless it only consists of safe characters.
+fn quote(arg: &str) -> String {
+    let is_safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=,@%+".contains(c));
+    if is_safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core::Healthcheck, GenericImage};
+
+    #[test]
+    fn docker_run_args_are_equivalent_to_request() {
+        let image =
+            GenericImage::new("postgres", "16-alpine").with_entrypoint("docker-entrypoint.sh");
+        let plan = RunnableImage::from(image)
+            .with_container_name("db")
+            .with_env_var(("POSTGRES_PASSWORD", "secret"))
+            .with_mapped_port((5433, 5432))
+            .with_network("it-net")
+            .with_network_alias("postgres")
+            .with_healthcheck(Healthcheck::cmd_shell("pg_isready"))
+            .with_cmd(["postgres", "-c", "fsync=off"])
+            .plan()
+            .unwrap();
+
+        let args = plan.docker_run_args();
+        let has = |option: &str, value: &str| {
+            args.windows(2)
+                .any(|pair| pair[0] == option && pair[1] == value)
+        };
+        assert_eq!(plan.name(), Some("db"));
+        assert!(has("--name", "db"));
+        assert!(has("--env", "POSTGRES_PASSWORD=secret"));
+        assert!(has("--publish", "5433:5432/tcp"));
+        assert!(has("--network", "it-net"));
+        assert!(has("--network-alias", "postgres"));
+        assert!(has("--health-cmd", "pg_isready"));
+        assert!(has("--entrypoint", "docker-entrypoint.sh"));
+        assert!(args
+            .iter()
+            .any(|arg| arg.starts_with("org.testcontainers.session-id=")));
+        assert!(args.ends_with(&[
+            "postgres:16-alpine".to_string(),
+            "postgres".to_string(),
+            "-c".to_string(),
+            "fsync=off".to_string(),
+        ]));
+    }
+
+    #[test]
+    fn args_are_quoted_for_shell() {
+        assert_eq!(quote("redis:7.2"), "redis:7.2");
+        assert_eq!(quote("a b"), "'a b'");
+        assert_eq!(quote("it's"), r"'it'\''s'");
+        assert_eq!(quote(""), "''");
+    }
+}
@@ -1,11 +1,18 @@
 use bollard_stubs::models::{PortBinding, PortMap};
-use std::{collections::HashMap, net::IpAddr};
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::IpAddr,
+};
 
 /// The exposed ports of a running container.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct Ports {
-    ipv4_mapping: HashMap<u16, u16>,
-    ipv6_mapping: HashMap<u16, u16>,
+    ipv4_mapping: BTreeMap<u16, u16>,
+    ipv6_mapping: BTreeMap<u16, u16>,
+    udp_ipv4_mapping: BTreeMap<u16, u16>,
+    udp_ipv6_mapping: BTreeMap<u16, u16>,
+    // the container uses the network stack of the host, so ports are not mapped
+    host_network: bool,
 }
 
 impl Ports {
@@ -31,28 +38,107 @@ impl Ports {
         Self::from(port_binding)
     }
 
+    /// The ports of a container that uses the network stack of the host, where every internal
+    /// port is the same as the host port.
+    pub(crate) fn host_network() -> Self {
+        Self {
+            host_network: true,
+            ..Default::default()
+        }
+    }
+
     /// Returns the host port for the given internal port, on the host's IPv4 interfaces.
+    ///
+    /// The TCP port is returned if the port is mapped for TCP and UDP, the UDP port if it is
+    /// only mapped for UDP.
     pub fn map_to_host_port_ipv4(&self, internal_port: u16) -> Option<u16> {
-        self.ipv4_mapping.get(&internal_port).cloned()
+        self.map(&self.ipv4_mapping, internal_port)
     }
 
-    /// Returns the host port for the given internal port, on the host's IPv6 interfaces.
+    /// Returns the host port for the given internal port, on the host's IPv6 interfaces, see
+    /// [`Ports::map_to_host_port_ipv4`].
     pub fn map_to_host_port_ipv6(&self, internal_port: u16) -> Option<u16> {
-        self.ipv6_mapping.get(&internal_port).cloned()
+        self.map(&self.ipv6_mapping, internal_port)
+    }
+
+    /// Returns the host port for the given internal UDP port, on the host's IPv4 interfaces.
+    pub fn map_to_host_udp_port_ipv4(&self, internal_port: u16) -> Option<u16> {
+        self.map(&self.udp_ipv4_mapping, internal_port)
+    }
+
+    /// Returns the host port for the given internal UDP port, on the host's IPv6 interfaces.
+    pub fn map_to_host_udp_port_ipv6(&self, internal_port: u16) -> Option<u16> {
+        self.map(&self.udp_ipv6_mapping, internal_port)
+    }
+
+    /// Returns the internal ports with the host ports they are mapped to on the host's IPv4
+    /// interfaces, ordered by internal port.
+    ///
+    /// Ports are not mapped for containers that use the network stack of the host, where every
+    /// internal port is the same as the host port.
+    pub fn ipv4_mappings(&self) -> impl Iterator<Item = (u16, u16)> + '_ {
+        self.ipv4_mapping
+            .iter()
+            .map(|(&internal, &host)| (internal, host))
+    }
+
+    /// Returns the internal ports with the host ports they are mapped to on the host's IPv6
+    /// interfaces, ordered by internal port.
+    pub fn ipv6_mappings(&self) -> impl Iterator<Item = (u16, u16)> + '_ {
+        self.ipv6_mapping
+            .iter()
+            .map(|(&internal, &host)| (internal, host))
+    }
+
+    /// Returns the internal UDP ports with the host ports they are mapped to on the host's
+    /// IPv4 interfaces, ordered by internal port.
+    pub fn udp_ipv4_mappings(&self) -> impl Iterator<Item = (u16, u16)> + '_ {
+        self.udp_ipv4_mapping
+            .iter()
+            .map(|(&internal, &host)| (internal, host))
+    }
+
+    /// Returns the internal UDP ports with the host ports they are mapped to on the host's
+    /// IPv6 interfaces, ordered by internal port.
+    pub fn udp_ipv6_mappings(&self) -> impl Iterator<Item = (u16, u16)> + '_ {
+        self.udp_ipv6_mapping
+            .iter()
+            .map(|(&internal, &host)| (internal, host))
+    }
+
+    fn map(&self, mapping: &BTreeMap<u16, u16>, internal_port: u16) -> Option<u16> {
+        if self.host_network {
+            return Some(internal_port);
+        }
+
+        mapping.get(&internal_port).cloned()
+    }
+
+    /// Returns the internal ports that are mapped on the host's IPv4 interfaces.
+    #[cfg_attr(
+        not(any(feature = "http_wait", feature = "grpc_wait")),
+        allow(dead_code)
+    )]
+    pub(crate) fn ipv4_internal_ports(&self) -> impl Iterator<Item = u16> + '_ {
+        self.ipv4_mapping.keys().copied()
     }
 }
 
 impl From<PortMap> for Ports {
     fn from(ports: PortMap) -> Self {
-        let mut ipv4_mapping = HashMap::new();
-        let mut ipv6_mapping = HashMap::new();
+        let mut ipv4_mapping = BTreeMap::new();
+        let mut ipv6_mapping = BTreeMap::new();
+        let mut udp_ipv4_mapping = BTreeMap::new();
+        let mut udp_ipv6_mapping = BTreeMap::new();
         for (internal, external) in ports {
             // internal is of the form '8332/tcp', split off the protocol ...
-            let internal_port = if let Some(internal) = internal.split('/').next() {
+            let mut parts = internal.split('/');
+            let internal_port = if let Some(internal) = parts.next() {
                 parse_port(internal)
             } else {
                 continue;
             };
+            let is_udp = parts.next() == Some("udp");
 
             // get the `HostPort` of each external port binding
             for binding in external.into_iter().flatten() {
@@ -64,18 +150,26 @@ impl From<PortMap> for Ports {
                         Some(Ok(IpAddr::V4(_))) => {
                             log::debug!(
                                 "Registering IPv4 port mapping: {} -> {}",
-                                internal_port,
+                                internal,
                                 external_port
                             );
-                            &mut ipv4_mapping
+                            if is_udp {
+                                &mut udp_ipv4_mapping
+                            } else {
+                                &mut ipv4_mapping
+                            }
                         }
                         Some(Ok(IpAddr::V6(_))) => {
                             log::debug!(
                                 "Registering IPv6 port mapping: {} -> {}",
-                                internal_port,
+                                internal,
                                 external_port
                             );
-                            &mut ipv6_mapping
+                            if is_udp {
+                                &mut udp_ipv6_mapping
+                            } else {
+                                &mut ipv6_mapping
+                            }
                         }
                         Some(Err(_)) | None => continue,
                     };
@@ -87,9 +181,21 @@ impl From<PortMap> for Ports {
             }
         }
 
+        // UDP ports used to be mapped like TCP ports, so they are still found by
+        // `map_to_host_port_ipv4` and friends, unless the same port is also mapped for TCP
+        for (internal, external) in &udp_ipv4_mapping {
+            ipv4_mapping.entry(*internal).or_insert(*external);
+        }
+        for (internal, external) in &udp_ipv6_mapping {
+            ipv6_mapping.entry(*internal).or_insert(*external);
+        }
+
         Self {
             ipv4_mapping,
             ipv6_mapping,
+            udp_ipv4_mapping,
+            udp_ipv6_mapping,
+            host_network: false,
         }
     }
 }
@@ -352,4 +458,95 @@ mod tests {
 
         assert_eq!(parsed_ports, expected_ports)
     }
+
+    #[test]
+    fn mappings_are_ordered_by_internal_port() {
+        let binding = |host_ip: &str, host_port: &str| PortBinding {
+            host_ip: Some(host_ip.to_string()),
+            host_port: Some(host_port.to_string()),
+        };
+        let port_map = PortMap::from([
+            (
+                "18332/tcp".to_string(),
+                Some(vec![binding("0.0.0.0", "33076")]),
+            ),
+            (
+                "8332/tcp".to_string(),
+                Some(vec![binding("0.0.0.0", "33078"), binding("::", "49718")]),
+            ),
+            (
+                "53/udp".to_string(),
+                Some(vec![binding("0.0.0.0", "32769"), binding("::", "32770")]),
+            ),
+        ]);
+
+        let ports = Ports::from(port_map);
+
+        assert_eq!(
+            ports.ipv4_mappings().collect::<Vec<_>>(),
+            vec![(53, 32769), (8332, 33078), (18332, 33076)]
+        );
+        assert_eq!(
+            ports.ipv6_mappings().collect::<Vec<_>>(),
+            vec![(53, 32770), (8332, 49718)]
+        );
+        assert_eq!(
+            ports.udp_ipv4_mappings().collect::<Vec<_>>(),
+            vec![(53, 32769)]
+        );
+        assert_eq!(
+            ports.udp_ipv6_mappings().collect::<Vec<_>>(),
+            vec![(53, 32770)]
+        );
+    }
+
+    #[test]
+    fn udp_ports_are_mapped_separately() {
+        let binding = |host_ip: &str, host_port: &str| PortBinding {
+            host_ip: Some(host_ip.to_string()),
+            host_port: Some(host_port.to_string()),
+        };
+        let port_map = PortMap::from([
+            (
+                "53/tcp".to_string(),
+                Some(vec![binding("0.0.0.0", "32768")]),
+            ),
+            (
+                "53/udp".to_string(),
+                Some(vec![binding("0.0.0.0", "32769"), binding("::", "32770")]),
+            ),
+        ]);
+
+        let ports = Ports::from(port_map);
+
+        assert_eq!(ports.map_to_host_port_ipv4(53), Some(32768));
+        assert_eq!(ports.map_to_host_udp_port_ipv4(53), Some(32769));
+        assert_eq!(ports.map_to_host_udp_port_ipv6(53), Some(32770));
+        // the TCP port takes precedence, the UDP port is the fallback
+        assert_eq!(ports.map_to_host_port_ipv6(53), Some(32770));
+    }
+
+    #[test]
+    fn udp_only_ports_are_found_as_ports() {
+        let port_map = PortMap::from([(
+            "514/udp".to_string(),
+            Some(vec![PortBinding {
+                host_ip: Some("0.0.0.0".to_string()),
+                host_port: Some("32771".to_string()),
+            }]),
+        )]);
+
+        let ports = Ports::from(port_map);
+
+        assert_eq!(ports.map_to_host_port_ipv4(514), Some(32771));
+        assert_eq!(ports.map_to_host_udp_port_ipv4(514), Some(32771));
+    }
+
+    #[test]
+    fn ports_are_not_mapped_with_host_network() {
+        let ports = Ports::host_network();
+
+        assert_eq!(ports.map_to_host_port_ipv4(8080), Some(8080));
+        assert_eq!(ports.map_to_host_udp_port_ipv6(53), Some(53));
+    }
 }
@@ -0,0 +1,47 @@
+use bollard_stubs::models::ContainerTopResponse;
+
+/// The processes running in a container, as reported by `docker top`.
+///
+/// The columns depend on the arguments passed to `ps`, by default `-ef` with the columns
+/// `UID`, `PID`, `PPID`, `C`, `STIME`, `TTY`, `TIME` and `CMD`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProcessList {
+    /// The titles of the columns
+    pub titles: Vec<String>,
+    /// The values of the columns of each process
+    pub processes: Vec<Vec<String>>,
+}
+
+impl ProcessList {
+    /// Returns the number of processes.
+    pub fn len(&self) -> usize {
+        self.processes.len()
+    }
+
+    /// Returns whether no process is running.
+    pub fn is_empty(&self) -> bool {
+        self.processes.is_empty()
+    }
+
+    /// Returns the values of the column with the given title of each process, e.g. `CMD`, or
+    /// `None` if there is no such column.
+    pub fn column(&self, title: &str) -> Option<Vec<&str>> {
+        let index = self.titles.iter().position(|t| t == title)?;
+
+        Some(
+            self.processes
+                .iter()
+                .map(|process| process.get(index).map_or("", String::as_str))
+                .collect(),
+        )
+    }
+}
+
+impl From<ContainerTopResponse> for ProcessList {
+    fn from(response: ContainerTopResponse) -> Self {
+        Self {
+            titles: response.titles.unwrap_or_default(),
+            processes: response.processes.unwrap_or_default(),
+        }
+    }
+}
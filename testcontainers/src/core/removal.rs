@@ -0,0 +1,65 @@
+use crate::core::client::Client;
+
+/// When a container is removed once it is dropped.
+///
+/// Configured per container with [`RunnableImage::with_remove_on_drop`], or for all containers
+/// with the `TESTCONTAINERS_KEEP_ON_FAILURE` environment variable (`1` or `true`), which keeps
+/// the containers of failed tests.
+///
+/// Containers that may be kept are not removed by the resource reaper of the `reaper` feature
+/// or the watchdog of the `watchdog` feature. Setting `TESTCONTAINERS_KEEP_ON_FAILURE` disables
+/// the resource reaper altogether.
+///
+/// [`RunnableImage::with_remove_on_drop`]: crate::core::RunnableImage::with_remove_on_drop
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum RemovePolicy {
+    /// The container is always removed.
+    #[default]
+    Always,
+    /// The container is removed unless it is dropped while the thread is panicking, e.g.
+    /// because an assertion of the test failed, or it fails to start. A kept container is left
+    /// running, and its id and ports are logged as a warning.
+    OnSuccess,
+    /// The container is never removed.
+    Never,
+}
+
+impl RemovePolicy {
+    pub(crate) fn removes(self, failed: bool) -> bool {
+        match self {
+            RemovePolicy::Always => true,
+            RemovePolicy::OnSuccess => !failed,
+            RemovePolicy::Never => false,
+        }
+    }
+}
+
+/// Logs the id and the mapped ports of a container that was kept because its test failed, so
+/// that it can be inspected.
+pub(crate) async fn report_kept(client: &Client, id: &str) {
+    let host = client.docker_hostname();
+    let ports = client
+        .ports(id)
+        .await
+        .ipv4_mappings()
+        .map(|(internal, port)| format!("{internal} -> {host}:{port}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    log::warn!(
+        "Kept container {id} of the failed test for debugging (ports: [{ports}]), remove it with `docker rm -f {id}`"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_policy_applies_depending_on_failure() {
+        assert!(RemovePolicy::Always.removes(true));
+        assert!(RemovePolicy::OnSuccess.removes(false));
+        assert!(!RemovePolicy::OnSuccess.removes(true));
+        assert!(!RemovePolicy::Never.removes(false));
+    }
+}
@@ -0,0 +1,98 @@
+use bollard::container::UpdateContainerOptions;
+
+/// New resource limits of a running container, see [`ContainerAsync::update_limits`].
+///
+/// Limits that are not set are kept as they are.
+///
+/// ```rust
+/// use testcontainers::core::ResourceLimits;
+///
+/// let limits = ResourceLimits::new()
+///     .with_memory_limit(64 * 1024 * 1024)
+///     .with_memory_swap(64 * 1024 * 1024)
+///     .with_cpus(0.5);
+/// ```
+///
+/// [`ContainerAsync::update_limits`]: crate::ContainerAsync::update_limits
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResourceLimits {
+    memory_limit: Option<u64>,
+    memory_swap: Option<i64>,
+    cpus: Option<f64>,
+    cpu_shares: Option<u64>,
+    pids_limit: Option<i64>,
+}
+
+impl ResourceLimits {
+    /// Creates an update that keeps all limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the memory the container can use, in bytes.
+    ///
+    /// If the container has a swap limit, it has to be updated as well when the memory limit
+    /// is raised above it.
+    pub fn with_memory_limit(mut self, bytes: u64) -> Self {
+        self.memory_limit = Some(bytes);
+        self
+    }
+
+    /// Limits the total of memory and swap the container can use, in bytes.
+    ///
+    /// Has to be at least the memory limit, or `-1` to allow unlimited swap.
+    pub fn with_memory_swap(mut self, bytes: i64) -> Self {
+        self.memory_swap = Some(bytes);
+        self
+    }
+
+    /// Limits the number of CPUs the container can use, e.g. `1.5`.
+    pub fn with_cpus(mut self, cpus: f64) -> Self {
+        self.cpus = Some(cpus);
+        self
+    }
+
+    /// Sets the CPU weight of the container relative to other containers (default `1024`).
+    pub fn with_cpu_shares(mut self, shares: u64) -> Self {
+        self.cpu_shares = Some(shares);
+        self
+    }
+
+    /// Limits the number of processes and threads of the container. `-1` means unlimited.
+    pub fn with_pids_limit(mut self, pids_limit: i64) -> Self {
+        self.pids_limit = Some(pids_limit);
+        self
+    }
+}
+
+impl From<&ResourceLimits> for UpdateContainerOptions<String> {
+    fn from(limits: &ResourceLimits) -> Self {
+        UpdateContainerOptions {
+            memory: limits.memory_limit.map(|bytes| bytes as i64),
+            memory_swap: limits.memory_swap,
+            nano_cp_us: limits.cpus.map(|cpus| (cpus * 1_000_000_000.0) as i64),
+            cpu_shares: limits.cpu_shares.map(|shares| shares as isize),
+            pids_limit: limits.pids_limit,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_into_update_options() {
+        let options = UpdateContainerOptions::from(
+            &ResourceLimits::new()
+                .with_memory_limit(64 * 1024 * 1024)
+                .with_cpus(0.5),
+        );
+
+        assert_eq!(options.memory, Some(64 * 1024 * 1024));
+        assert_eq!(options.nano_cp_us, Some(500_000_000));
+        assert_eq!(options.memory_swap, None);
+        assert_eq!(options.pids_limit, None);
+    }
+}
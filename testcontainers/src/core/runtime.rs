@@ -0,0 +1,164 @@
+use std::{
+    future::Future,
+    panic::resume_unwind,
+    pin::Pin,
+    sync::OnceLock,
+    task::{Context, Poll},
+};
+
+use futures::Stream;
+use tokio::runtime::{Handle, Runtime, RuntimeFlavor};
+
+/// The runtime that drives all calls to the docker daemon.
+///
+/// The docker client needs a tokio reactor, and its connections are driven by the runtime they
+/// were opened on. Opening them on a runtime of its own makes the async API independent of the
+/// runtime of the caller: it works on tokio's multi-threaded and current-thread runtimes as well
+/// as on other executors like async-std, and blocking the caller, e.g. while a container is
+/// dropped, can't stall the connections it is waiting for.
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+fn runtime() -> &'static Runtime {
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .thread_name("testcontainers")
+            .enable_all()
+            .build()
+            .expect("failed to build testcontainers runtime")
+    })
+}
+
+/// A future or stream that is polled within the context of the testcontainers runtime, see
+/// [`compat`].
+pub(crate) struct Compat<T> {
+    inner: Pin<Box<T>>,
+}
+
+/// Wraps a future or stream so that the tokio resources it uses, like connections to the docker
+/// daemon and timers, are driven by the testcontainers runtime, whichever executor polls it.
+pub(crate) fn compat<T>(inner: T) -> Compat<T> {
+    Compat {
+        inner: Box::pin(inner),
+    }
+}
+
+impl<T: Future> Future for Compat<T> {
+    type Output = T::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let _guard = runtime().enter();
+        self.inner.as_mut().poll(cx)
+    }
+}
+
+impl<T: Stream> Stream for Compat<T> {
+    type Item = T::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let _guard = runtime().enter();
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+/// Runs the future in the background: on the testcontainers runtime, or with the `async_std`
+/// feature as a task of async-std that is polled within the context of that runtime.
+pub(crate) fn spawn<F>(future: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    #[cfg(feature = "async_std")]
+    async_std::task::spawn(compat(future));
+    #[cfg(not(feature = "async_std"))]
+    runtime().spawn(future);
+}
+
+/// Runs the closure in the background on a thread where it may block, e.g. to call back into
+/// user code: of the blocking pool of the testcontainers runtime, or with the `async_std`
+/// feature of async-std.
+pub(crate) fn spawn_blocking<F>(f: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    #[cfg(feature = "async_std")]
+    async_std::task::spawn_blocking(f);
+    #[cfg(not(feature = "async_std"))]
+    runtime().spawn_blocking(f);
+}
+
+/// Blocks the current thread until the future, which is run on the testcontainers runtime,
+/// completes. Most useful for async calls in `Drop` implementations.
+///
+/// The caller may run on any runtime: a worker of a multi-threaded tokio runtime hands its
+/// other tasks off to the remaining workers while it is blocked.
+pub(crate) fn block_on<F>(future: F) -> F::Output
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let task = runtime().spawn(future);
+    let wait = move || match futures::executor::block_on(task) {
+        Ok(output) => output,
+        Err(err) if err.is_panic() => resume_unwind(err.into_panic()),
+        Err(err) => panic!("testcontainers runtime was shut down: {err}"),
+    };
+
+    match Handle::try_current().map(|handle| handle.runtime_flavor()) {
+        Ok(RuntimeFlavor::MultiThread) => tokio::task::block_in_place(wait),
+        _ => wait(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::StreamExt;
+
+    use super::*;
+
+    #[test]
+    fn compat_provides_a_tokio_context_to_other_executors() {
+        let slept = futures::executor::block_on(compat(async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            true
+        }));
+        assert!(slept);
+
+        let ticks = futures::executor::block_on(
+            compat(futures::stream::iter(0..3).then(|i| async move {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+                i
+            }))
+            .collect::<Vec<_>>(),
+        );
+        assert_eq!(ticks, vec![0, 1, 2]);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn block_on_does_not_stall_current_thread_runtime() {
+        let value = block_on(async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            42
+        });
+        assert_eq!(value, 42);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn block_on_works_on_multi_thread_runtime() {
+        assert_eq!(block_on(async { 42 }), 42);
+    }
+
+    #[test]
+    fn spawn_blocking_runs_in_the_background() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        spawn_blocking(move || tx.send(42).unwrap());
+        assert_eq!(rx.recv_timeout(Duration::from_secs(5)), Ok(42));
+    }
+
+    #[test]
+    #[should_panic(expected = "failed in the background")]
+    fn block_on_resumes_panics() {
+        block_on(async { panic!("failed in the background") });
+    }
+}
@@ -0,0 +1,66 @@
+use std::{sync::OnceLock, time::SystemTime};
+
+/// The label that marks all resources created by testcontainers.
+pub(crate) const TESTCONTAINERS_LABEL: &str = "org.testcontainers";
+
+/// The label with the id of the session that created a resource.
+pub(crate) const SESSION_ID_LABEL: &str = "org.testcontainers.session-id";
+
+/// The label that marks the resources the resource reaper removes once the session ends. It is
+/// left out for containers whose [`RemovePolicy`] may keep them.
+///
+/// [`RemovePolicy`]: crate::core::RemovePolicy
+pub(crate) const REAP_LABEL: &str = "org.testcontainers.reap";
+
+/// The label that marks the containers of a [`SharedContainer`], which the resource reaper
+/// removes once the session ends, as they are never dropped.
+///
+/// [`SharedContainer`]: crate::SharedContainer
+pub(crate) const SHARED_LABEL: &str = "org.testcontainers.shared";
+
+static SESSION_ID: OnceLock<String> = OnceLock::new();
+
+/// Returns the id of the current session, which is unique per process.
+///
+/// The id is a lowercase ULID, so it also records when the session started.
+pub(crate) fn session_id() -> &'static str {
+    SESSION_ID.get_or_init(|| ulid::Ulid::new().to_string().to_lowercase())
+}
+
+/// Returns when the session with the id started, or `None` if the id is not one of a session.
+pub(crate) fn started_at(session_id: &str) -> Option<SystemTime> {
+    ulid::Ulid::from_string(session_id)
+        .ok()
+        .map(|ulid| ulid.datetime())
+}
+
+/// Returns the labels that are applied to every container, network and volume created in the
+/// current session.
+pub(crate) fn labels() -> impl Iterator<Item = (String, String)> {
+    [
+        (TESTCONTAINERS_LABEL.to_string(), "true".to_string()),
+        (SESSION_ID_LABEL.to_string(), session_id().to_string()),
+        (REAP_LABEL.to_string(), "true".to_string()),
+    ]
+    .into_iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn session_id_records_when_the_session_started() {
+        let started = SystemTime::UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+        let id = ulid::Ulid::from_datetime(started)
+            .to_string()
+            .to_lowercase();
+        assert_eq!(started_at(&id), Some(started));
+        assert!(started_at(session_id()).is_some());
+
+        assert_eq!(started_at(""), None);
+        assert_eq!(started_at("not-a-session"), None);
+    }
+}
@@ -0,0 +1,40 @@
+use std::fmt;
+
+/// A signal that is sent to the main process of a container, see [`ContainerAsync::kill`].
+///
+/// [`ContainerAsync::kill`]: crate::ContainerAsync::kill
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Signal {
+    /// `SIGHUP`, which makes many services reload their configuration
+    Hup,
+    /// `SIGINT`
+    Int,
+    /// `SIGQUIT`
+    Quit,
+    /// `SIGKILL`, which terminates the process immediately
+    Kill,
+    /// `SIGUSR1`
+    Usr1,
+    /// `SIGUSR2`
+    Usr2,
+    /// `SIGTERM`
+    Term,
+    /// Any other signal, by name or number, e.g. `SIGWINCH` or `28`
+    Other(String),
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Signal::Hup => "SIGHUP",
+            Signal::Int => "SIGINT",
+            Signal::Quit => "SIGQUIT",
+            Signal::Kill => "SIGKILL",
+            Signal::Usr1 => "SIGUSR1",
+            Signal::Usr2 => "SIGUSR2",
+            Signal::Term => "SIGTERM",
+            Signal::Other(signal) => signal,
+        };
+        f.write_str(name)
+    }
+}
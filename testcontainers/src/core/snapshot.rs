@@ -0,0 +1,117 @@
+use std::{fmt, sync::Arc};
+
+use crate::{
+    core::{client::Client, runtime, TestcontainersError},
+    runners::AsyncRunner,
+    ContainerAsync, Image, RunnableImage,
+};
+
+/// An image committed from a container, from which fresh copies of the container are started,
+/// see [`ContainerAsync::snapshot`].
+///
+/// Seeding a database once and starting a copy of it for every test gives each test the same
+/// state in seconds, instead of running the migrations for every test. The copies are started
+/// with the configuration of the container, except its name, so e.g. fixed host ports can't be
+/// used by more than one copy at a time.
+///
+/// Data in volumes is not part of a snapshot, and many database images declare a volume for
+/// their data directory, so the database has to keep its data elsewhere, e.g. by setting
+/// `PGDATA` for Postgres.
+///
+/// The image is removed once the last handle to the snapshot is dropped, which fails while
+/// containers started from it still exist, so it has to be dropped after them, e.g. by
+/// declaring it before them.
+///
+/// ```rust,no_run
+/// use testcontainers::{core::WaitFor, runners::AsyncRunner, GenericImage};
+///
+/// async fn a_test() {
+///     let postgres = GenericImage::new("postgres", "16-alpine")
+///         .with_env_var(("POSTGRES_PASSWORD", "postgres"))
+///         .with_env_var(("PGDATA", "/pgdata"))
+///         .with_wait_for(WaitFor::message_on_stderr("ready to accept connections"))
+///         .start()
+///         .await
+///         .unwrap();
+///     // run the migrations and seed the database
+///     let snapshot = postgres.snapshot().await;
+///     drop(postgres);
+///
+///     let first = snapshot.start().await.unwrap();
+///     let second = snapshot.start().await.unwrap();
+/// }
+/// ```
+pub struct Snapshot<I: Image> {
+    image: RunnableImage<I>,
+    committed: Arc<CommittedImage>,
+}
+
+struct CommittedImage {
+    descriptor: String,
+}
+
+impl<I: Image + Clone> Snapshot<I> {
+    pub(crate) fn new(image: RunnableImage<I>) -> Self {
+        Self {
+            committed: Arc::new(CommittedImage {
+                descriptor: image.descriptor(),
+            }),
+            image,
+        }
+    }
+
+    /// Returns the descriptor of the committed image, e.g. to start a copy with a different
+    /// configuration.
+    pub fn descriptor(&self) -> &str {
+        &self.committed.descriptor
+    }
+
+    /// Returns the image that copies are started from.
+    pub fn image(&self) -> &RunnableImage<I> {
+        &self.image
+    }
+
+    /// Starts a fresh copy of the container, see [`AsyncRunner::start`].
+    pub async fn start(&self) -> Result<ContainerAsync<I>, TestcontainersError> {
+        self.image.clone().start().await
+    }
+
+    /// Starts a fresh copy of the container, see [`SyncRunner::start`].
+    ///
+    /// [`SyncRunner::start`]: crate::runners::SyncRunner::start
+    #[cfg(feature = "blocking")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+    pub fn start_blocking(&self) -> Result<crate::Container<I>, TestcontainersError> {
+        crate::runners::SyncRunner::start(self.image.clone())
+    }
+}
+
+impl<I: Image + Clone> Clone for Snapshot<I> {
+    fn clone(&self) -> Self {
+        Self {
+            image: self.image.clone(),
+            committed: self.committed.clone(),
+        }
+    }
+}
+
+impl<I: Image> fmt::Debug for Snapshot<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Snapshot")
+            .field("descriptor", &self.committed.descriptor)
+            .finish()
+    }
+}
+
+impl Drop for CommittedImage {
+    fn drop(&mut self) {
+        let descriptor = self.descriptor.clone();
+        let drop_task = async move {
+            let client = Client::lazy_client().await;
+            log::trace!("Drop was called for snapshot {descriptor}, cleaning up");
+            client.remove_image(&descriptor).await;
+        };
+
+        runtime::block_on(drop_task);
+    }
+}
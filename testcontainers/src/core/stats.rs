@@ -0,0 +1,78 @@
+use bollard::container::{MemoryStatsStats, Stats};
+
+/// A snapshot of the resource usage of a container.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainerStats {
+    /// The CPU usage since the previous sample, in percent of a single CPU, i.e. up to
+    /// `100.0` times the number of CPUs available to the container
+    pub cpu_percent: f64,
+    /// The memory used by the container in bytes, excluding the page cache, as reported by
+    /// `docker stats`
+    pub memory_usage: u64,
+    /// The memory limit of the container in bytes, which is the memory of the host unless
+    /// limited
+    pub memory_limit: u64,
+    /// The bytes received over all networks of the container
+    pub network_rx_bytes: u64,
+    /// The bytes sent over all networks of the container
+    pub network_tx_bytes: u64,
+    /// The number of processes and threads in the container
+    pub pids: u64,
+}
+
+impl From<Stats> for ContainerStats {
+    fn from(stats: Stats) -> Self {
+        // the same calculations as `docker stats`
+        let cpu_delta = stats
+            .cpu_stats
+            .cpu_usage
+            .total_usage
+            .saturating_sub(stats.precpu_stats.cpu_usage.total_usage);
+        let system_delta = stats
+            .cpu_stats
+            .system_cpu_usage
+            .unwrap_or_default()
+            .saturating_sub(stats.precpu_stats.system_cpu_usage.unwrap_or_default());
+        let online_cpus = stats.cpu_stats.online_cpus.unwrap_or_else(|| {
+            stats
+                .cpu_stats
+                .cpu_usage
+                .percpu_usage
+                .as_ref()
+                .map_or(1, |usage| usage.len() as u64)
+        });
+        let cpu_percent = if system_delta > 0 {
+            cpu_delta as f64 / system_delta as f64 * online_cpus as f64 * 100.0
+        } else {
+            0.0
+        };
+
+        let cache = match stats.memory_stats.stats {
+            Some(MemoryStatsStats::V1(stats)) => stats.total_inactive_file,
+            Some(MemoryStatsStats::V2(stats)) => stats.inactive_file,
+            None => 0,
+        };
+        let memory_usage = stats
+            .memory_stats
+            .usage
+            .unwrap_or_default()
+            .saturating_sub(cache);
+
+        let (network_rx_bytes, network_tx_bytes) = stats
+            .networks
+            .unwrap_or_default()
+            .values()
+            .fold((0, 0), |(rx, tx), network| {
+                (rx + network.rx_bytes, tx + network.tx_bytes)
+            });
+
+        Self {
+            cpu_percent,
+            memory_usage,
+            memory_limit: stats.memory_stats.limit.unwrap_or_default(),
+            network_rx_bytes,
+            network_tx_bytes,
+            pids: stats.pids_stats.current.unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use std::{fmt, sync::Arc};
+
+use crate::core::{client::Client, env, runtime, Mount};
+
+/// A named volume that is removed once it is not used anymore.
+///
+/// The volume is created when the first container that mounts it is started, and
+/// removed when the last handle to it is dropped, i.e. when this handle and all containers
+/// it is mounted into have been dropped. Useful to share data between containers, e.g. between
+/// a container that prepares data and the service under test.
+///
+/// ```rust,no_run
+/// use testcontainers::{
+///     core::{RunnableImage, Volume},
+///     runners::AsyncRunner,
+///     GenericImage,
+/// };
+///
+/// # async fn example() {
+/// let volume = Volume::new();
+///
+/// let _init = RunnableImage::from(GenericImage::new("alpine", "3.19"))
+///     .with_mount(volume.mount("/data"))
+///     .with_cmd(["sh", "-c", "echo hello > /data/greeting"])
+///     .start()
+///     .await
+///     .unwrap();
+/// let _service = RunnableImage::from(GenericImage::new("alpine", "3.19"))
+///     .with_mount(volume.mount("/data").with_read_only(true))
+///     .start()
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Volume {
+    inner: Arc<VolumeInner>,
+}
+
+struct VolumeInner {
+    name: String,
+}
+
+impl Volume {
+    /// Creates a handle to a new volume with a unique name.
+    pub fn new() -> Self {
+        let name = format!(
+            "testcontainers-{}",
+            ulid::Ulid::new().to_string().to_lowercase()
+        );
+
+        Self {
+            inner: Arc::new(VolumeInner { name }),
+        }
+    }
+
+    /// Returns the name of the volume.
+    pub fn name(&self) -> &str {
+        &self.inner.name
+    }
+
+    /// Returns a mount of this volume at the given path inside a container.
+    ///
+    /// The mount keeps the volume alive as long as the container it is used for.
+    pub fn mount(&self, container_path: impl Into<String>) -> Mount {
+        Mount::managed_volume(self.clone(), container_path)
+    }
+}
+
+impl Default for Volume {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Volume {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Volume")
+            .field("name", &self.inner.name)
+            .finish()
+    }
+}
+
+// Handles are only equal if they refer to the same volume.
+impl PartialEq for Volume {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.name == other.inner.name
+    }
+}
+
+impl Eq for Volume {}
+
+impl Drop for VolumeInner {
+    fn drop(&mut self) {
+        let name = self.name.clone();
+        let drop_task = async move {
+            let client = Client::lazy_client().await;
+            if client.config.command() == env::Command::Remove {
+                log::trace!("Drop was called for volume {name}, cleaning up");
+                client.remove_volume(&name).await;
+            }
+        };
+
+        runtime::block_on(drop_task);
+    }
+}
@@ -0,0 +1,199 @@
+use std::time::Duration;
+
+use crate::core::client::Client;
+
+/// `SERVING` value of the `ServingStatus` enum of the gRPC health checking protocol.
+const SERVING: u64 = 1;
+
+/// Waits until a gRPC service of the container reports `SERVING`, using the standard
+/// [gRPC Health Checking Protocol](https://github.com/grpc/grpc/blob/master/doc/health-checking.md).
+///
+/// Requests are sent over plaintext HTTP/2 to the host port that is mapped to the configured
+/// container port, so the port needs to be exposed.
+///
+/// ```rust
+/// use testcontainers::core::{GrpcHealthWaitStrategy, WaitFor};
+///
+/// let wait_for = WaitFor::grpc_health(
+///     GrpcHealthWaitStrategy::new()
+///         .with_port(50051)
+///         .with_service("helloworld.Greeter"),
+/// );
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GrpcHealthWaitStrategy {
+    port: Option<u16>,
+    service: String,
+    poll_interval: Duration,
+}
+
+impl GrpcHealthWaitStrategy {
+    /// Creates a strategy that checks the overall health of the server.
+    pub fn new() -> Self {
+        Self {
+            port: None,
+            service: String::new(),
+            poll_interval: Duration::from_millis(100),
+        }
+    }
+
+    /// Sets the container port the gRPC server listens on.
+    ///
+    /// Defaults to the lowest container port that is mapped to the host.
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    /// Checks the health of the given service instead of the overall health of the server.
+    pub fn with_service(mut self, service: impl Into<String>) -> Self {
+        self.service = service.into();
+        self
+    }
+
+    /// Sets the time to wait between two health checks.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    pub(crate) async fn wait_until_ready(&self, client: &Client, id: &str) {
+        let ports = client.ports(id).await;
+        let port = self
+            .port
+            .or_else(|| ports.ipv4_internal_ports().min())
+            .unwrap_or_else(|| panic!("container {id} does not expose any port to wait for"));
+        let host_port = ports.map_to_host_port_ipv4(port).unwrap_or_else(|| {
+            panic!("container {id} does not expose (IPV4) port {port}");
+        });
+        let url = format!(
+            "http://{}:{host_port}/grpc.health.v1.Health/Check",
+            client.docker_hostname()
+        );
+
+        log::debug!("Waiting for {url} to report SERVING");
+
+        let http_client = reqwest::Client::builder()
+            .http2_prior_knowledge()
+            .build()
+            .expect("failed to build gRPC client");
+        loop {
+            let response = http_client
+                .post(&url)
+                .header("content-type", "application/grpc")
+                .header("te", "trailers")
+                .body(encode_request(&self.service))
+                .send()
+                .await;
+
+            match response {
+                Ok(response) => match response.bytes().await {
+                    Ok(body) if decode_status(&body) == Some(SERVING) => break,
+                    Ok(body) => {
+                        log::trace!("{url} reported status {:?}", decode_status(&body));
+                    }
+                    Err(err) => log::trace!("failed to read the response of {url}: {err}"),
+                },
+                Err(err) => log::trace!("health check {url} failed: {err}"),
+            }
+
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+}
+
+impl Default for GrpcHealthWaitStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Encodes a length-prefixed `HealthCheckRequest { service }` message.
+fn encode_request(service: &str) -> Vec<u8> {
+    let mut message = Vec::new();
+    if !service.is_empty() {
+        // field 1, wire type 2 (length-delimited)
+        message.push(0x0a);
+        encode_varint(service.len() as u64, &mut message);
+        message.extend_from_slice(service.as_bytes());
+    }
+
+    // uncompressed flag, followed by the big-endian length of the message
+    let mut frame = vec![0];
+    frame.extend_from_slice(&(message.len() as u32).to_be_bytes());
+    frame.extend_from_slice(&message);
+    frame
+}
+
+/// Decodes the `status` of a length-prefixed `HealthCheckResponse` message.
+///
+/// Returns `None` if the body does not contain a complete message.
+fn decode_status(body: &[u8]) -> Option<u64> {
+    let length = u32::from_be_bytes(body.get(1..5)?.try_into().ok()?) as usize;
+    let mut message = body.get(5..5 + length)?;
+
+    // `status` is omitted from the message if it has the default value `UNKNOWN`
+    let mut status = 0;
+    while !message.is_empty() {
+        let key = decode_varint(&mut message)?;
+        match key & 0x07 {
+            0 => {
+                let value = decode_varint(&mut message)?;
+                if key >> 3 == 1 {
+                    status = value;
+                }
+            }
+            2 => {
+                let length = decode_varint(&mut message)? as usize;
+                message = message.get(length..)?;
+            }
+            _ => return None,
+        }
+    }
+
+    Some(status)
+}
+
+fn encode_varint(mut value: u64, buf: &mut Vec<u8>) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn decode_varint(buf: &mut &[u8]) -> Option<u64> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        let (byte, rest) = buf.split_first()?;
+        *buf = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_request_for_service() {
+        assert_eq!(encode_request(""), vec![0, 0, 0, 0, 0]);
+        assert_eq!(
+            encode_request("foo"),
+            vec![0, 0, 0, 0, 5, 0x0a, 3, b'f', b'o', b'o']
+        );
+    }
+
+    #[test]
+    fn decodes_response_status() {
+        assert_eq!(decode_status(&[0, 0, 0, 0, 2, 0x08, 1]), Some(SERVING));
+        assert_eq!(decode_status(&[0, 0, 0, 0, 2, 0x08, 2]), Some(2));
+        assert_eq!(decode_status(&[0, 0, 0, 0, 0]), Some(0));
+        assert_eq!(decode_status(&[]), None);
+    }
+}
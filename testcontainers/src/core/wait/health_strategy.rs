@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use bollard_stubs::models::HealthStatusEnum;
+use tokio::time::Instant;
+
+use crate::core::client::Client;
+
+/// Waits until the healthcheck of the container reports `healthy`.
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use testcontainers::core::WaitFor;
+///
+/// let wait_for: WaitFor = WaitFor::healthcheck()
+///     .with_poll_interval(Duration::from_secs(1))
+///     .with_timeout(Duration::from_secs(30))
+///     .into();
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct HealthWaitStrategy {
+    poll_interval: Duration,
+    timeout: Option<Duration>,
+}
+
+impl HealthWaitStrategy {
+    /// Creates a strategy that checks the health status every 100 milliseconds, without a timeout.
+    pub fn new() -> Self {
+        Self {
+            poll_interval: Duration::from_millis(100),
+            timeout: None,
+        }
+    }
+
+    /// Sets the time to wait between two checks of the health status.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Sets the maximum time to wait for the container to become healthy.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub(crate) async fn wait_until_ready(&self, client: &Client, id: &str) {
+        use HealthStatusEnum::*;
+
+        let started_at = Instant::now();
+
+        loop {
+            let health_status = client
+                .inspect(id)
+                .await
+                .state
+                .unwrap_or_else(|| panic!("Container state not available"))
+                .health
+                .unwrap_or_else(|| panic!("Health state not available"))
+                .status;
+
+            match health_status {
+                Some(HEALTHY) => break,
+                None | Some(EMPTY) | Some(NONE) => {
+                    panic!("Healthcheck not configured for container")
+                }
+                Some(UNHEALTHY) => panic!("Healthcheck reports unhealthy"),
+                Some(STARTING) => {}
+            }
+
+            if let Some(timeout) = self.timeout {
+                if started_at.elapsed() >= timeout {
+                    panic!("container {id} did not become healthy within {timeout:?}");
+                }
+            }
+
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+}
+
+impl Default for HealthWaitStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,244 @@
+use std::{collections::BTreeMap, fmt, sync::Arc, time::Duration};
+
+use url::Url;
+
+use crate::core::client::Client;
+
+/// Waits until an HTTP endpoint of the container responds with the expected status code.
+///
+/// Requests are sent to the host port that is mapped to the configured container port,
+/// so the port needs to be exposed.
+///
+/// ```rust
+/// use testcontainers::core::{HttpWaitStrategy, WaitFor};
+///
+/// let wait_for = WaitFor::http(
+///     HttpWaitStrategy::new("/health")
+///         .with_port(8443)
+///         .with_insecure_tls()
+///         .with_basic_auth("admin", "secret")
+///         .with_header("Accept", "application/json")
+///         .with_expected_status_code(200)
+///         .with_body_containing(r#""status":"UP""#),
+/// );
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct HttpWaitStrategy {
+    path: String,
+    port: Option<u16>,
+    tls: Option<Tls>,
+    basic_auth: Option<(String, String)>,
+    headers: BTreeMap<String, String>,
+    expected_status_code: Option<u16>,
+    body_matcher: Option<BodyMatcher>,
+    poll_interval: Duration,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Tls {
+    Verified,
+    Insecure,
+}
+
+#[derive(Clone)]
+enum BodyMatcher {
+    Contains(String),
+    Predicate(Arc<dyn Fn(&str) -> bool + Send + Sync>),
+}
+
+impl HttpWaitStrategy {
+    /// Creates a strategy that sends `GET` requests to the given path.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            port: None,
+            tls: None,
+            basic_auth: None,
+            headers: BTreeMap::new(),
+            expected_status_code: None,
+            body_matcher: None,
+            poll_interval: Duration::from_millis(100),
+        }
+    }
+
+    /// Sets the container port to send requests to.
+    ///
+    /// Defaults to the lowest container port that is mapped to the host.
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    /// Sends the requests via `https`, verifying the certificate of the container.
+    pub fn with_tls(mut self) -> Self {
+        self.tls = Some(Tls::Verified);
+        self
+    }
+
+    /// Sends the requests via `https` without verifying the certificate of the container,
+    /// e.g. for containers that use a self-signed certificate.
+    pub fn with_insecure_tls(mut self) -> Self {
+        self.tls = Some(Tls::Insecure);
+        self
+    }
+
+    /// Authenticates the requests with the given credentials, using basic authentication.
+    pub fn with_basic_auth(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.basic_auth = Some((username.into(), password.into()));
+        self
+    }
+
+    /// Adds a header to the requests.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Sets the status code the endpoint has to respond with.
+    ///
+    /// By default, any successful (`2xx`) status code is accepted.
+    pub fn with_expected_status_code(mut self, status_code: u16) -> Self {
+        self.expected_status_code = Some(status_code);
+        self
+    }
+
+    /// Requires the body of the response to contain the given text.
+    pub fn with_body_containing(mut self, text: impl Into<String>) -> Self {
+        self.body_matcher = Some(BodyMatcher::Contains(text.into()));
+        self
+    }
+
+    /// Requires the body of the response to satisfy the given predicate.
+    pub fn with_body_matching<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.body_matcher = Some(BodyMatcher::Predicate(Arc::new(predicate)));
+        self
+    }
+
+    /// Sets the time to wait between two requests.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    pub(crate) async fn wait_until_ready(&self, client: &Client, id: &str) {
+        let ports = client.ports(id).await;
+        let port = self
+            .port
+            .or_else(|| ports.ipv4_internal_ports().min())
+            .unwrap_or_else(|| panic!("container {id} does not expose any port to wait for"));
+        let host_port = ports.map_to_host_port_ipv4(port).unwrap_or_else(|| {
+            panic!("container {id} does not expose (IPV4) port {port}");
+        });
+
+        let scheme = if self.tls.is_some() { "https" } else { "http" };
+        let url = Url::parse(&format!(
+            "{scheme}://{}:{host_port}",
+            client.docker_hostname()
+        ))
+        .and_then(|base| base.join(&self.path))
+        .unwrap_or_else(|err| panic!("invalid path '{}' for HTTP wait: {err}", self.path));
+
+        log::debug!("Waiting for {url} to respond");
+
+        let http_client = reqwest::Client::builder()
+            .danger_accept_invalid_certs(self.tls == Some(Tls::Insecure))
+            .build()
+            .expect("failed to build HTTP client");
+        loop {
+            match self.request(&http_client, url.clone()).send().await {
+                Ok(response) if !self.is_expected(response.status()) => {
+                    log::trace!(
+                        "{url} responded with unexpected status {}",
+                        response.status()
+                    );
+                }
+                Ok(response) => match response.text().await {
+                    Ok(body) if self.body_matches(&body) => break,
+                    Ok(body) => log::trace!("{url} responded with unexpected body: {body}"),
+                    Err(err) => log::trace!("failed to read the response of {url}: {err}"),
+                },
+                Err(err) => log::trace!("request to {url} failed: {err}"),
+            }
+
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    fn request(&self, http_client: &reqwest::Client, url: Url) -> reqwest::RequestBuilder {
+        let mut request = http_client.get(url);
+
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        if let Some((username, password)) = &self.basic_auth {
+            request = request.basic_auth(username, Some(password));
+        }
+
+        request
+    }
+
+    fn is_expected(&self, status: reqwest::StatusCode) -> bool {
+        match self.expected_status_code {
+            Some(expected) => status.as_u16() == expected,
+            None => status.is_success(),
+        }
+    }
+
+    fn body_matches(&self, body: &str) -> bool {
+        match &self.body_matcher {
+            None => true,
+            Some(BodyMatcher::Contains(text)) => body.contains(text.as_str()),
+            Some(BodyMatcher::Predicate(predicate)) => predicate(body),
+        }
+    }
+}
+
+impl fmt::Debug for BodyMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BodyMatcher::Contains(text) => f.debug_tuple("Contains").field(text).finish(),
+            BodyMatcher::Predicate(_) => f.write_str("Predicate"),
+        }
+    }
+}
+
+// Predicates can't be compared, so two of them are only considered equal if they are the same instance.
+impl PartialEq for BodyMatcher {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (BodyMatcher::Contains(a), BodyMatcher::Contains(b)) => a == b,
+            (BodyMatcher::Predicate(a), BodyMatcher::Predicate(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for BodyMatcher {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn body_matches_substring() {
+        let strategy = HttpWaitStrategy::new("/").with_body_containing("UP");
+
+        assert!(strategy.body_matches(r#"{"status":"UP"}"#));
+        assert!(!strategy.body_matches(r#"{"status":"DOWN"}"#));
+    }
+
+    #[test]
+    fn body_matches_predicate() {
+        let strategy = HttpWaitStrategy::new("/").with_body_matching(|body| body.len() > 2);
+
+        assert!(strategy.body_matches("foo"));
+        assert!(!strategy.body_matches("{}"));
+    }
+}
@@ -0,0 +1,674 @@
+use std::{env::var, io, time::Duration};
+
+use futures::{
+    future::{join_all, select_all, BoxFuture},
+    FutureExt,
+};
+use regex::Regex;
+use tokio::net::{TcpStream, UdpSocket};
+
+#[cfg(feature = "grpc_wait")]
+#[cfg_attr(docsrs, doc(cfg(feature = "grpc_wait")))]
+pub use self::grpc_strategy::GrpcHealthWaitStrategy;
+pub use self::health_strategy::HealthWaitStrategy;
+#[cfg(feature = "http_wait")]
+#[cfg_attr(docsrs, doc(cfg(feature = "http_wait")))]
+pub use self::http_strategy::HttpWaitStrategy;
+use crate::core::{client::Client, ExecCommand};
+
+#[cfg(feature = "grpc_wait")]
+pub(crate) mod grpc_strategy;
+pub(crate) mod health_strategy;
+#[cfg(feature = "http_wait")]
+pub(crate) mod http_strategy;
+
+/// Represents a condition that needs to be met before a container is considered ready.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum WaitFor {
+    /// An empty condition. Useful for default cases or fallbacks.
+    Nothing,
+    /// Wait for a message on the stdout stream of the container's logs.
+    StdOutMessage { message: String },
+    /// Wait for a message on the stderr stream of the container's logs.
+    StdErrMessage { message: String },
+    /// Wait for a message on either the stdout or the stderr stream of the container's logs.
+    EitherStreamMessage { message: String },
+    /// Wait for a message to appear `times` times on the stdout stream of the container's logs.
+    StdOutMessageTimes { message: String, times: usize },
+    /// Wait for a message to appear `times` times on the stderr stream of the container's logs.
+    StdErrMessageTimes { message: String, times: usize },
+    /// Wait for a line matching the regular expression on the stdout stream of the container's logs.
+    StdOutMatches { pattern: String },
+    /// Wait for a line matching the regular expression on the stderr stream of the container's logs.
+    StdErrMatches { pattern: String },
+    /// Wait for a certain amount of time.
+    Duration { length: Duration },
+    /// Wait until the container has not written any logs for a certain amount of time.
+    LogSilence { duration: Duration },
+    /// Wait for the container's status to become `healthy`.
+    ///
+    /// The status is checked every 100 milliseconds, see [`WaitFor::HealthcheckWith`] to
+    /// configure how it is checked.
+    Healthcheck,
+    /// Wait for the container's status to become `healthy`, checked according to the strategy,
+    /// see [`WaitFor::healthcheck`].
+    HealthcheckWith(HealthWaitStrategy),
+    /// Wait for a TCP connection to the host port mapped to the given container port to succeed.
+    ///
+    /// The connection is retried every `poll_interval` until it is accepted.
+    TcpPortOpen { port: u16, poll_interval: Duration },
+    /// Wait for the host UDP port mapped to the given container UDP port to accept a probe datagram.
+    ///
+    /// If `expect_response` is set, the container has to respond to the probe, otherwise it is
+    /// enough that no error (e.g. an ICMP port unreachable message) is reported within
+    /// `poll_interval`. The probe is sent again every `poll_interval` until it succeeds.
+    UdpPortOpen {
+        port: u16,
+        probe: Vec<u8>,
+        expect_response: bool,
+        poll_interval: Duration,
+    },
+    /// Wait for the container to exit with the given exit code.
+    ExitCode { code: i64 },
+    /// Wait for a command executed inside the container to exit with code `0`.
+    ///
+    /// The command is executed again every `poll_interval` until it succeeds.
+    Command {
+        cmd: Vec<String>,
+        poll_interval: Duration,
+    },
+    /// Wait for a file or directory to exist inside the container.
+    ///
+    /// The container's filesystem is checked every `poll_interval` until the path exists.
+    FileExists {
+        path: String,
+        poll_interval: Duration,
+    },
+    /// Wait for a field of the container's inspect response to have the expected value.
+    ///
+    /// The field is addressed by a [JSON pointer](https://datatracker.ietf.org/doc/html/rfc6901)
+    /// into the response, using the field names printed by `docker inspect`, e.g. `/State/Running`.
+    /// The response is fetched again every `poll_interval` until the field matches.
+    InspectField {
+        pointer: String,
+        expected: serde_json::Value,
+        poll_interval: Duration,
+    },
+    /// Wait for all of the conditions to be met. The conditions are evaluated concurrently.
+    AllOf { conditions: Vec<WaitFor> },
+    /// Wait for any of the conditions to be met. The conditions are evaluated concurrently.
+    AnyOf { conditions: Vec<WaitFor> },
+    /// Wait for the condition to be met within its own timeout.
+    ///
+    /// When used as a ready condition of a container, the condition is not bound by the
+    /// startup timeout, and the time spent waiting for it doesn't count against it.
+    Timeout {
+        condition: Box<WaitFor>,
+        timeout: Duration,
+    },
+    /// Wait for an HTTP endpoint of the container to respond with the expected status.
+    #[cfg(feature = "http_wait")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "http_wait")))]
+    Http(HttpWaitStrategy),
+    /// Wait for a gRPC service of the container to report `SERVING`.
+    #[cfg(feature = "grpc_wait")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "grpc_wait")))]
+    GrpcHealth(GrpcHealthWaitStrategy),
+}
+
+impl WaitFor {
+    /// Wait for the message to appear on the container's stdout. A message that contains line
+    /// breaks matches consecutive log lines.
+    pub fn message_on_stdout<S: Into<String>>(message: S) -> WaitFor {
+        WaitFor::StdOutMessage {
+            message: message.into(),
+        }
+    }
+
+    /// Wait for the message to appear on the container's stderr, see
+    /// [`WaitFor::message_on_stdout`].
+    pub fn message_on_stderr<S: Into<String>>(message: S) -> WaitFor {
+        WaitFor::StdErrMessage {
+            message: message.into(),
+        }
+    }
+
+    /// Wait for a message on either log stream of the container, e.g. for images that moved
+    /// their output from one stream to the other between versions.
+    pub fn message_on_either<S: Into<String>>(message: S) -> WaitFor {
+        WaitFor::EitherStreamMessage {
+            message: message.into(),
+        }
+    }
+
+    /// Wait for the container's status to become `healthy`.
+    ///
+    /// The returned strategy can be customized before it is converted into a
+    /// [`WaitFor::HealthcheckWith`], e.g. to change how often the status is checked.
+    pub fn healthcheck() -> HealthWaitStrategy {
+        HealthWaitStrategy::default()
+    }
+
+    /// Wait for the message to appear `times` times on the container's stdout, e.g. for services
+    /// that print the same message repeatedly while they are starting. The occurrences are counted
+    /// on whole log lines, so a message that docker splits across chunks still counts once.
+    pub fn message_on_stdout_times<S: Into<String>>(message: S, times: usize) -> WaitFor {
+        WaitFor::StdOutMessageTimes {
+            message: message.into(),
+            times,
+        }
+    }
+
+    /// Wait for the message to appear `times` times on the container's stderr.
+    pub fn message_on_stderr_times<S: Into<String>>(message: S, times: usize) -> WaitFor {
+        WaitFor::StdErrMessageTimes {
+            message: message.into(),
+            times,
+        }
+    }
+
+    /// Wait for a line matching the regular expression on the container's stdout.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `pattern` is not a valid regular expression.
+    pub fn stdout_matches<S: Into<String>>(pattern: S) -> WaitFor {
+        WaitFor::StdOutMatches {
+            pattern: validated_pattern(pattern.into()),
+        }
+    }
+
+    /// Wait for a line matching the regular expression on the container's stderr.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `pattern` is not a valid regular expression.
+    pub fn stderr_matches<S: Into<String>>(pattern: S) -> WaitFor {
+        WaitFor::StdErrMatches {
+            pattern: validated_pattern(pattern.into()),
+        }
+    }
+
+    /// Wait for the given container port to accept TCP connections, retrying every 100 milliseconds.
+    pub fn tcp_port_open(port: u16) -> WaitFor {
+        WaitFor::TcpPortOpen {
+            port,
+            poll_interval: Duration::from_millis(100),
+        }
+    }
+
+    /// Wait for the given container UDP port to accept an empty datagram, retrying every
+    /// 100 milliseconds.
+    ///
+    /// Only UDP ports that are mapped to the host can be probed, e.g. ports that are exposed as
+    /// `53/udp` by the image.
+    pub fn udp_port_open(port: u16) -> WaitFor {
+        WaitFor::UdpPortOpen {
+            port,
+            probe: Vec::new(),
+            expect_response: false,
+            poll_interval: Duration::from_millis(100),
+        }
+    }
+
+    /// Wait for the given container UDP port to respond to the probe datagram, retrying every
+    /// 100 milliseconds.
+    ///
+    /// This is the reliable way to wait for UDP services like DNS servers, as a proxied UDP
+    /// port may accept datagrams before the service inside the container is listening.
+    pub fn udp_response(port: u16, probe: impl Into<Vec<u8>>) -> WaitFor {
+        WaitFor::UdpPortOpen {
+            port,
+            probe: probe.into(),
+            expect_response: true,
+            poll_interval: Duration::from_millis(100),
+        }
+    }
+
+    /// Wait for the container to exit, e.g. for containers that run a one-shot job.
+    ///
+    /// If it exits with a code other than `code`, the start fails with
+    /// [`TestcontainersError::Startup`], whose reason is [`StartupFailure::ConditionFailed`].
+    ///
+    /// [`TestcontainersError::Startup`]: crate::core::TestcontainersError::Startup
+    /// [`StartupFailure::ConditionFailed`]: crate::core::StartupFailure::ConditionFailed
+    pub fn exit_code(code: i64) -> WaitFor {
+        WaitFor::ExitCode { code }
+    }
+
+    /// Wait for a command executed inside the container to succeed, e.g. `pg_isready`.
+    ///
+    /// The command is retried every 100 milliseconds until it exits with code `0`.
+    pub fn command<I, S>(cmd: I) -> WaitFor
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        WaitFor::Command {
+            cmd: cmd.into_iter().map(Into::into).collect(),
+            poll_interval: Duration::from_millis(100),
+        }
+    }
+
+    /// Wait for a file or directory to exist inside the container, e.g. a sentinel file
+    /// written once the initialization is complete.
+    ///
+    /// The container's filesystem is checked every 100 milliseconds.
+    pub fn file_exists<S: Into<String>>(path: S) -> WaitFor {
+        WaitFor::FileExists {
+            path: path.into(),
+            poll_interval: Duration::from_millis(100),
+        }
+    }
+
+    /// Wait for a field of the container's inspect response to have the expected value,
+    /// checking it every 100 milliseconds.
+    ///
+    /// ```rust
+    /// use testcontainers::core::WaitFor;
+    ///
+    /// let running = WaitFor::inspect_field("/State/Running", true);
+    /// let healthy = WaitFor::inspect_field("/State/Health/Status", "healthy");
+    /// let not_restarted = WaitFor::inspect_field("/RestartCount", 0);
+    /// ```
+    pub fn inspect_field<P, V>(pointer: P, expected: V) -> WaitFor
+    where
+        P: Into<String>,
+        V: Into<serde_json::Value>,
+    {
+        WaitFor::InspectField {
+            pointer: pointer.into(),
+            expected: expected.into(),
+            poll_interval: Duration::from_millis(100),
+        }
+    }
+
+    /// Wait for all of the given conditions to be met, evaluating them concurrently.
+    pub fn all_of(conditions: Vec<WaitFor>) -> WaitFor {
+        WaitFor::AllOf { conditions }
+    }
+
+    /// Wait for any of the given conditions to be met, evaluating them concurrently.
+    ///
+    /// An empty list of conditions is met immediately.
+    pub fn any_of(conditions: Vec<WaitFor>) -> WaitFor {
+        WaitFor::AnyOf { conditions }
+    }
+
+    /// Limits the time to wait for this condition to the given timeout, instead of the
+    /// startup timeout of the container.
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use testcontainers::core::WaitFor;
+    ///
+    /// let wait_for = WaitFor::message_on_stdout("ready").with_timeout(Duration::from_secs(90));
+    /// ```
+    pub fn with_timeout(self, timeout: Duration) -> WaitFor {
+        WaitFor::Timeout {
+            condition: Box::new(self),
+            timeout,
+        }
+    }
+
+    /// Wait for an HTTP endpoint of the container to respond as configured by the given strategy.
+    #[cfg(feature = "http_wait")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "http_wait")))]
+    pub fn http(strategy: HttpWaitStrategy) -> WaitFor {
+        WaitFor::Http(strategy)
+    }
+
+    /// Wait for a gRPC service of the container to report `SERVING` via the standard
+    /// health checking protocol, as configured by the given strategy.
+    #[cfg(feature = "grpc_wait")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "grpc_wait")))]
+    pub fn grpc_health(strategy: GrpcHealthWaitStrategy) -> WaitFor {
+        WaitFor::GrpcHealth(strategy)
+    }
+
+    pub fn seconds(length: u64) -> WaitFor {
+        WaitFor::Duration {
+            length: Duration::from_secs(length),
+        }
+    }
+
+    pub fn millis(length: u64) -> WaitFor {
+        WaitFor::Duration {
+            length: Duration::from_millis(length),
+        }
+    }
+
+    /// Wait until the container has not written anything to stdout or stderr for the given
+    /// duration. Useful for chatty images that settle down without a deterministic final message.
+    pub fn log_silence(duration: Duration) -> WaitFor {
+        WaitFor::LogSilence { duration }
+    }
+
+    pub fn millis_in_env_var(name: &'static str) -> WaitFor {
+        let additional_sleep_period = var(name).map(|value| value.parse());
+
+        (|| {
+            let length = additional_sleep_period.ok()?.ok()?;
+
+            Some(WaitFor::Duration {
+                length: Duration::from_millis(length),
+            })
+        })()
+        .unwrap_or(WaitFor::Nothing)
+    }
+
+    /// Returns whether the condition is met by a message in the output, which is how it is
+    /// checked as the ready condition of an [`ExecCommand`].
+    pub(crate) fn is_log_based(&self) -> bool {
+        matches!(
+            self,
+            WaitFor::StdOutMessage { .. }
+                | WaitFor::StdErrMessage { .. }
+                | WaitFor::EitherStreamMessage { .. }
+                | WaitFor::StdOutMessageTimes { .. }
+                | WaitFor::StdErrMessageTimes { .. }
+                | WaitFor::StdOutMatches { .. }
+                | WaitFor::StdErrMatches { .. }
+        )
+    }
+
+    /// Blocks until the condition is met for the container with the given id.
+    // The future is boxed because composite conditions wait for their nested conditions recursively.
+    pub(crate) fn wait_until_ready<'a>(
+        &'a self,
+        client: &'a Client,
+        id: &'a str,
+    ) -> BoxFuture<'a, ()> {
+        async move {
+            match self {
+                WaitFor::StdOutMessage { message } => client
+                    .stdout_logs(id)
+                    .wait_for_message(message)
+                    .await
+                    .unwrap(),
+                WaitFor::StdErrMessage { message } => client
+                    .stderr_logs(id)
+                    .wait_for_message(message)
+                    .await
+                    .unwrap(),
+                WaitFor::EitherStreamMessage { message } => {
+                    client.all_logs(id).wait_for_message(message).await.unwrap()
+                }
+                WaitFor::StdOutMessageTimes { message, times } => client
+                    .stdout_logs(id)
+                    .wait_for_message_times(message, *times)
+                    .await
+                    .unwrap(),
+                WaitFor::StdErrMessageTimes { message, times } => client
+                    .stderr_logs(id)
+                    .wait_for_message_times(message, *times)
+                    .await
+                    .unwrap(),
+                WaitFor::StdOutMatches { pattern } => client
+                    .stdout_logs(id)
+                    .wait_for_match(&compile_pattern(pattern))
+                    .await
+                    .unwrap(),
+                WaitFor::StdErrMatches { pattern } => client
+                    .stderr_logs(id)
+                    .wait_for_match(&compile_pattern(pattern))
+                    .await
+                    .unwrap(),
+                WaitFor::Duration { length } => {
+                    tokio::time::sleep(*length).await;
+                }
+                WaitFor::LogSilence { duration } => client
+                    .all_logs(id)
+                    .wait_for_silence(*duration)
+                    .await
+                    .unwrap(),
+                WaitFor::Healthcheck => {
+                    HealthWaitStrategy::default()
+                        .wait_until_ready(client, id)
+                        .await
+                }
+                WaitFor::HealthcheckWith(strategy) => strategy.wait_until_ready(client, id).await,
+                WaitFor::TcpPortOpen {
+                    port,
+                    poll_interval,
+                } => {
+                    let host_port = client
+                        .ports(id)
+                        .await
+                        .map_to_host_port_ipv4(*port)
+                        .unwrap_or_else(|| {
+                            panic!("container {id} does not expose (IPV4) port {port}")
+                        });
+                    let address = (client.docker_hostname(), host_port);
+
+                    while let Err(err) = TcpStream::connect(&address).await {
+                        log::trace!("TCP port {port} of container {id} is not open yet: {err}");
+                        tokio::time::sleep(*poll_interval).await;
+                    }
+                }
+                WaitFor::UdpPortOpen {
+                    port,
+                    probe,
+                    expect_response,
+                    poll_interval,
+                } => {
+                    let host_port = client
+                        .ports(id)
+                        .await
+                        .map_to_host_udp_port_ipv4(*port)
+                        .unwrap_or_else(|| {
+                            panic!("container {id} does not expose (IPV4) UDP port {port}")
+                        });
+                    let address = (client.docker_hostname(), host_port);
+
+                    loop {
+                        match probe_udp(&address, probe, *poll_interval).await {
+                            Ok(true) => break,
+                            Ok(false) if !expect_response => break,
+                            Ok(false) => {
+                                log::trace!("UDP port {port} of container {id} did not respond")
+                            }
+                            Err(err) => {
+                                log::trace!(
+                                    "UDP port {port} of container {id} is not open yet: {err}"
+                                );
+                                tokio::time::sleep(*poll_interval).await;
+                            }
+                        }
+                    }
+                }
+                WaitFor::ExitCode { code } => {
+                    let exit_code = client.wait_for_exit(id).await;
+                    if exit_code != *code {
+                        panic!("container {id} exited with code {exit_code}, expected {code}");
+                    }
+                }
+                WaitFor::Command { cmd, poll_interval } => loop {
+                    let exit_code = client
+                        .exec(id, &ExecCommand::new(cmd.clone()))
+                        .await
+                        .exit_code;
+                    if exit_code == Some(0) {
+                        break;
+                    }
+
+                    log::trace!("Command {cmd:?} in container {id} exited with code {exit_code:?}");
+                    tokio::time::sleep(*poll_interval).await;
+                },
+                WaitFor::FileExists {
+                    path,
+                    poll_interval,
+                } => {
+                    while !client.path_exists(id, path).await {
+                        log::trace!("{path} does not exist in container {id} yet");
+                        tokio::time::sleep(*poll_interval).await;
+                    }
+                }
+                WaitFor::InspectField {
+                    pointer,
+                    expected,
+                    poll_interval,
+                } => loop {
+                    let inspect = serde_json::to_value(client.inspect(id).await)
+                        .expect("inspect response is serializable");
+
+                    match inspect.pointer(pointer) {
+                        Some(value) if value == expected => break,
+                        value => log::trace!(
+                            "{pointer} of container {id} is {value:?}, waiting for {expected}"
+                        ),
+                    }
+
+                    tokio::time::sleep(*poll_interval).await;
+                },
+                #[cfg(feature = "http_wait")]
+                WaitFor::Http(strategy) => strategy.wait_until_ready(client, id).await,
+                #[cfg(feature = "grpc_wait")]
+                WaitFor::GrpcHealth(strategy) => strategy.wait_until_ready(client, id).await,
+                WaitFor::AllOf { conditions } => {
+                    join_all(
+                        conditions
+                            .iter()
+                            .map(|condition| condition.wait_until_ready(client, id)),
+                    )
+                    .await;
+                }
+                WaitFor::AnyOf { conditions } => {
+                    if !conditions.is_empty() {
+                        select_all(
+                            conditions
+                                .iter()
+                                .map(|condition| condition.wait_until_ready(client, id)),
+                        )
+                        .await;
+                    }
+                }
+                WaitFor::Timeout { condition, timeout } => {
+                    let ready = condition.wait_until_ready(client, id);
+                    if tokio::time::timeout(*timeout, ready).await.is_err() {
+                        panic!("container {id} did not meet {condition:?} within {timeout:?}");
+                    }
+                }
+                WaitFor::Nothing => {}
+            }
+        }
+        .boxed()
+    }
+}
+
+/// Sends the probe to the address and returns whether a response was received within `wait`.
+async fn probe_udp(address: &(String, u16), probe: &[u8], wait: Duration) -> io::Result<bool> {
+    let socket = UdpSocket::bind("0.0.0.0:0").await?;
+    socket.connect(address).await?;
+    socket.send(probe).await?;
+
+    // errors like an ICMP port unreachable message are reported when receiving
+    let mut buf = [0; 512];
+    match tokio::time::timeout(wait, socket.recv(&mut buf)).await {
+        Ok(received) => received.map(|_| true),
+        Err(_) => Ok(false),
+    }
+}
+
+pub(crate) fn compile_pattern(pattern: &str) -> Regex {
+    Regex::new(pattern).unwrap_or_else(|err| panic!("invalid log pattern '{pattern}': {err}"))
+}
+
+fn validated_pattern(pattern: String) -> String {
+    compile_pattern(&pattern);
+    pattern
+}
+
+impl From<HealthWaitStrategy> for WaitFor {
+    fn from(strategy: HealthWaitStrategy) -> Self {
+        WaitFor::HealthcheckWith(strategy)
+    }
+}
+
+#[cfg(feature = "http_wait")]
+#[cfg_attr(docsrs, doc(cfg(feature = "http_wait")))]
+impl From<HttpWaitStrategy> for WaitFor {
+    fn from(strategy: HttpWaitStrategy) -> Self {
+        WaitFor::Http(strategy)
+    }
+}
+
+#[cfg(feature = "grpc_wait")]
+#[cfg_attr(docsrs, doc(cfg(feature = "grpc_wait")))]
+impl From<GrpcHealthWaitStrategy> for WaitFor {
+    fn from(strategy: GrpcHealthWaitStrategy) -> Self {
+        WaitFor::GrpcHealth(strategy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn any_of_is_met_once_the_first_condition_is_met() {
+        let client = Client::lazy_client().await;
+        let condition = WaitFor::any_of(vec![WaitFor::seconds(60), WaitFor::millis(10)]);
+
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            condition.wait_until_ready(&client, "unused"),
+        )
+        .await
+        .expect("any_of should not wait for all conditions");
+    }
+
+    #[tokio::test]
+    async fn all_of_evaluates_conditions_concurrently() {
+        let client = Client::lazy_client().await;
+        let condition = WaitFor::all_of(vec![WaitFor::millis(500), WaitFor::millis(500)]);
+
+        // a sequential evaluation would take one second
+        tokio::time::timeout(
+            Duration::from_millis(900),
+            condition.wait_until_ready(&client, "unused"),
+        )
+        .await
+        .expect("all_of should evaluate conditions concurrently");
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "did not meet Duration { length: 60s } within 10ms")]
+    async fn with_timeout_fails_once_the_timeout_elapsed() {
+        let client = Client::lazy_client().await;
+        let condition = WaitFor::seconds(60).with_timeout(Duration::from_millis(10));
+
+        condition.wait_until_ready(&client, "unused").await;
+    }
+
+    #[tokio::test]
+    async fn probe_udp_reports_whether_a_response_was_received() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let address = ("127.0.0.1".to_string(), server.local_addr().unwrap().port());
+        let wait = Duration::from_millis(200);
+
+        assert!(!probe_udp(&address, b"ping", wait).await.unwrap());
+
+        let echo = tokio::spawn(async move {
+            let mut buf = [0; 512];
+            loop {
+                let (len, peer) = server.recv_from(&mut buf).await.unwrap();
+                // the peer of the first, unanswered probe is gone already
+                let _ = server.send_to(&buf[..len], peer).await;
+            }
+        });
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !probe_udp(&address, b"ping", wait).await.unwrap() {}
+        })
+        .await
+        .expect("probe should receive the echo");
+        echo.abort();
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid log pattern 'ready ('")]
+    fn stdout_matches_panics_on_invalid_pattern() {
+        WaitFor::stdout_matches("ready (");
+    }
+}
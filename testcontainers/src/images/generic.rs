@@ -52,8 +52,8 @@ impl GenericImage {
         self
     }
 
-    pub fn with_wait_for(mut self, wait_for: WaitFor) -> Self {
-        self.wait_for.push(wait_for);
+    pub fn with_wait_for(mut self, wait_for: impl Into<WaitFor>) -> Self {
+        self.wait_for.push(wait_for.into());
         self
     }
 
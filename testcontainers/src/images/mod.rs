@@ -1 +1,4 @@
 pub mod generic;
+mod pull;
+
+pub use pull::{pull_all, pull_all_with_progress, PullProgress};
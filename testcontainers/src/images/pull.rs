@@ -0,0 +1,132 @@
+use crate::core::{client::Client, runtime, TestcontainersError};
+
+/// A progress message of the daemon while it pulls an image, see [`pull_all_with_progress`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullProgress {
+    /// The image as it was passed to the pull, e.g. `postgres:16`.
+    pub image: String,
+    /// The id of the layer the message is about, if it is about one.
+    pub layer: Option<String>,
+    /// The status, e.g. `Downloading`, `Pull complete` or `Status: Downloaded newer image`.
+    pub status: String,
+    /// The number of bytes of the layer that were downloaded or extracted so far.
+    pub current: Option<u64>,
+    /// The size of the layer in bytes.
+    pub total: Option<u64>,
+}
+
+/// Pulls the images in parallel, e.g. before the tests of a suite start, so that the first test
+/// that starts a container of an image doesn't spend its startup timeout on the download.
+///
+/// Images without a tag or digest are pulled with the `latest` tag. All pulls are awaited,
+/// even if one fails, and the first error is returned.
+///
+/// Like every pull, a pull that fails for a transient reason, like a registry that responds
+/// with a 5xx status or times out, is retried with an exponential backoff, as often as the
+/// `TESTCONTAINERS_PULL_RETRIES` environment variable says (2 by default). If all retries
+/// fail, a Docker Hub image is pulled from the registry mirrors in the comma-separated
+/// `TESTCONTAINERS_REGISTRY_MIRRORS` environment variable instead, e.g. `mirror.gcr.io`.
+///
+/// ```rust,no_run
+/// async fn warm_up() {
+///     testcontainers::images::pull_all(&["postgres:16", "redis:7"])
+///         .await
+///         .unwrap();
+/// }
+/// ```
+pub async fn pull_all(images: &[&str]) -> Result<(), TestcontainersError> {
+    pull_all_with_progress(images, |_| {}).await
+}
+
+/// Pulls the images in parallel like [`pull_all`], and passes each progress message of the
+/// daemon to the callback, which is called from the pulls of all images.
+///
+/// ```rust,no_run
+/// async fn warm_up() {
+///     testcontainers::images::pull_all_with_progress(&["postgres:16", "redis:7"], |progress| {
+///         if progress.layer.is_none() {
+///             eprintln!("{}: {}", progress.image, progress.status);
+///         }
+///     })
+///     .await
+///     .unwrap();
+/// }
+/// ```
+pub async fn pull_all_with_progress<F>(
+    images: &[&str],
+    on_progress: F,
+) -> Result<(), TestcontainersError>
+where
+    F: Fn(&PullProgress) + Sync,
+{
+    runtime::compat(async {
+        let client = Client::try_lazy_client().await?;
+        let pulls = images.iter().map(|image| {
+            let client = &client;
+            let on_progress = &on_progress;
+            async move {
+                let descriptor = with_default_tag(image);
+                log::debug!("Pulling image {descriptor}");
+                client
+                    .pull_image_with_progress(&descriptor, None, |info| {
+                        on_progress(&PullProgress {
+                            image: image.to_string(),
+                            layer: info.id,
+                            status: info.status.unwrap_or_default(),
+                            current: info
+                                .progress_detail
+                                .as_ref()
+                                .and_then(|detail| detail.current)
+                                .and_then(|current| u64::try_from(current).ok()),
+                            total: info
+                                .progress_detail
+                                .and_then(|detail| detail.total)
+                                .and_then(|total| u64::try_from(total).ok()),
+                        })
+                    })
+                    .await
+                    .map_err(|err| {
+                        TestcontainersError::from_client(err, |message| {
+                            TestcontainersError::PullFailed {
+                                image: descriptor.clone(),
+                                message,
+                            }
+                        })
+                    })
+            }
+        });
+
+        futures::future::join_all(pulls).await.into_iter().collect()
+    })
+    .await
+}
+
+/// Adds the `latest` tag to an image without a tag or digest, as the daemon pulls all tags of
+/// an image that has none.
+fn with_default_tag(image: &str) -> String {
+    let name = image.rsplit('/').next().unwrap_or(image);
+    if name.contains(':') || name.contains('@') {
+        image.to_string()
+    } else {
+        format!("{image}:latest")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn images_without_tag_are_pulled_with_latest_tag() {
+        assert_eq!(with_default_tag("redis"), "redis:latest");
+        assert_eq!(with_default_tag("redis:7"), "redis:7");
+        assert_eq!(
+            with_default_tag("localhost:5000/team/app"),
+            "localhost:5000/team/app:latest"
+        );
+        assert_eq!(
+            with_default_tag("alpine@sha256:c5b1261d"),
+            "alpine@sha256:c5b1261d"
+        );
+    }
+}
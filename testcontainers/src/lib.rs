@@ -25,6 +25,11 @@
 //!
 //! See examples in the corresponding runner ([`AsyncRunner`] and [`SyncRunner`])
 //!
+//! The [`AsyncRunner`] does not depend on the runtime of your tests: the calls to the docker daemon are driven by a
+//! runtime of testcontainers itself, so it can be used with tokio's multi-threaded and current-thread runtimes, as well
+//! as with other executors like async-std. The work testcontainers does in the background, like forwarding logs to
+//! [`LogConsumer`]s, runs on that runtime as well, or on async-std with the `async_std` feature.
+//!
 //! # Ecosystem
 //!
 //! `testcontainers` is the core crate that provides an API for working with containers in a test environment.
@@ -32,6 +37,36 @@
 //!
 //! However, it does not provide ready-to-use modules, you can implement your [`Image`]s using the library directly or use community supported [`testcontainers-modules`].
 //!
+//! # Docker hosts
+//!
+//! The docker host is taken from the `tc.host` property in `~/.testcontainers.properties`, `DOCKER_HOST` or the
+//! `docker.host` property. Without any of them, the endpoint of the selected docker context is used, see below, or else
+//! the first existing socket of `/var/run/docker.sock`, `$XDG_RUNTIME_DIR/docker.sock` of a rootless Docker,
+//! `~/.docker/run/docker.sock` and `~/.docker/desktop/docker.sock` of Docker Desktop, `~/.colima/default/docker.sock`
+//! and `~/.colima/docker.sock` of Colima, `~/.rd/docker.sock` of Rancher Desktop and the sockets of Podman. If none of
+//! them exists, a warning lists the sockets that were looked for.
+//!
+//! # Remote Docker hosts
+//!
+//! With `DOCKER_HOST=tcp://<host>:<port>`, containers are started on a remote daemon, e.g. a Docker-in-Docker service of
+//! a CI pipeline, and their ports are reached on that host. If `DOCKER_TLS_VERIFY=1` is set, the connection uses mutual
+//! TLS with the `ca.pem`, `cert.pem` and `key.pem` files in `DOCKER_CERT_PATH`, which defaults to `~/.docker`.
+//!
+//! With `DOCKER_HOST=ssh://[user@]<host>[:port]`, the docker socket of the host is forwarded by the `ssh` command of
+//! the system, which has to be able to log in without a prompt, e.g. with a key of the ssh agent. The socket is
+//! expected at `/var/run/docker.sock` on the host, unless `TESTCONTAINERS_DOCKER_SOCKET_OVERRIDE` says otherwise.
+//!
+//! Without a `DOCKER_HOST`, the endpoint of the docker context selected by `DOCKER_CONTEXT` or `docker context use` is
+//! used, e.g. the one of Colima or Docker Desktop, along with its TLS certificates.
+//!
+//! # Podman
+//!
+//! Podman serves the same API as the Docker daemon and is used like it. Without a `DOCKER_HOST`, its socket is used if
+//! none of the sockets of Docker exists: the one of a rootless Podman in `$XDG_RUNTIME_DIR/podman/podman.sock`, or
+//! else the one of a rootful Podman in `/run/podman/podman.sock`. As the resource reaper can't access the socket of a
+//! rootless Podman, it is disabled there unless `TESTCONTAINERS_RYUK_DISABLED=false` is set, and it runs privileged on
+//! Podman unless `TESTCONTAINERS_RYUK_PRIVILEGED=false` is set.
+//!
 //! # Usage in production code
 //!
 //! Although nothing inherently prevents testcontainers from being used in production code, the library itself was not designed with that in mind.
@@ -41,17 +76,36 @@
 //! [`Docker`]: https://docker.com
 //! [`AsyncRunner`]: runners::AsyncRunner
 //! [`SyncRunner`]: runners::SyncRunner
+//! [`LogConsumer`]: core::LogConsumer
 //! [`testcontainers-modules`]: https://crates.io/crates/testcontainers-modules
 
+pub mod cleanup;
+pub mod compose;
+pub mod config;
 pub mod core;
 pub use crate::core::{containers::*, Image, ImageArgs, RunnableImage};
 
+#[cfg(feature = "macros")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+pub use testcontainers_macros::test;
+
+/// The tokio of testcontainers, which the code generated by [`test`] refers to.
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub use tokio as __tokio;
+
+pub(crate) mod reaper;
+
+#[cfg(feature = "rstest_fixtures")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rstest_fixtures")))]
+pub mod rstest;
+
 #[cfg(feature = "watchdog")]
 #[cfg_attr(docsrs, doc(cfg(feature = "watchdog")))]
 pub(crate) mod watchdog;
 
-/// All available Docker images.
-mod images;
+/// All available Docker images, and pulling images ahead of the tests.
+pub mod images;
 pub use images::generic::GenericImage;
 
 pub mod runners;
@@ -0,0 +1,253 @@
+//! The resource reaper removes all containers, networks and volumes of a session once the
+//! test process is gone, even if it was killed or aborted before it could clean up itself.
+//!
+//! The [`ryuk`] sidecar is started once per session. The process keeps a connection to it open
+//! for its whole lifetime and registers the labels of the session with it. Once the connection
+//! closes, ryuk removes every resource with these labels, which excludes the containers whose
+//! [`RemovePolicy`] may keep them. The [`SharedContainer`]s of the session are registered with
+//! it as well, independent of the `reaper` feature, as they are never dropped.
+//!
+//! [`SharedContainer`]: crate::SharedContainer
+//! [`ryuk`]: https://github.com/testcontainers/moby-ryuk
+
+use std::{collections::HashMap, time::Duration};
+
+use bollard::{
+    container::{Config, CreateContainerOptions},
+    models::{HostConfig, PortBinding},
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::TcpStream,
+    sync::OnceCell,
+    time::Instant,
+};
+
+use crate::core::{
+    client::{Client, Engine},
+    runtime, session, RemovePolicy, TestcontainersError,
+};
+
+const RYUK_IMAGE: &str = "testcontainers/ryuk:0.7.0";
+const RYUK_PORT: u16 = 8080;
+/// The label that marks the ryuk container, so it is not reaped by itself.
+const RYUK_LABEL: &str = "org.testcontainers.ryuk";
+/// How long to wait for ryuk to accept the labels of the session.
+const RYUK_STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The id of the ryuk container of the session, which is started once.
+static RYUK: OnceCell<String> = OnceCell::const_new();
+static SESSION_WATCHED: OnceCell<()> = OnceCell::const_new();
+static SHARED_CONTAINERS_WATCHED: OnceCell<()> = OnceCell::const_new();
+
+/// Starts the reaper unless it is already running or disabled, and registers the session
+/// with it.
+#[cfg_attr(not(feature = "reaper"), allow(dead_code))]
+pub(crate) async fn ensure_started(client: &Client) -> Result<(), TestcontainersError> {
+    // ryuk would remove the networks and volumes of the containers that are kept
+    if client.config.remove_policy() != RemovePolicy::Always || is_disabled(client).await {
+        return Ok(());
+    }
+
+    // containers that may be kept don't have the label, so they are not removed
+    SESSION_WATCHED
+        .get_or_try_init(|| watch(client, session::REAP_LABEL))
+        .await
+        .map(|_| ())
+}
+
+/// Starts the reaper unless it is already running or disabled, and registers the shared
+/// containers of the session with it, i.e. the ones with the [`session::SHARED_LABEL`].
+pub(crate) async fn ensure_shared_containers_removed(
+    client: &Client,
+) -> Result<(), TestcontainersError> {
+    if is_disabled(client).await {
+        log::warn!(
+            "The resource reaper is disabled, so the shared containers of session {} are left running once the process exits",
+            session::session_id()
+        );
+        return Ok(());
+    }
+
+    SHARED_CONTAINERS_WATCHED
+        .get_or_try_init(|| watch(client, session::SHARED_LABEL))
+        .await
+        .map(|_| ())
+}
+
+async fn is_disabled(client: &Client) -> bool {
+    // ryuk can't access the socket of a rootless Podman, so its containers are only removed
+    // once they are dropped, unless ryuk is enabled explicitly
+    let engine = client.engine().await;
+    client
+        .config
+        .ryuk_disabled()
+        .unwrap_or(engine == Engine::Podman { rootless: true })
+}
+
+/// Makes the reaper remove the resources of the session with the label once the process
+/// exits, starting it unless it is already running.
+async fn watch(client: &Client, label: &str) -> Result<(), TestcontainersError> {
+    let id = RYUK.get_or_try_init(|| start_ryuk(client)).await?;
+    let connection = connect(client, id, label).await?;
+    // the connection is kept open for the lifetime of the process
+    runtime::spawn(keep_alive(connection));
+    log::debug!(
+        "Resource reaper {id} watches the resources of session {} labelled {label}",
+        session::session_id()
+    );
+    Ok(())
+}
+
+async fn start_ryuk(client: &Client) -> Result<String, TestcontainersError> {
+    // Podman labels its socket for SELinux so that only privileged containers can use it
+    let privileged = client
+        .config
+        .ryuk_privileged()
+        .unwrap_or(client.engine().await.is_podman());
+    let config = Config {
+        image: Some(RYUK_IMAGE.to_string()),
+        labels: Some(HashMap::from([
+            (
+                session::TESTCONTAINERS_LABEL.to_string(),
+                "true".to_string(),
+            ),
+            (RYUK_LABEL.to_string(), "true".to_string()),
+        ])),
+        exposed_ports: Some(HashMap::from([(
+            format!("{RYUK_PORT}/tcp"),
+            HashMap::new(),
+        )])),
+        host_config: Some(HostConfig {
+            auto_remove: Some(true),
+            privileged: Some(privileged),
+            binds: Some(vec![format!(
+                "{}:/var/run/docker.sock",
+                client.config.docker_socket()
+            )]),
+            port_bindings: Some(HashMap::from([(
+                format!("{RYUK_PORT}/tcp"),
+                Some(vec![PortBinding::default()]),
+            )])),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let options = Some(CreateContainerOptions {
+        name: format!("testcontainers-ryuk-{}", session::session_id()),
+        platform: None,
+    });
+
+    let created = match client
+        .create_container(options.clone(), config.clone())
+        .await
+    {
+        Err(bollard::errors::Error::DockerResponseServerError {
+            status_code: 404, ..
+        }) => {
+            client.pull_image(RYUK_IMAGE, None).await.map_err(|err| {
+                TestcontainersError::from_client(err, |message| TestcontainersError::PullFailed {
+                    image: RYUK_IMAGE.to_string(),
+                    message,
+                })
+            })?;
+            client.create_container(options, config).await
+        }
+        result => result,
+    };
+    let id = created
+        .map_err(|err| {
+            TestcontainersError::from_client(err, |message| TestcontainersError::CreateFailed {
+                image: RYUK_IMAGE.to_string(),
+                message,
+            })
+        })?
+        .id;
+
+    client
+        .bollard
+        .start_container::<String>(&id, None)
+        .await
+        .map_err(|err| {
+            TestcontainersError::from_client(err, |message| TestcontainersError::StartFailed {
+                container_id: id.clone(),
+                message,
+            })
+        })?;
+
+    Ok(id)
+}
+
+/// Connects to ryuk and registers the resources of the session with the label, retrying until
+/// it accepts them.
+async fn connect(client: &Client, id: &str, label: &str) -> Result<TcpStream, TestcontainersError> {
+    let port = client
+        .ports(id)
+        .await
+        .map_to_host_port_ipv4(RYUK_PORT)
+        .ok_or_else(|| TestcontainersError::StartFailed {
+            container_id: id.to_string(),
+            message: format!("port {RYUK_PORT} of the resource reaper is not mapped"),
+        })?;
+    let address = format!("{}:{port}", client.docker_hostname());
+    let filter = url::form_urlencoded::Serializer::new(String::new())
+        .append_pair(
+            "label",
+            &format!("{}={}", session::SESSION_ID_LABEL, session::session_id()),
+        )
+        .append_pair("label", &format!("{label}=true"))
+        .finish();
+
+    let started_at = Instant::now();
+    loop {
+        match register(&address, &filter).await {
+            Ok(connection) => return Ok(connection),
+            Err(err) if started_at.elapsed() < RYUK_STARTUP_TIMEOUT => {
+                log::trace!("Resource reaper at {address} is not ready yet: {err}");
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            Err(err) => {
+                return Err(TestcontainersError::StartFailed {
+                    container_id: id.to_string(),
+                    message: format!(
+                        "resource reaper at {address} did not accept the session: {err}"
+                    ),
+                })
+            }
+        }
+    }
+}
+
+async fn register(address: &str, filter: &str) -> std::io::Result<TcpStream> {
+    let mut connection = TcpStream::connect(address).await?;
+    connection
+        .write_all(format!("{filter}\n").as_bytes())
+        .await?;
+
+    let mut reply = String::new();
+    BufReader::new(&mut connection)
+        .read_line(&mut reply)
+        .await?;
+    if reply.trim() != "ACK" {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("unexpected reply {reply:?}"),
+        ));
+    }
+
+    Ok(connection)
+}
+
+async fn keep_alive(mut connection: TcpStream) {
+    let mut buf = [0; 64];
+    loop {
+        match connection.read(&mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+    }
+    log::warn!(
+        "Connection to the resource reaper was closed, resources of session {} may be removed",
+        session::session_id()
+    );
+}
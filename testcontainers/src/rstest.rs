@@ -0,0 +1,82 @@
+//! Helpers to declare containers as [rstest] fixtures.
+//!
+//! A fixture that starts a container for every test that uses it needs no helper, as rstest
+//! supports `async` fixtures:
+//!
+//! ```rust,ignore
+//! use rstest::{fixture, rstest};
+//! use testcontainers::{runners::AsyncRunner, ContainerAsync, GenericImage};
+//!
+//! #[fixture]
+//! async fn redis() -> ContainerAsync<GenericImage> {
+//!     GenericImage::new("redis", "7.2.4").start().await.unwrap()
+//! }
+//!
+//! #[rstest]
+//! #[tokio::test]
+//! async fn a_test(#[future(awt)] redis: ContainerAsync<GenericImage>) {}
+//! ```
+//!
+//! A `#[once]` fixture is started once and shared by all tests of the test binary. It can't be
+//! `async`, so it is started with [`start_once`], or [`start_once_blocking`] for synchronous
+//! tests. Its value is never dropped, so the container is removed by the resource reaper once
+//! the process exits, like the one of a [`SharedContainer`]:
+//!
+//! ```rust,ignore
+//! use rstest::{fixture, rstest};
+//! use testcontainers::{core::WaitFor, ContainerAsync, GenericImage};
+//!
+//! #[fixture]
+//! #[once]
+//! fn postgres() -> ContainerAsync<GenericImage> {
+//!     testcontainers::rstest::start_once(
+//!         GenericImage::new("postgres", "16-alpine")
+//!             .with_env_var(("POSTGRES_PASSWORD", "postgres"))
+//!             .with_wait_for(WaitFor::message_on_stderr("ready to accept connections")),
+//!     )
+//! }
+//!
+//! #[rstest]
+//! #[tokio::test]
+//! async fn a_test(postgres: &ContainerAsync<GenericImage>) {
+//!     let port = postgres.get_host_port_ipv4(5432).await;
+//! }
+//! ```
+//!
+//! [rstest]: https://docs.rs/rstest
+//! [`SharedContainer`]: crate::SharedContainer
+
+use crate::{
+    core::{containers::shared_container::start_shared, runtime},
+    ContainerAsync, Image, RunnableImage,
+};
+
+/// Starts a container for a `#[once]` fixture of `async` tests, which can use it from any
+/// runtime.
+///
+/// # Panics
+///
+/// Panics if the container fails to start, which fails the tests that use the fixture.
+pub fn start_once<I: Image + 'static>(image: impl Into<RunnableImage<I>>) -> ContainerAsync<I> {
+    let image = image.into();
+    runtime::block_on(start_shared(image))
+        .unwrap_or_else(|err| panic!("failed to start the container of the fixture: {err}"))
+}
+
+/// Starts a container for a `#[once]` fixture of synchronous tests.
+///
+/// # Panics
+///
+/// Panics if the container fails to start, which fails the tests that use the fixture.
+#[cfg(feature = "blocking")]
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+pub fn start_once_blocking<I: Image + 'static>(
+    image: impl Into<RunnableImage<I>>,
+) -> crate::Container<I> {
+    let runtime = crate::runners::sync_runner::build_sync_runner();
+    let container = runtime
+        .block_on(start_shared(image.into()))
+        .unwrap_or_else(|err| panic!("failed to start the container of the fixture: {err}"));
+
+    crate::Container::new(runtime, container)
+}
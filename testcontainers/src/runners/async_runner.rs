@@ -1,11 +1,18 @@
 use crate::{
-    core::{client::Client, network::Network, ContainerState},
+    config,
+    core::{
+        client::Client, env, logs, network::CreatedNetwork, removal, runtime, session,
+        CgroupnsMode, ContainerPlan, ContainerState, Mount, RemovePolicy, TestcontainersError,
+    },
     ContainerAsync, Image, ImageArgs, RunnableImage,
 };
 use async_trait::async_trait;
 use bollard::{
-    container::{Config, CreateContainerOptions},
-    models::{HostConfig, PortBinding},
+    container::{Config, CreateContainerOptions, NetworkingConfig},
+    models::{
+        DeviceMapping, DeviceRequest, EndpointIpamConfig, EndpointSettings, HostConfig,
+        HostConfigCgroupnsModeEnum, HostConfigLogConfig, PortBinding, ResourcesUlimits,
+    },
 };
 use std::collections::HashMap;
 
@@ -22,16 +29,22 @@ use std::collections::HashMap;
 ///         .with_exposed_port(6379)
 ///         .with_wait_for(WaitFor::message_on_stdout("Ready to accept connections"))
 ///         .start()
-///         .await;
+///         .await
+///         .unwrap();
 /// }
 /// ```
 pub trait AsyncRunner<I: Image> {
     /// Starts the container and returns an instance of `ContainerAsync`.
-    async fn start(self) -> ContainerAsync<I>;
+    ///
+    /// Returns an error if the image can't be pulled, the container can't be created or
+    /// started, or if it doesn't become ready. If the `TESTCONTAINERS_DRY_RUN` environment
+    /// variable is set, no container is started and [`TestcontainersError::DryRun`] is returned
+    /// with the plan of the container instead.
+    async fn start(self) -> Result<ContainerAsync<I>, TestcontainersError>;
 
     /// Pulls the image from the registry.
     /// Useful if you want to pull the image before starting the container.
-    async fn pull_image(self) -> RunnableImage<I>;
+    async fn pull_image(self) -> Result<RunnableImage<I>, TestcontainersError>;
 }
 
 #[async_trait]
@@ -40,131 +53,698 @@ where
     T: Into<RunnableImage<I>> + Send,
     I: Image,
 {
-    async fn start(self) -> ContainerAsync<I> {
-        let client = Client::lazy_client().await;
+    async fn start(self) -> Result<ContainerAsync<I>, TestcontainersError> {
+        runtime::compat(start_container(self.into())).await
+    }
+
+    async fn pull_image(self) -> Result<RunnableImage<I>, TestcontainersError> {
         let runnable_image = self.into();
-        let mut create_options: Option<CreateContainerOptions<String>> = None;
+        runtime::compat(async {
+            let client = Client::try_lazy_client().await?;
+            let descriptor = runnable_image.descriptor();
+            client
+                .pull_image(&descriptor, runnable_image.platform())
+                .await
+                .map_err(|err| {
+                    TestcontainersError::from_client(err, |message| {
+                        TestcontainersError::PullFailed {
+                            image: descriptor.clone(),
+                            message,
+                        }
+                    })
+                })
+        })
+        .await?;
 
-        let extra_hosts: Vec<_> = runnable_image
-            .hosts()
-            .map(|(key, value)| format!("{key}:{value}"))
-            .collect();
+        Ok(runnable_image)
+    }
+}
 
-        let mut config: Config<String> = Config {
-            image: Some(runnable_image.descriptor()),
-            host_config: Some(HostConfig {
-                privileged: Some(runnable_image.privileged()),
-                extra_hosts: Some(extra_hosts),
-                ..Default::default()
-            }),
-            ..Default::default()
-        };
+/// Starts all containers concurrently, pulling their images and waiting for their ready
+/// conditions in parallel, and returns them in the order of the images.
+///
+/// If any container can't be started, the first error is returned once all others are done,
+/// and the containers that were started are removed again.
+///
+/// ## Example
+///
+/// ```rust
+/// use testcontainers::{core::WaitFor, runners, GenericImage};
+///
+/// async fn test_cluster() {
+///     let image = GenericImage::new("redis", "7.2.4")
+///         .with_exposed_port(6379)
+///         .with_wait_for(WaitFor::message_on_stdout("Ready to accept connections"));
+///     let nodes = runners::start_all(vec![image.clone(), image.clone(), image])
+///         .await
+///         .unwrap();
+/// }
+/// ```
+pub async fn start_all<T, I>(
+    images: impl IntoIterator<Item = T>,
+) -> Result<Vec<ContainerAsync<I>>, TestcontainersError>
+where
+    T: Into<RunnableImage<I>> + Send,
+    I: Image,
+{
+    // all starts are awaited, even if one fails, so that no container is left behind half-started
+    futures::future::join_all(images.into_iter().map(|image| image.start()))
+        .await
+        .into_iter()
+        .collect()
+}
+
+/// Helper trait to start containers of different images concurrently, implemented for tuples
+/// of up to six images.
+///
+/// Like [`start_all`], all containers are started in parallel, the first error is returned once
+/// all others are done, and the containers that were started are removed again.
+///
+/// ## Example
+///
+/// ```rust
+/// use testcontainers::{core::WaitFor, runners::AsyncTupleRunner, GenericImage};
+///
+/// async fn test_cache_and_database() {
+///     let redis = GenericImage::new("redis", "7.2.4")
+///         .with_wait_for(WaitFor::message_on_stdout("Ready to accept connections"));
+///     let postgres = GenericImage::new("postgres", "16-alpine")
+///         .with_env_var("POSTGRES_PASSWORD", "postgres")
+///         .with_wait_for(WaitFor::message_on_stderr(
+///             "database system is ready to accept connections",
+///         ));
+///     let (redis, postgres) = (redis, postgres).start_all().await.unwrap();
+/// }
+/// ```
+#[async_trait]
+pub trait AsyncTupleRunner<I> {
+    /// The started containers, in the order of the images.
+    type Containers;
+
+    /// Starts all containers concurrently and returns them in the order of the images.
+    async fn start_all(self) -> Result<Self::Containers, TestcontainersError>;
+}
+
+macro_rules! impl_async_tuple_runner {
+    ($(($image:ident, $runnable:ident, $container:ident)),+) => {
+        #[async_trait]
+        impl<$($image, $runnable),+> AsyncTupleRunner<($($image,)+)> for ($($runnable,)+)
+        where
+            $($runnable: Into<RunnableImage<$image>> + Send, $image: Image,)+
+        {
+            type Containers = ($(ContainerAsync<$image>,)+);
 
-        // shared memory
-        if let Some(bytes) = runnable_image.shm_size() {
-            config.host_config = config.host_config.map(|mut host_config| {
-                host_config.shm_size = Some(bytes as i64);
-                host_config
-            });
+            async fn start_all(self) -> Result<Self::Containers, TestcontainersError> {
+                let ($($container,)+) = self;
+                // all starts are awaited, even if one fails, so that no container is left
+                // behind half-started
+                let ($($container,)+) = futures::join!($(AsyncRunner::<$image>::start($container)),+);
+                Ok(($($container?,)+))
+            }
         }
+    };
+}
+
+impl_async_tuple_runner!((I1, T1, c1), (I2, T2, c2));
+impl_async_tuple_runner!((I1, T1, c1), (I2, T2, c2), (I3, T3, c3));
+impl_async_tuple_runner!((I1, T1, c1), (I2, T2, c2), (I3, T3, c3), (I4, T4, c4));
+impl_async_tuple_runner!(
+    (I1, T1, c1),
+    (I2, T2, c2),
+    (I3, T3, c3),
+    (I4, T4, c4),
+    (I5, T5, c5)
+);
+impl_async_tuple_runner!(
+    (I1, T1, c1),
+    (I2, T2, c2),
+    (I3, T3, c3),
+    (I4, T4, c4),
+    (I5, T5, c5),
+    (I6, T6, c6)
+);
+
+/// Creates and starts the container, pulling its image if it is missing, and waits until it
+/// is ready.
+async fn start_container<I: Image>(
+    runnable_image: RunnableImage<I>,
+) -> Result<ContainerAsync<I>, TestcontainersError> {
+    if env::dry_run::<env::Os>() {
+        let plan = ContainerPlan::new(&runnable_image)?;
+        log::info!(
+            "Dry run, not starting a container of {}:\n{plan}",
+            runnable_image.descriptor()
+        );
+        return Err(TestcontainersError::DryRun(Box::new(plan)));
+    }
+
+    let client = Client::try_lazy_client().await?;
+
+    #[cfg(feature = "reaper")]
+    crate::reaper::ensure_started(&client).await?;
+
+    let (create_options, config) = create_request(&runnable_image)?;
 
-        // create network and add it to container creation
-        let network = if let Some(network) = runnable_image.network() {
-            config.host_config = config.host_config.map(|mut host_config| {
-                host_config.network_mode = Some(network.to_string());
-                host_config
-            });
-            Network::new(network, client.clone()).await
-        } else {
-            None
+    let descriptor = runnable_image.descriptor();
+    let create_failed = |err| {
+        TestcontainersError::from_client(err, |message| TestcontainersError::CreateFailed {
+            image: descriptor.clone(),
+            message,
+        })
+    };
+
+    // create the network, unless it exists already
+    let network = if let Some(network) = runnable_image.network() {
+        CreatedNetwork::new(network, runnable_image.network_subnets(), client.clone())
+            .await
+            .map_err(create_failed)?
+    } else {
+        None
+    };
+
+    // managed volumes are created up front, as docker would create them without the labels of
+    // the session
+    for name in runnable_image
+        .mounts()
+        .iter()
+        .filter_map(Mount::managed_volume_name)
+    {
+        client.create_volume(name).await.map_err(create_failed)?;
+    }
+
+    let attempts = runnable_image.startup_attempts();
+    let remove_policy = runnable_image
+        .remove_on_drop()
+        .unwrap_or_else(|| client.config.remove_policy());
+    let mut attempt = 1;
+    let container_id = loop {
+        // held until the container is started, see `config::set_max_concurrent_starts`
+        let start_permit = config::start_permit().await;
+
+        // create the container with options, pulling the image if it is missing
+        let create_result = match client
+            .create_container(create_options.clone(), config.clone())
+            .await
+        {
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => {
+                client
+                    .pull_image(&descriptor, runnable_image.platform())
+                    .await
+                    .map_err(|err| {
+                        TestcontainersError::from_client(err, |message| {
+                            TestcontainersError::PullFailed {
+                                image: descriptor.clone(),
+                                message,
+                            }
+                        })
+                    })?;
+                client
+                    .create_container(create_options.clone(), config.clone())
+                    .await
+            }
+            result => result,
         };
+        let container_id = create_result.map_err(create_failed)?.id;
 
-        // name of the container
-        if let Some(name) = runnable_image.container_name() {
-            create_options = Some(CreateContainerOptions {
-                name: name.to_owned(),
-                platform: None,
-            })
+        // the watchdog would remove a container that is kept once the process is interrupted
+        #[cfg(feature = "watchdog")]
+        if remove_policy == RemovePolicy::Always {
+            crate::watchdog::register(container_id.clone());
         }
 
-        // handle environment variables
-        let envs: Vec<String> = runnable_image
-            .env_vars()
-            .map(|(k, v)| format!("{k}={v}"))
-            .collect();
-        config.env = Some(envs);
+        if let Err(err) = client
+            .bollard
+            .start_container::<String>(&container_id, None)
+            .await
+        {
+            // the container is never handed out, so it has to be cleaned up here
+            if remove_policy.removes(true) {
+                client.rm_if_exists(&container_id).await;
+            } else {
+                removal::report_kept(&client, &container_id).await;
+            }
+            #[cfg(feature = "watchdog")]
+            crate::watchdog::unregister(&container_id);
+            return Err(TestcontainersError::from_client(err, |message| {
+                TestcontainersError::StartFailed {
+                    container_id: container_id.clone(),
+                    message,
+                }
+            }));
+        }
 
-        // volumes
-        let binds: Vec<String> = runnable_image
-            .volumes()
-            .map(|(orig, dest)| format!("{orig}:{dest}"))
-            .collect();
-        if !binds.is_empty() {
-            config.host_config = config.host_config.map(|mut host_config| {
-                host_config.binds = Some(binds);
-                host_config
-            });
+        drop(start_permit);
+
+        // forward the logs from the start on, so those of failed attempts are included
+        if !runnable_image.log_consumers().is_empty() {
+            logs::forward_to_consumers(
+                container_id.clone(),
+                runnable_image.log_consumers().to_vec(),
+            );
         }
 
-        // entrypoint
-        if let Some(entrypoint) = runnable_image.entrypoint() {
-            config.entrypoint = Some(vec![entrypoint]);
+        let ready =
+            ContainerAsync::block_until_ready(&client, &container_id, &runnable_image).await;
+
+        match ready {
+            Ok(()) => break container_id,
+            Err(error) => {
+                let is_last_attempt = attempt >= attempts;
+
+                let log_dump = runnable_image
+                    .log_dump()
+                    .unwrap_or_else(|| client.config.log_dump());
+                if is_last_attempt && log_dump.applies(true) {
+                    logs::dump_to_file(&client, &container_id).await;
+                }
+
+                // the container is never handed out, so it has to be cleaned up here
+                if !is_last_attempt || remove_policy.removes(true) {
+                    client.rm_if_exists(&container_id).await;
+                } else {
+                    removal::report_kept(&client, &container_id).await;
+                }
+                #[cfg(feature = "watchdog")]
+                crate::watchdog::unregister(&container_id);
+
+                if is_last_attempt {
+                    return Err(error.into());
+                }
+
+                log::warn!(
+                    "Container {container_id} did not become ready (attempt {attempt} of {attempts}), recreating it"
+                );
+                attempt += 1;
+            }
         }
+    };
 
-        let is_container_networked = runnable_image
-            .network()
-            .as_ref()
-            .map(|network| network.starts_with("container:"))
-            .unwrap_or(false);
+    let container = ContainerAsync::new(container_id, client.clone(), runnable_image, network);
+
+    for cmd in container
+        .image()
+        .exec_after_start(ContainerState::new(container.ports().await))
+    {
+        // the container is removed once it is dropped
+        container
+            .try_exec(&cmd)
+            .await
+            .map_err(|message| TestcontainersError::StartFailed {
+                container_id: container.id().to_string(),
+                message,
+            })?;
+    }
+
+    Ok(container)
+}
+
+/// Returns the options and the configuration the container of the image is created with.
+pub(crate) fn create_request<I: Image>(
+    runnable_image: &RunnableImage<I>,
+) -> Result<(Option<CreateContainerOptions<String>>, Config<String>), TestcontainersError> {
+    let invalid = |message: &str| TestcontainersError::InvalidConfiguration {
+        image: runnable_image.descriptor(),
+        message: message.to_string(),
+    };
+    if let Some(message) = runnable_image.env_file_errors().first() {
+        return Err(invalid(message));
+    }
+    let mut create_options: Option<CreateContainerOptions<String>> = None;
+
+    let extra_hosts: Vec<_> = runnable_image
+        .hosts()
+        .map(|(key, value)| format!("{key}:{value}"))
+        .collect();
+
+    let mut config: Config<String> = Config {
+        image: Some(runnable_image.descriptor()),
+        host_config: Some(HostConfig {
+            privileged: Some(runnable_image.privileged()),
+            extra_hosts: Some(extra_hosts),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    // shared memory
+    if let Some(bytes) = runnable_image.shm_size() {
+        config.host_config = config.host_config.map(|mut host_config| {
+            host_config.shm_size = Some(bytes as i64);
+            host_config
+        });
+    }
 
-        // exposed ports
-        if !is_container_networked {
-            config.exposed_ports = Some(
+    // network mode
+    if let Some(network_mode) = runnable_image.network_mode() {
+        // Docker Desktop runs containers in a virtual machine, whose network is not the one of
+        // the host
+        if network_mode == "host" && !cfg!(target_os = "linux") {
+            return Err(invalid("host networking is only supported on Linux"));
+        }
+        if runnable_image.network().is_some() {
+            return Err(invalid(
+                "a network mode can't be combined with a network, see `with_network`",
+            ));
+        }
+        config.host_config = config.host_config.map(|mut host_config| {
+            host_config.network_mode = Some(network_mode.to_string());
+            host_config
+        });
+    }
+
+    // network
+    if let Some(network) = runnable_image.network() {
+        config.host_config = config.host_config.map(|mut host_config| {
+            host_config.network_mode = Some(network.to_string());
+            host_config
+        });
+    }
+
+    // name and platform of the container
+    if runnable_image.container_name().is_some() || runnable_image.platform().is_some() {
+        create_options = Some(CreateContainerOptions {
+            name: runnable_image.container_name().clone().unwrap_or_default(),
+            platform: runnable_image.platform().map(ToString::to_string),
+        })
+    }
+
+    // handle environment variables
+    let envs: Vec<String> = runnable_image
+        .env_vars()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect();
+    config.env = Some(envs);
+
+    // volumes and mounts
+    let binds: Vec<String> = runnable_image
+        .volumes()
+        .map(|(orig, dest)| format!("{orig}:{dest}"))
+        .chain(runnable_image.mounts().iter().filter_map(Mount::bind_spec))
+        .collect();
+    if !binds.is_empty() {
+        config.host_config = config.host_config.map(|mut host_config| {
+            host_config.binds = Some(binds);
+            host_config
+        });
+    }
+    let tmpfs: HashMap<String, String> = runnable_image
+        .mounts()
+        .iter()
+        .filter_map(Mount::tmpfs_spec)
+        .collect();
+    if !tmpfs.is_empty() {
+        config.host_config = config.host_config.map(|mut host_config| {
+            host_config.tmpfs = Some(tmpfs);
+            host_config
+        });
+    }
+
+    // entrypoint
+    if let Some(entrypoint) = runnable_image.entrypoint_args() {
+        config.entrypoint = Some(entrypoint);
+    }
+
+    // ports can't be published if the container doesn't have its own network stack
+    let is_container_networked = runnable_image
+        .network_mode()
+        .into_iter()
+        .chain(runnable_image.network().as_deref())
+        .any(has_no_network_stack);
+
+    // exposed ports
+    if !is_container_networked {
+        config.exposed_ports = Some(
+            runnable_image
+                .expose_ports()
+                .into_iter()
+                .map(|p| (format!("{p}/tcp"), HashMap::new()))
+                .collect(),
+        );
+    }
+
+    // ports
+    if is_container_networked {
+        // the ports of the container are the ones of the network stack it uses
+    } else if runnable_image.ports().is_some() || !runnable_image.expose_ports().is_empty() {
+        let empty: Vec<_> = Vec::new();
+        let bindings = runnable_image
+            .ports()
+            .as_ref()
+            .unwrap_or(&empty)
+            .iter()
+            .map(|p| {
+                (
+                    format!("{}/tcp", p.internal),
+                    Some(vec![PortBinding {
+                        host_ip: None,
+                        host_port: Some(p.local.to_string()),
+                    }]),
+                )
+            })
+            .chain(
                 runnable_image
                     .expose_ports()
                     .into_iter()
-                    .map(|p| (format!("{p}/tcp"), HashMap::new()))
-                    .collect(),
+                    .map(|p| (format!("{}/tcp", p), Some(vec![PortBinding::default()]))),
             );
-        }
 
-        // ports
-        if runnable_image.ports().is_some() || !runnable_image.expose_ports().is_empty() {
-            let empty: Vec<_> = Vec::new();
-            let bindings = runnable_image
-                .ports()
-                .as_ref()
-                .unwrap_or(&empty)
-                .iter()
-                .map(|p| {
-                    (
-                        format!("{}/tcp", p.internal),
-                        Some(vec![PortBinding {
-                            host_ip: None,
-                            host_port: Some(p.local.to_string()),
-                        }]),
-                    )
-                })
-                .chain(
-                    runnable_image
-                        .expose_ports()
-                        .into_iter()
-                        .map(|p| (format!("{}/tcp", p), Some(vec![PortBinding::default()]))),
-                );
+        config.host_config = config.host_config.map(|mut host_config| {
+            host_config.port_bindings = Some(bindings.collect());
+            host_config
+        });
+    } else {
+        config.host_config = config.host_config.map(|mut host_config| {
+            host_config.publish_all_ports = Some(true);
+            host_config
+        });
+    }
 
-            config.host_config = config.host_config.map(|mut host_config| {
-                host_config.port_bindings = Some(bindings.collect());
-                host_config
-            });
-        } else if !is_container_networked {
-            config.host_config = config.host_config.map(|mut host_config| {
-                host_config.publish_all_ports = Some(true);
-                host_config
-            });
-        }
+    // user
+    if let Some(user) = runnable_image.user() {
+        config.user = Some(user.to_string());
+    }
+
+    // working directory
+    if let Some(working_dir) = runnable_image.working_dir() {
+        config.working_dir = Some(working_dir.to_string());
+    }
+
+    // labels, including the ones of the session, without the one of the reaper if the container
+    // may be kept
+    let kept = runnable_image
+        .remove_on_drop()
+        .is_some_and(|policy| policy != RemovePolicy::Always);
+    let labels: HashMap<String, String> = runnable_image
+        .labels()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .chain(session::labels().filter(|(key, _)| !(kept && key == session::REAP_LABEL)))
+        .collect();
+    config.labels = Some(labels);
+
+    // resource limits
+    config.host_config = config.host_config.map(|mut host_config| {
+        host_config.memory = runnable_image.memory_limit().map(|bytes| bytes as i64);
+        host_config.memory_swap = runnable_image.memory_swap();
+        host_config.nano_cpus = runnable_image
+            .cpus()
+            .map(|cpus| (cpus * 1_000_000_000.0) as i64);
+        host_config.cpu_shares = runnable_image.cpu_shares().map(|shares| shares as i64);
+        host_config
+    });
+
+    // ulimits
+    let ulimits: Vec<_> = runnable_image
+        .ulimits()
+        .map(|(name, (soft, hard))| ResourcesUlimits {
+            name: Some(name.clone()),
+            soft: Some(*soft),
+            hard: Some(*hard),
+        })
+        .collect();
+    if !ulimits.is_empty() {
+        config.host_config = config.host_config.map(|mut host_config| {
+            host_config.ulimits = Some(ulimits);
+            host_config
+        });
+    }
+
+    // capabilities
+    if !runnable_image.cap_add().is_empty() || !runnable_image.cap_drop().is_empty() {
+        config.host_config = config.host_config.map(|mut host_config| {
+            host_config.cap_add = Some(runnable_image.cap_add().to_vec());
+            host_config.cap_drop = Some(runnable_image.cap_drop().to_vec());
+            host_config
+        });
+    }
+
+    // security options
+    if !runnable_image.security_opts().is_empty() {
+        config.host_config = config.host_config.map(|mut host_config| {
+            host_config.security_opt = Some(runnable_image.security_opts().to_vec());
+            host_config
+        });
+    }
+
+    // read-only root filesystem
+    if runnable_image.readonly_rootfs() {
+        config.host_config = config.host_config.map(|mut host_config| {
+            host_config.readonly_rootfs = Some(true);
+            host_config
+        });
+    }
+
+    // devices
+    if !runnable_image.devices().is_empty() {
+        let devices = runnable_image
+            .devices()
+            .iter()
+            .map(|(host_path, container_path, permissions)| DeviceMapping {
+                path_on_host: Some(host_path.clone()),
+                path_in_container: Some(container_path.clone()),
+                cgroup_permissions: Some(permissions.clone()),
+            })
+            .collect();
+        config.host_config = config.host_config.map(|mut host_config| {
+            host_config.devices = Some(devices);
+            host_config
+        });
+    }
+
+    // network aliases and static IP addresses
+    let has_endpoint_settings = !runnable_image.network_aliases().is_empty()
+        || runnable_image.ipv4_address().is_some()
+        || runnable_image.ipv6_address().is_some();
+    if has_endpoint_settings {
+        let network = runnable_image.network().as_ref().ok_or_else(|| {
+            invalid("network aliases and static IP addresses require the container to join a network, see `with_network`")
+        })?;
+        let endpoint = EndpointSettings {
+            aliases: Some(runnable_image.network_aliases().to_vec()),
+            ipam_config: Some(EndpointIpamConfig {
+                ipv4_address: runnable_image.ipv4_address().map(|ip| ip.to_string()),
+                ipv6_address: runnable_image.ipv6_address().map(|ip| ip.to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        config.networking_config = Some(NetworkingConfig {
+            endpoints_config: HashMap::from([(network.clone(), endpoint)]),
+        });
+    }
+
+    // stop signal and timeout
+    if let Some(stop_signal) = runnable_image.stop_signal() {
+        config.stop_signal = Some(stop_signal.to_string());
+    }
+    if let Some(stop_timeout) = runnable_image.stop_timeout() {
+        config.stop_timeout = Some(stop_timeout.as_secs() as i64);
+    }
+
+    // healthcheck
+    if let Some(healthcheck) = runnable_image.healthcheck() {
+        config.healthcheck = Some(healthcheck.clone().into());
+    }
+
+    // logging driver
+    if let Some(log_driver) = runnable_image.log_driver() {
+        let log_config = HostConfigLogConfig {
+            typ: Some(log_driver.to_string()),
+            config: Some(
+                runnable_image
+                    .log_options()
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect(),
+            ),
+        };
+        config.host_config = config.host_config.map(|mut host_config| {
+            host_config.log_config = Some(log_config);
+            host_config
+        });
+    }
+
+    // GPUs
+    if let Some(gpus) = runnable_image.gpus() {
+        let device_request = gpu_device_request(gpus).map_err(|message| invalid(&message))?;
+        config.host_config = config.host_config.map(|mut host_config| {
+            host_config.device_requests = Some(vec![device_request]);
+            host_config
+        });
+    }
+
+    // sysctls
+    let sysctls: HashMap<String, String> = runnable_image
+        .sysctls()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    if !sysctls.is_empty() {
+        config.host_config = config.host_config.map(|mut host_config| {
+            host_config.sysctls = Some(sysctls);
+            host_config
+        });
+    }
+
+    // init process
+    if runnable_image.init() {
+        config.host_config = config.host_config.map(|mut host_config| {
+            host_config.init = Some(true);
+            host_config
+        });
+    }
+
+    // namespace modes
+    config.host_config = config.host_config.map(|mut host_config| {
+        host_config.pid_mode = runnable_image.pid_mode().map(ToString::to_string);
+        host_config.ipc_mode = runnable_image.ipc_mode().map(ToString::to_string);
+        host_config.userns_mode = runnable_image.userns_mode().map(ToString::to_string);
+        host_config
+    });
+
+    // supplementary groups
+    if !runnable_image.group_add().is_empty() {
+        config.host_config = config.host_config.map(|mut host_config| {
+            host_config.group_add = Some(runnable_image.group_add().to_vec());
+            host_config
+        });
+    }
 
-        // extra hosts
+    // cgroups
+    config.host_config = config.host_config.map(|mut host_config| {
+        host_config.cgroup_parent = runnable_image.cgroup_parent().map(ToString::to_string);
+        host_config.cgroupns_mode = runnable_image.cgroupns_mode().map(|mode| match mode {
+            CgroupnsMode::Private => HostConfigCgroupnsModeEnum::PRIVATE,
+            CgroupnsMode::Host => HostConfigCgroupnsModeEnum::HOST,
+        });
+        host_config
+    });
+
+    // auto remove
+    if runnable_image.auto_remove() {
+        config.host_config = config.host_config.map(|mut host_config| {
+            host_config.auto_remove = Some(true);
+            host_config
+        });
+    }
+
+    // OOM killer
+    if let Some(oom_score_adj) = runnable_image.oom_score_adj() {
+        if !(-1000..=1000).contains(&oom_score_adj) {
+            return Err(invalid(&format!(
+                "OOM score adjustment must be between -1000 and 1000, got {oom_score_adj}"
+            )));
+        }
+    }
+    config.host_config = config.host_config.map(|mut host_config| {
+        host_config.oom_kill_disable = runnable_image.oom_kill_disable().then_some(true);
+        host_config.oom_score_adj = runnable_image.oom_score_adj().map(i64::from);
+        host_config
+    });
 
+    // command
+    if let Some(cmd) = runnable_image.cmd() {
+        config.cmd = Some(cmd.to_vec());
+    } else {
         let args = runnable_image
             .args()
             .clone()
@@ -173,73 +753,98 @@ where
         if !args.is_empty() {
             config.cmd = Some(args);
         }
+    }
 
-        // create the container with options
-        let create_result = client
-            .create_container(create_options.clone(), config.clone())
-            .await;
-        let container_id = {
-            match create_result {
-                Ok(container) => container.id,
-                Err(bollard::errors::Error::DockerResponseServerError {
-                    status_code: 404, ..
-                }) => {
-                    client.pull_image(&runnable_image.descriptor()).await;
-                    client
-                        .bollard
-                        .create_container(create_options, config)
-                        .await
-                        .unwrap()
-                        .id
-                }
-                Err(err) => panic!("{}", err),
-            }
-        };
-
-        #[cfg(feature = "watchdog")]
-        if client.config.command() == crate::core::env::Command::Remove {
-            crate::watchdog::register(container_id.clone());
-        }
-
-        client
-            .bollard
-            .start_container::<String>(&container_id, None)
-            .await
-            .unwrap();
+    Ok((create_options, config))
+}
 
-        let container =
-            ContainerAsync::new(container_id, client.clone(), runnable_image, network).await;
+/// Whether a container with the network mode uses the network stack of the host or of another
+/// container, or has no network at all, so that none of its ports can be published.
+fn has_no_network_stack(network_mode: &str) -> bool {
+    network_mode == "host" || network_mode == "none" || network_mode.starts_with("container:")
+}
 
-        for cmd in container
-            .image()
-            .exec_after_start(ContainerState::new(container.ports().await))
-        {
-            container.exec(cmd).await;
-        }
+/// Converts GPUs in the format of `docker run --gpus` into a device request.
+fn gpu_device_request(gpus: &str) -> Result<DeviceRequest, String> {
+    let mut device_request = DeviceRequest {
+        driver: Some("nvidia".to_string()),
+        capabilities: Some(vec![vec!["gpu".to_string()]]),
+        ..Default::default()
+    };
 
-        container
+    if gpus == "all" {
+        device_request.count = Some(-1);
+    } else if let Some(device_ids) = gpus.strip_prefix("device=") {
+        device_request.device_ids = Some(device_ids.split(',').map(ToString::to_string).collect());
+    } else if let Ok(count) = gpus.parse() {
+        device_request.count = Some(count);
+    } else {
+        return Err(format!(
+            "invalid GPU request '{gpus}', expected 'all', a number or 'device=<ids>'"
+        ));
     }
 
-    async fn pull_image(self) -> RunnableImage<I> {
-        let runnable_image = self.into();
-        let client = Client::lazy_client().await;
-        client.pull_image(&runnable_image.descriptor()).await;
-
-        runnable_image
-    }
+    Ok(device_request)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{core::WaitFor, images::generic::GenericImage};
+    use std::time::Duration;
+
+    #[test]
+    fn ports_are_only_published_with_own_network_stack() {
+        let image = GenericImage::new("simple_web_server", "latest").with_exposed_port(5000);
+        let port_bindings = |network_mode: &str| {
+            let image = RunnableImage::from(image.clone()).with_network_mode(network_mode);
+            let (_, config) = create_request(&image).unwrap();
+            config.host_config.unwrap().port_bindings
+        };
+
+        assert!(port_bindings("bridge").unwrap().contains_key("5000/tcp"));
+        assert!(port_bindings("my-network")
+            .unwrap()
+            .contains_key("5000/tcp"));
+        assert_eq!(port_bindings("none"), None);
+        assert_eq!(port_bindings("container:redis"), None);
+        #[cfg(target_os = "linux")]
+        assert_eq!(port_bindings("host"), None);
+    }
+
+    #[test]
+    fn network_settings_that_cant_be_combined_are_invalid() {
+        let image = RunnableImage::from(GenericImage::new("hello-world", "latest"));
+
+        assert!(matches!(
+            create_request(&image.clone().with_network_alias("hello")),
+            Err(TestcontainersError::InvalidConfiguration { .. })
+        ));
+        assert!(matches!(
+            create_request(&image.with_network_mode("none").with_network("my-network")),
+            Err(TestcontainersError::InvalidConfiguration { .. })
+        ));
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    #[test]
+    fn host_network_mode_is_invalid_outside_of_linux() {
+        let image = RunnableImage::from(GenericImage::new("hello-world", "latest"))
+            .with_network_mode("host");
+
+        assert!(matches!(
+            create_request(&image),
+            Err(TestcontainersError::InvalidConfiguration { .. })
+        ));
+    }
 
     #[tokio::test]
     async fn async_run_command_should_expose_all_ports_if_no_explicit_mapping_requested() {
         let client = Client::lazy_client().await;
         let container = RunnableImage::from(GenericImage::new("hello-world", "latest"))
             .start()
-            .await;
+            .await
+            .unwrap();
 
         // inspect volume and env
         let container_details = client.inspect(container.id()).await;
@@ -257,7 +862,7 @@ mod tests {
             .with_exposed_port(5000)
             .with_wait_for(WaitFor::message_on_stdout("server is ready"))
             .with_wait_for(WaitFor::seconds(1));
-        let container = image.start().await;
+        let container = image.start().await.unwrap();
         container.get_host_port_ipv4(5000).await;
     }
 
@@ -269,7 +874,8 @@ mod tests {
             .with_mapped_port((123, 456))
             .with_mapped_port((555, 888))
             .start()
-            .await;
+            .await
+            .unwrap();
 
         let container_details = client.inspect(container.id()).await;
 
@@ -289,7 +895,8 @@ mod tests {
         let container = RunnableImage::from(image)
             .with_network("awesome-net-1")
             .start()
-            .await;
+            .await
+            .unwrap();
 
         let container_details = client.inspect(container.id()).await;
         let networks = container_details
@@ -311,7 +918,8 @@ mod tests {
         let container = RunnableImage::from(image)
             .with_container_name("async_hello_container")
             .start()
-            .await;
+            .await
+            .unwrap();
 
         let container_details = client.inspect(container.id()).await;
         let container_name = container_details.name.unwrap();
@@ -324,27 +932,29 @@ mod tests {
 
         {
             let client = Client::lazy_client().await;
-            assert!(!client.network_exists("awesome-net-2").await);
+            assert!(!client.network_exists("awesome-net-2").await.unwrap());
 
             // creating the first container creates the network
             let _container1 = RunnableImage::from(hello_world.clone())
                 .with_network("awesome-net-2")
                 .start()
-                .await;
+                .await
+                .unwrap();
 
             // creating a 2nd container doesn't fail because check if the network exists already
             let _container2 = RunnableImage::from(hello_world)
                 .with_network("awesome-net-2")
                 .start()
-                .await;
+                .await
+                .unwrap();
 
-            assert!(client.network_exists("awesome-net-2").await);
+            assert!(client.network_exists("awesome-net-2").await.unwrap());
         }
 
         // containers have been dropped, should clean up networks
         tokio::time::sleep(std::time::Duration::from_secs(1)).await;
         let client = Client::lazy_client().await;
-        assert!(!client.network_exists("awesome-net-2").await)
+        assert!(!client.network_exists("awesome-net-2").await.unwrap())
     }
 
     #[tokio::test]
@@ -354,7 +964,8 @@ mod tests {
         let container = RunnableImage::from(image)
             .with_shm_size(1_000_000)
             .start()
-            .await;
+            .await
+            .unwrap();
 
         let container_details = client.inspect(container.id()).await;
         let shm_size = container_details.host_config.unwrap().shm_size.unwrap();
@@ -368,7 +979,8 @@ mod tests {
         let container = RunnableImage::from(image)
             .with_privileged(true)
             .start()
-            .await;
+            .await
+            .unwrap();
 
         let client = Client::lazy_client().await;
         let container_details = client.inspect(container.id()).await;
@@ -376,4 +988,583 @@ mod tests {
         let privileged = container_details.host_config.unwrap().privileged.unwrap();
         assert!(privileged, "privileged must be `true`");
     }
+
+    #[tokio::test]
+    async fn async_run_command_should_override_cmd() {
+        let image = GenericImage::new("hello-world", "latest");
+        let container = RunnableImage::from(image)
+            .with_cmd(["/hello", "--ignored"])
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        let cmd = container_details.config.unwrap().cmd.unwrap();
+        assert_eq!(cmd, vec!["/hello", "--ignored"]);
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_set_user() {
+        let image = GenericImage::new("hello-world", "latest");
+        let container = RunnableImage::from(image)
+            .with_user("1000:1000")
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        let user = container_details.config.unwrap().user.unwrap();
+        assert_eq!(user, "1000:1000");
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_set_working_dir() {
+        let image = GenericImage::new("hello-world", "latest");
+        let container = RunnableImage::from(image)
+            .with_working_dir("/tmp")
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        let working_dir = container_details.config.unwrap().working_dir.unwrap();
+        assert_eq!(working_dir, "/tmp");
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_include_labels() {
+        let image = GenericImage::new("hello-world", "latest");
+        let container = RunnableImage::from(image)
+            .with_label("com.example.suite", "integration")
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        let labels = container_details.config.unwrap().labels.unwrap();
+        assert_eq!(
+            labels.get("com.example.suite").map(String::as_str),
+            Some("integration")
+        );
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_set_resource_limits() {
+        let image = GenericImage::new("hello-world", "latest");
+        let container = RunnableImage::from(image)
+            .with_memory_limit(64 * 1024 * 1024)
+            .with_memory_swap(128 * 1024 * 1024)
+            .with_cpus(0.5)
+            .with_cpu_shares(512)
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        let host_config = container_details.host_config.unwrap();
+        assert_eq!(host_config.memory, Some(64 * 1024 * 1024));
+        assert_eq!(host_config.memory_swap, Some(128 * 1024 * 1024));
+        assert_eq!(host_config.nano_cpus, Some(500_000_000));
+        assert_eq!(host_config.cpu_shares, Some(512));
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_set_ulimits() {
+        let image = GenericImage::new("hello-world", "latest");
+        let container = RunnableImage::from(image)
+            .with_ulimit("nofile", 65536, 65536)
+            .with_ulimit("memlock", -1, -1)
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        let ulimits = container_details.host_config.unwrap().ulimits.unwrap();
+        assert!(ulimits.contains(&ResourcesUlimits {
+            name: Some("nofile".to_string()),
+            soft: Some(65536),
+            hard: Some(65536),
+        }));
+        assert!(ulimits.contains(&ResourcesUlimits {
+            name: Some("memlock".to_string()),
+            soft: Some(-1),
+            hard: Some(-1),
+        }));
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_set_capabilities() {
+        let image = GenericImage::new("hello-world", "latest");
+        let container = RunnableImage::from(image)
+            .with_cap_add("NET_ADMIN")
+            .with_cap_drop("MKNOD")
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        let host_config = container_details.host_config.unwrap();
+        assert_eq!(host_config.cap_add, Some(vec!["NET_ADMIN".to_string()]));
+        assert_eq!(host_config.cap_drop, Some(vec!["MKNOD".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_set_security_opts() {
+        let image = GenericImage::new("hello-world", "latest");
+        let container = RunnableImage::from(image)
+            .with_security_opt("seccomp=unconfined")
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        let security_opt = container_details.host_config.unwrap().security_opt.unwrap();
+        assert_eq!(security_opt, vec!["seccomp=unconfined".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_set_readonly_rootfs() {
+        let image = GenericImage::new("hello-world", "latest");
+        let container = RunnableImage::from(image)
+            .with_readonly_rootfs(true)
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        let readonly_rootfs = container_details.host_config.unwrap().readonly_rootfs;
+        assert_eq!(readonly_rootfs, Some(true));
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_map_devices() {
+        let image = GenericImage::new("hello-world", "latest");
+        let container = RunnableImage::from(image)
+            .with_device("/dev/null", "/dev/test-null", "rw")
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        let devices = container_details.host_config.unwrap().devices.unwrap();
+        assert_eq!(
+            devices,
+            vec![DeviceMapping {
+                path_on_host: Some("/dev/null".to_string()),
+                path_in_container: Some("/dev/test-null".to_string()),
+                cgroup_permissions: Some("rw".to_string()),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_include_network_aliases() {
+        let client = Client::lazy_client().await;
+        let image = GenericImage::new("hello-world", "latest");
+        let container = RunnableImage::from(image)
+            .with_network("awesome-net-3")
+            .with_network_alias("hello")
+            .with_network_alias("world")
+            .start()
+            .await
+            .unwrap();
+
+        let container_details = client.inspect(container.id()).await;
+        let aliases = container_details
+            .network_settings
+            .unwrap()
+            .networks
+            .unwrap()
+            .remove("awesome-net-3")
+            .unwrap()
+            .aliases
+            .unwrap();
+
+        assert!(
+            aliases.contains(&"hello".to_string()),
+            "Aliases are {aliases:?}"
+        );
+        assert!(
+            aliases.contains(&"world".to_string()),
+            "Aliases are {aliases:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_assign_static_ip_address() {
+        let client = Client::lazy_client().await;
+        let image = GenericImage::new("simple_web_server", "latest")
+            .with_wait_for(WaitFor::message_on_stdout("server is ready"));
+        let container = RunnableImage::from(image)
+            .with_network("awesome-net-4")
+            .with_network_subnet("172.31.254.0/24")
+            .with_ip_address("172.31.254.10".parse().unwrap())
+            .start()
+            .await
+            .unwrap();
+
+        let container_details = client.inspect(container.id()).await;
+        let ip_address = container_details
+            .network_settings
+            .unwrap()
+            .networks
+            .unwrap()
+            .remove("awesome-net-4")
+            .unwrap()
+            .ip_address;
+
+        assert_eq!(ip_address.as_deref(), Some("172.31.254.10"));
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_use_platform() {
+        let image = GenericImage::new("hello-world", "latest");
+        let container = RunnableImage::from(image)
+            .with_platform("linux/amd64")
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        assert_eq!(container_details.platform.as_deref(), Some("linux"));
+        let image_details = client
+            .bollard
+            .inspect_image(&container_details.image.unwrap())
+            .await
+            .unwrap();
+        assert_eq!(image_details.architecture.as_deref(), Some("amd64"));
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_set_stop_signal_and_timeout() {
+        let image = GenericImage::new("hello-world", "latest");
+        let container = RunnableImage::from(image)
+            .with_stop_signal("SIGINT")
+            .with_stop_timeout(Duration::from_secs(2))
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        let config = container_details.config.unwrap();
+        assert_eq!(config.stop_signal.as_deref(), Some("SIGINT"));
+        assert_eq!(config.stop_timeout, Some(2));
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_set_log_driver() {
+        let image = GenericImage::new("hello-world", "latest");
+        let container = RunnableImage::from(image)
+            .with_log_driver("json-file", [("max-size", "1m"), ("max-file", "2")])
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        let log_config = container_details.host_config.unwrap().log_config.unwrap();
+        assert_eq!(log_config.typ.as_deref(), Some("json-file"));
+        let options = log_config.config.unwrap();
+        assert_eq!(options.get("max-size").map(String::as_str), Some("1m"));
+        assert_eq!(options.get("max-file").map(String::as_str), Some("2"));
+    }
+
+    #[test]
+    fn gpu_device_request_should_parse_gpus() {
+        let all = gpu_device_request("all").unwrap();
+        assert_eq!(all.driver.as_deref(), Some("nvidia"));
+        assert_eq!(all.count, Some(-1));
+        assert_eq!(all.capabilities, Some(vec![vec!["gpu".to_string()]]));
+
+        assert_eq!(gpu_device_request("2").unwrap().count, Some(2));
+
+        let devices = gpu_device_request("device=0,1").unwrap();
+        assert_eq!(devices.count, None);
+        assert_eq!(
+            devices.device_ids,
+            Some(vec!["0".to_string(), "1".to_string()])
+        );
+    }
+
+    #[test]
+    fn invalid_gpus_fail_to_start() {
+        let image =
+            RunnableImage::from(GenericImage::new("hello-world", "latest")).with_gpus("some");
+
+        let err = create_request(&image).unwrap_err();
+        assert!(
+            err.to_string().contains("invalid GPU request 'some'"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn out_of_range_oom_score_adj_is_invalid() {
+        let image = RunnableImage::from(GenericImage::new("hello-world", "latest"))
+            .with_oom_score_adj(1001);
+
+        let err = image.plan().unwrap_err();
+        assert!(
+            matches!(
+                &err,
+                TestcontainersError::InvalidConfiguration { message, .. }
+                    if message.contains("between -1000 and 1000, got 1001")
+            ),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_set_sysctls() {
+        let image = GenericImage::new("hello-world", "latest");
+        let container = RunnableImage::from(image)
+            .with_sysctl("net.core.somaxconn", "4096")
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        let sysctls = container_details.host_config.unwrap().sysctls.unwrap();
+        assert_eq!(
+            sysctls.get("net.core.somaxconn").map(String::as_str),
+            Some("4096")
+        );
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_include_init() {
+        let image = GenericImage::new("hello-world", "latest");
+        let container = RunnableImage::from(image)
+            .with_init(true)
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        let init = container_details.host_config.unwrap().init;
+        assert_eq!(init, Some(true));
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_set_namespace_modes() {
+        let image = GenericImage::new("hello-world", "latest");
+        let container = RunnableImage::from(image)
+            .with_pid_mode("host")
+            .with_ipc_mode("private")
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        let host_config = container_details.host_config.unwrap();
+        assert_eq!(host_config.pid_mode.as_deref(), Some("host"));
+        assert_eq!(host_config.ipc_mode.as_deref(), Some("private"));
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_include_mounts() {
+        let image = GenericImage::new("hello-world", "latest");
+        let container = RunnableImage::from(image)
+            .with_mount(Mount::bind_ro("/tmp", "/host-tmp"))
+            .with_mount(Mount::tmpfs("/scratch").with_tmpfs_size(1024 * 1024))
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        let host_config = container_details.host_config.unwrap();
+        assert_eq!(
+            host_config.binds,
+            Some(vec!["/tmp:/host-tmp:ro".to_string()])
+        );
+        let tmpfs = host_config.tmpfs.unwrap();
+        assert_eq!(
+            tmpfs.get("/scratch").map(String::as_str),
+            Some("size=1048576")
+        );
+    }
+
+    #[test]
+    fn overridden_entrypoint_keeps_its_arguments() {
+        let image = RunnableImage::from(GenericImage::new("simple_web_server", "latest"))
+            .with_entrypoint(["sh", "-c"]);
+
+        assert_eq!(image.entrypoint(), Some("sh".to_string()));
+        let (_, config) = create_request(&image).unwrap();
+        assert_eq!(
+            config.entrypoint,
+            Some(vec!["sh".to_string(), "-c".to_string()])
+        );
+    }
+
+    #[test]
+    fn missing_env_file_is_invalid() {
+        let image = RunnableImage::from(GenericImage::new("simple_web_server", "latest"))
+            .with_env_file("/nonexistent/testcontainers.env");
+
+        let err = create_request(&image).unwrap_err();
+        assert!(
+            matches!(
+                &err,
+                TestcontainersError::InvalidConfiguration { message, .. }
+                    if message.starts_with("failed to read env file /nonexistent/testcontainers.env")
+            ),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn containers_that_may_be_kept_are_not_reaped() {
+        let has_reap_label = |image: RunnableImage<GenericImage>| {
+            let (_, config) = create_request(&image).unwrap();
+            config.labels.unwrap().contains_key(session::REAP_LABEL)
+        };
+        let image = RunnableImage::from(GenericImage::new("simple_web_server", "latest"));
+
+        assert!(has_reap_label(image.clone()));
+        assert!(has_reap_label(
+            image.clone().with_remove_on_drop(RemovePolicy::Always)
+        ));
+        assert!(!has_reap_label(
+            image.clone().with_remove_on_drop(RemovePolicy::OnSuccess)
+        ));
+        assert!(!has_reap_label(
+            image.with_remove_on_drop(RemovePolicy::Never)
+        ));
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_override_entrypoint() {
+        let image = GenericImage::new("simple_web_server", "latest");
+        let container = RunnableImage::from(image)
+            .with_entrypoint(["./bar"])
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        let entrypoint = container_details.config.unwrap().entrypoint.unwrap();
+        assert_eq!(entrypoint, vec!["./bar"]);
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_add_groups() {
+        let image = GenericImage::new("hello-world", "latest");
+        let container = RunnableImage::from(image)
+            .with_group_add("999")
+            .with_group_add("audio")
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        let group_add = container_details.host_config.unwrap().group_add.unwrap();
+        assert_eq!(group_add, vec!["999".to_string(), "audio".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_set_cgroupns_mode() {
+        let image = GenericImage::new("hello-world", "latest");
+        let container = RunnableImage::from(image)
+            .with_cgroupns_mode(CgroupnsMode::Private)
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        let cgroupns_mode = container_details.host_config.unwrap().cgroupns_mode;
+        assert_eq!(cgroupns_mode, Some(HostConfigCgroupnsModeEnum::PRIVATE));
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_set_auto_remove() {
+        let image = GenericImage::new("simple_web_server", "latest")
+            .with_wait_for(WaitFor::message_on_stdout("server is ready"));
+        let container = RunnableImage::from(image)
+            .with_auto_remove(true)
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        let auto_remove = container_details.host_config.unwrap().auto_remove;
+        assert_eq!(auto_remove, Some(true));
+    }
+
+    #[tokio::test]
+    async fn async_run_command_should_set_oom_score_adj() {
+        let image = GenericImage::new("hello-world", "latest");
+        let container = RunnableImage::from(image)
+            .with_oom_score_adj(500)
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        let oom_score_adj = container_details.host_config.unwrap().oom_score_adj;
+        assert_eq!(oom_score_adj, Some(500));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn async_run_command_should_use_host_network_mode() {
+        let image = GenericImage::new("simple_web_server", "latest")
+            .with_wait_for(WaitFor::message_on_stdout("server is ready"));
+        let container = RunnableImage::from(image)
+            .with_network_mode("host")
+            .start()
+            .await
+            .unwrap();
+
+        let client = Client::lazy_client().await;
+        let container_details = client.inspect(container.id()).await;
+
+        let network_mode = container_details.host_config.unwrap().network_mode;
+        assert_eq!(network_mode.as_deref(), Some("host"));
+        // ports are not mapped with host networking
+        assert_eq!(container.get_host_port_ipv4(80).await, 80);
+    }
 }
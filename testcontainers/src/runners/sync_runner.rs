@@ -1,4 +1,4 @@
-use crate::{Container, Image, RunnableImage};
+use crate::{core::TestcontainersError, Container, Image, RunnableImage};
 
 /// Helper trait to start containers synchronously.
 ///
@@ -11,16 +11,22 @@ use crate::{Container, Image, RunnableImage};
 ///     let container = GenericImage::new("redis", "7.2.4")
 ///         .with_exposed_port(6379)
 ///         .with_wait_for(WaitFor::message_on_stdout("Ready to accept connections"))
-///         .start();
+///         .start()
+///         .unwrap();
 /// }
 /// ```
 pub trait SyncRunner<I: Image> {
     /// Starts the container and returns an instance of `Container`.
-    fn start(self) -> Container<I>;
+    ///
+    /// Returns an error if the image can't be pulled, the container can't be created or
+    /// started, or if it doesn't become ready. If the `TESTCONTAINERS_DRY_RUN` environment
+    /// variable is set, no container is started and [`TestcontainersError::DryRun`] is returned
+    /// with the plan of the container instead.
+    fn start(self) -> Result<Container<I>, TestcontainersError>;
 
     /// Pulls the image from the registry.
     /// Useful if you want to pull the image before starting the container.
-    fn pull_image(self) -> RunnableImage<I>;
+    fn pull_image(self) -> Result<RunnableImage<I>, TestcontainersError>;
 }
 
 impl<T, I> SyncRunner<I> for T
@@ -28,20 +34,39 @@ where
     T: Into<RunnableImage<I>> + Send,
     I: Image,
 {
-    fn start(self) -> Container<I> {
+    fn start(self) -> Result<Container<I>, TestcontainersError> {
         let runtime = build_sync_runner();
-        let async_container = runtime.block_on(super::AsyncRunner::start(self));
+        let async_container = runtime.block_on(super::AsyncRunner::start(self))?;
 
-        Container::new(runtime, async_container)
+        Ok(Container::new(runtime, async_container))
     }
 
-    fn pull_image(self) -> RunnableImage<I> {
+    fn pull_image(self) -> Result<RunnableImage<I>, TestcontainersError> {
         let runtime = build_sync_runner();
         runtime.block_on(super::AsyncRunner::pull_image(self))
     }
 }
 
-fn build_sync_runner() -> tokio::runtime::Runtime {
+/// Starts all containers concurrently and returns them in the order of the images, see
+/// [`start_all`].
+///
+/// [`start_all`]: crate::runners::start_all
+pub fn start_all_blocking<T, I>(
+    images: impl IntoIterator<Item = T>,
+) -> Result<Vec<Container<I>>, TestcontainersError>
+where
+    T: Into<RunnableImage<I>> + Send,
+    I: Image,
+{
+    let async_containers = build_sync_runner().block_on(super::start_all(images))?;
+
+    Ok(async_containers
+        .into_iter()
+        .map(|async_container| Container::new(build_sync_runner(), async_container))
+        .collect())
+}
+
+pub(crate) fn build_sync_runner() -> tokio::runtime::Runtime {
     tokio::runtime::Builder::new_current_thread()
         .enable_all()
         .build()
@@ -81,7 +106,7 @@ mod tests {
     }
 
     fn network_exists(client: &Arc<Client>, name: &str) -> bool {
-        runtime().block_on(client.network_exists(name))
+        runtime().block_on(client.network_exists(name)).unwrap()
     }
 
     #[derive(Default)]
@@ -116,7 +141,9 @@ mod tests {
 
     #[test]
     fn sync_run_command_should_expose_all_ports_if_no_explicit_mapping_requested() {
-        let container = RunnableImage::from(GenericImage::new("hello-world", "latest")).start();
+        let container = RunnableImage::from(GenericImage::new("hello-world", "latest"))
+            .start()
+            .unwrap();
 
         // inspect volume and env
         let container_details = inspect(container.id());
@@ -134,7 +161,7 @@ mod tests {
             .with_exposed_port(5000)
             .with_wait_for(WaitFor::message_on_stdout("server is ready"))
             .with_wait_for(WaitFor::seconds(1));
-        let container = image.start();
+        let container = image.start().unwrap();
         container.get_host_port_ipv4(5000);
     }
 
@@ -144,7 +171,8 @@ mod tests {
         let container = RunnableImage::from(image)
             .with_mapped_port((123, 456))
             .with_mapped_port((555, 888))
-            .start();
+            .start()
+            .unwrap();
 
         let container_details = inspect(container.id());
 
@@ -171,7 +199,8 @@ mod tests {
         let image = GenericImage::new("hello-world", "latest");
         let container = RunnableImage::from(image)
             .with_network("sync-awesome-net-1")
-            .start();
+            .start()
+            .unwrap();
 
         let container_details = inspect(container.id());
         let networks = container_details
@@ -191,7 +220,8 @@ mod tests {
         let image = GenericImage::new("hello-world", "latest");
         let container = RunnableImage::from(image)
             .with_container_name("sync_hello_container")
-            .start();
+            .start()
+            .unwrap();
 
         let container_details = inspect(container.id());
         let container_name = container_details.name.unwrap();
@@ -203,18 +233,23 @@ mod tests {
         let _first_container =
             RunnableImage::from(GenericImage::new("simple_web_server", "latest"))
                 .with_container_name("the_first_one")
-                .start();
+                .start()
+                .unwrap();
 
         let image = GenericImage::new("hello-world", "latest");
         RunnableImage::from(image)
             .with_network("container:the_first_one")
-            .start();
+            .start()
+            .unwrap();
     }
 
     #[test]
     fn sync_run_command_should_include_privileged() {
         let image = GenericImage::new("hello-world", "latest");
-        let container = RunnableImage::from(image).with_privileged(true).start();
+        let container = RunnableImage::from(image)
+            .with_privileged(true)
+            .start()
+            .unwrap();
         let container_details = inspect(container.id());
 
         let privileged = container_details.host_config.unwrap().privileged.unwrap();
@@ -224,7 +259,10 @@ mod tests {
     #[test]
     fn sync_run_command_should_set_shared_memory_size() {
         let image = GenericImage::new("hello-world", "latest");
-        let container = RunnableImage::from(image).with_shm_size(1_000_000).start();
+        let container = RunnableImage::from(image)
+            .with_shm_size(1_000_000)
+            .start()
+            .unwrap();
 
         let container_details = inspect(container.id());
         let shm_size = container_details.host_config.unwrap().shm_size.unwrap();
@@ -242,11 +280,13 @@ mod tests {
             // creating the first container creates the network
             let _container1: Container<HelloWorld> = RunnableImage::from(HelloWorld::default())
                 .with_network("sync-awesome-net")
-                .start();
+                .start()
+                .unwrap();
             // creating a 2nd container doesn't fail because check if the network exists already
             let _container2 = RunnableImage::from(HelloWorld::default())
                 .with_network("sync-awesome-net")
-                .start();
+                .start()
+                .unwrap();
 
             assert!(network_exists(&client, "sync-awesome-net"));
         }
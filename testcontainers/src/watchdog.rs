@@ -24,8 +24,8 @@ static WATCHDOG: Lazy<Mutex<Watchdog>> = Lazy::new(|| {
                     .map(|s| s.containers.clone())
                     .unwrap_or_default()
                 {
-                    signal_docker.stop(&container_id).await;
-                    signal_docker.rm(&container_id).await;
+                    signal_docker.stop(&container_id, None).await;
+                    signal_docker.rm_if_exists(&container_id).await;
                 }
 
                 let _ = signal_hook::low_level::emulate_default_handler(signal);
@@ -1,6 +1,16 @@
 use bollard::Docker;
+use futures::{FutureExt, StreamExt};
 use std::time::Duration;
-use testcontainers::{core::WaitFor, runners::AsyncRunner, GenericImage, *};
+use testcontainers::{
+    cleanup, compose,
+    core::{
+        ContainerEventKind, ExecCommand, FileChange, FileChangeKind, HealthState, Healthcheck,
+        LogDump, LogLine, LogSource, Network, PortError, RemovePolicy, ResourceLimits, Signal,
+        StartupError, StartupFailure, TestcontainersError, Volume, WaitFor,
+    },
+    runners::{self, AsyncRunner, AsyncTupleRunner},
+    GenericImage, *,
+};
 
 #[derive(Debug, Default)]
 pub struct HelloWorld;
@@ -25,7 +35,7 @@ impl Image for HelloWorld {
 async fn bollard_can_run_hello_world_with_multi_thread() {
     let _ = pretty_env_logger::try_init();
 
-    let _container = HelloWorld.start().await;
+    let _container = HelloWorld.start().await.unwrap();
 }
 
 async fn cleanup_hello_world_image() {
@@ -50,7 +60,7 @@ async fn cleanup_hello_world_image() {
 async fn bollard_pull_missing_image_hello_world() {
     let _ = pretty_env_logger::try_init();
     cleanup_hello_world_image().await;
-    let _container = RunnableImage::from(HelloWorld).start().await;
+    let _container = RunnableImage::from(HelloWorld).start().await.unwrap();
 }
 
 #[tokio::test]
@@ -60,8 +70,10 @@ async fn explicit_call_to_pull_missing_image_hello_world() {
     let _container = RunnableImage::from(HelloWorld)
         .pull_image()
         .await
+        .unwrap()
         .start()
-        .await;
+        .await
+        .unwrap();
 }
 
 #[tokio::test]
@@ -81,5 +93,2082 @@ async fn start_containers_in_parallel() {
     // actually, we should be finishing in 2 seconds but that is too unstable
     // a sequential start would mean 8 seconds, hence 5 seconds proves some form of parallelism
     let timeout = Duration::from_secs(5);
-    let _containers = tokio::time::timeout(timeout, run_all).await.unwrap();
+    let containers = tokio::time::timeout(timeout, run_all).await.unwrap();
+    assert!(containers.iter().all(Result::is_ok));
+}
+
+#[tokio::test]
+async fn start_all_starts_containers_in_parallel() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"))
+        .with_wait_for(WaitFor::seconds(2));
+
+    // a sequential start would take at least 6 seconds
+    let timeout = Duration::from_secs(5);
+    let containers = tokio::time::timeout(
+        timeout,
+        runners::start_all(vec![image.clone(), image.clone(), image]),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(containers.len(), 3);
+    for container in &containers {
+        assert!(container.inspect().await.state.running);
+    }
+}
+
+#[tokio::test]
+async fn start_all_starts_containers_of_different_images() {
+    let _ = pretty_env_logger::try_init();
+
+    let web_server = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"))
+        .with_wait_for(WaitFor::seconds(2));
+
+    // a sequential start would take at least 4 seconds
+    let timeout = Duration::from_secs(3);
+    let (first, second, hello_world) = tokio::time::timeout(
+        timeout,
+        (web_server.clone(), web_server, HelloWorld).start_all(),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert!(first.inspect().await.state.running);
+    assert!(second.inspect().await.state.running);
+    assert_eq!(hello_world.image().name(), "hello-world");
+}
+
+#[tokio::test]
+async fn start_all_removes_started_containers_if_one_fails() {
+    let _ = pretty_env_logger::try_init();
+
+    let label = "org.testcontainers.test=start_all_removes_started_containers_if_one_fails";
+    let (key, value) = label.split_once('=').unwrap();
+    let images = vec![
+        RunnableImage::from(
+            GenericImage::new("simple_web_server", "latest")
+                .with_wait_for(WaitFor::message_on_stdout("server is ready")),
+        )
+        .with_label(key, value),
+        RunnableImage::from(GenericImage::new(
+            "testcontainers/this-image-does-not-exist",
+            "latest",
+        ))
+        .with_label(key, value),
+    ];
+
+    let err = runners::start_all(images).await.unwrap_err();
+    assert!(
+        matches!(err, TestcontainersError::PullFailed { .. }),
+        "expected a pull error, got: {err}"
+    );
+
+    let containers = Docker::connect_with_unix_defaults()
+        .unwrap()
+        .list_containers(Some(bollard::container::ListContainersOptions {
+            all: true,
+            filters: std::collections::HashMap::from([("label", vec![label])]),
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+    assert!(
+        containers.is_empty(),
+        "containers were left: {containers:?}"
+    );
+}
+
+#[tokio::test]
+async fn containers_are_labeled_with_session() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"));
+    let first = image.clone().start().await.unwrap();
+    let second = image.start().await.unwrap();
+
+    let labels = first.inspect().await.config.labels;
+    assert_eq!(
+        labels.get("org.testcontainers").map(String::as_str),
+        Some("true")
+    );
+    let session_id = &labels["org.testcontainers.session-id"];
+    assert_eq!(
+        &second.inspect().await.config.labels["org.testcontainers.session-id"],
+        session_id
+    );
+}
+
+#[cfg(feature = "reaper")]
+#[tokio::test]
+async fn reaper_is_started_once_per_session() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"));
+    let first = image.clone().start().await.unwrap();
+    let _second = image.start().await.unwrap();
+
+    let labels = first.inspect().await.config.labels;
+    let reaper_name = format!(
+        "testcontainers-ryuk-{}",
+        labels["org.testcontainers.session-id"]
+    );
+    let reapers = Docker::connect_with_unix_defaults()
+        .unwrap()
+        .list_containers(Some(bollard::container::ListContainersOptions {
+            filters: std::collections::HashMap::from([
+                ("label", vec!["org.testcontainers.ryuk=true"]),
+                ("name", vec![reaper_name.as_str()]),
+            ]),
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+    assert_eq!(reapers.len(), 1, "reapers: {reapers:?}");
+}
+
+#[tokio::test]
+async fn cleanup_removes_resources_of_stale_sessions() {
+    let _ = pretty_env_logger::try_init();
+
+    let docker = Docker::connect_with_unix_defaults().unwrap();
+    let stale_session = ulid::Ulid::from_datetime(
+        std::time::SystemTime::now() - Duration::from_secs(2 * 24 * 60 * 60),
+    )
+    .to_string()
+    .to_lowercase();
+    let labels = std::collections::HashMap::from([
+        ("org.testcontainers", "true"),
+        ("org.testcontainers.session-id", stale_session.as_str()),
+    ]);
+    let leaked = docker
+        .create_container::<String, _>(
+            None,
+            bollard::container::Config {
+                image: Some("simple_web_server:latest"),
+                labels: Some(labels.clone()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap()
+        .id;
+    let volume = format!("leaked-{stale_session}");
+    docker
+        .create_volume(bollard::volume::CreateVolumeOptions {
+            name: volume.as_str(),
+            labels: labels.clone(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let current = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"))
+        .start()
+        .await
+        .unwrap();
+
+    let removed = cleanup::remove_stale(Duration::from_secs(24 * 60 * 60))
+        .await
+        .unwrap();
+    assert!(removed.containers.contains(&leaked), "removed: {removed:?}");
+    assert!(removed.volumes.contains(&volume), "removed: {removed:?}");
+    assert!(!removed.containers.contains(&current.id().to_string()));
+    assert!(current.inspect().await.state.running);
+
+    assert_eq!(
+        cleanup::remove_session(&stale_session).await.unwrap(),
+        cleanup::Removed::default()
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn start_container_on_multi_thread_runtime() {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"))
+        .start()
+        .await
+        .unwrap();
+
+    let result = container
+        .exec_to_completion(ExecCommand::new(vec!["echo".to_string(), "ok".to_string()]))
+        .await;
+    assert_eq!(result.stdout, b"ok\n");
+}
+
+#[tokio::test]
+async fn env_file_variables_reach_container() {
+    let _ = pretty_env_logger::try_init();
+
+    let path = std::env::temp_dir().join(format!("testcontainers-{}.env", std::process::id()));
+    std::fs::write(
+        &path,
+        "# the greeting of the test\nexport GREETING=\"hello from\\na file\"\n",
+    )
+    .unwrap();
+
+    let container = RunnableImage::from(
+        GenericImage::new("simple_web_server", "latest")
+            .with_wait_for(WaitFor::message_on_stdout("server is ready")),
+    )
+    .with_env_file(&path)
+    .start()
+    .await
+    .unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let result = container
+        .exec_to_completion(ExecCommand::new(vec![
+            "printenv".to_string(),
+            "GREETING".to_string(),
+        ]))
+        .await;
+    assert_eq!(result.stdout, b"hello from\na file\n");
+}
+
+#[async_std::test]
+async fn start_container_on_async_std() {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"))
+        .start()
+        .await
+        .unwrap();
+
+    let result = container
+        .exec_to_completion(ExecCommand::new(vec!["echo".to_string(), "ok".to_string()]))
+        .await;
+    assert_eq!(result.stdout, b"ok\n");
+
+    container.stop().await;
+    assert!(!container.inspect().await.state.running);
+}
+
+#[cfg(feature = "http_wait")]
+#[tokio::test]
+async fn wait_for_http_endpoint() {
+    use testcontainers::core::HttpWaitStrategy;
+
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("no_expose_port", "latest")
+        .with_exposed_port(8080)
+        .with_wait_for(WaitFor::http(
+            HttpWaitStrategy::new("/")
+                .with_port(8080)
+                .with_header("Accept", "text/plain")
+                .with_expected_status_code(200)
+                .with_body_containing("Hello, World!"),
+        ));
+    let container = image.start().await.unwrap();
+
+    let port = container.get_host_port_ipv4(8080).await;
+    assert!(reqwest::get(format!("http://127.0.0.1:{port}"))
+        .await
+        .unwrap()
+        .status()
+        .is_success());
+}
+
+#[cfg(feature = "grpc_wait")]
+#[tokio::test]
+async fn wait_for_grpc_health() {
+    use testcontainers::core::GrpcHealthWaitStrategy;
+
+    let _ = pretty_env_logger::try_init();
+
+    // etcd serves the standard gRPC health service on its client port
+    let image = GenericImage::new("quay.io/coreos/etcd", "v3.5.13")
+        .with_entrypoint("/usr/local/bin/etcd")
+        .with_exposed_port(2379)
+        .with_wait_for(WaitFor::grpc_health(
+            GrpcHealthWaitStrategy::new().with_port(2379),
+        ));
+    let container = RunnableImage::from(image)
+        .with_cmd([
+            "--listen-client-urls=http://0.0.0.0:2379",
+            "--advertise-client-urls=http://0.0.0.0:2379",
+        ])
+        .with_startup_timeout(Duration::from_secs(60))
+        .start()
+        .await
+        .unwrap();
+
+    let port = container.get_host_port_ipv4(2379).await;
+    let health = reqwest::get(format!("http://127.0.0.1:{port}/health"))
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(health.contains(r#""health":"true""#));
+}
+
+#[tokio::test]
+async fn wait_for_tcp_port_open() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("no_expose_port", "latest")
+        .with_exposed_port(8080)
+        .with_wait_for(WaitFor::tcp_port_open(8080));
+    let container = image.start().await.unwrap();
+
+    let port = container.get_host_port_ipv4(8080).await;
+    assert!(reqwest::get(format!("http://127.0.0.1:{port}"))
+        .await
+        .unwrap()
+        .status()
+        .is_success());
+}
+
+#[tokio::test]
+async fn wait_for_exit_code() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("hello-world", "latest").with_wait_for(WaitFor::exit_code(0));
+    let container = image.start().await.unwrap();
+
+    let state = Docker::connect_with_unix_defaults()
+        .unwrap()
+        .inspect_container(container.id(), None)
+        .await
+        .unwrap()
+        .state
+        .unwrap();
+    assert_eq!(state.running, Some(false));
+    assert_eq!(state.exit_code, Some(0));
+}
+
+#[tokio::test]
+async fn start_fails_once_startup_timeout_elapsed() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("this message is never printed"));
+    let err = RunnableImage::from(image)
+        .with_startup_timeout(Duration::from_secs(1))
+        .start()
+        .await
+        .unwrap_err();
+
+    let TestcontainersError::Startup(error) = &err else {
+        panic!("expected a startup error, got: {err}");
+    };
+    assert_eq!(
+        error.reason,
+        StartupFailure::TimedOut {
+            timeout: Duration::from_secs(1)
+        }
+    );
+    assert!(err.to_string().contains("did not become ready within 1s"));
+}
+
+#[tokio::test]
+async fn start_fails_once_timeout_of_condition_elapsed() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("simple_web_server", "latest").with_wait_for(
+        WaitFor::message_on_stdout("this message is never printed")
+            .with_timeout(Duration::from_secs(1)),
+    );
+    let err = image.start().await.unwrap_err();
+
+    let TestcontainersError::Startup(error) = &err else {
+        panic!("expected a startup error, got: {err}");
+    };
+    assert_eq!(
+        error.reason,
+        StartupFailure::TimedOut {
+            timeout: Duration::from_secs(1)
+        }
+    );
+}
+
+#[tokio::test]
+async fn start_fails_once_container_exits_before_being_ready() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("hello-world", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("this message is never printed"));
+    let err = image.start().await.unwrap_err();
+
+    assert!(matches!(
+        err,
+        TestcontainersError::Startup(StartupError {
+            exit_code: Some(0),
+            ..
+        })
+    ));
+    assert!(err.to_string().contains("container exited with code 0"));
+}
+
+#[tokio::test]
+async fn start_fails_if_image_can_not_be_pulled() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("testcontainers/this-image-does-not-exist", "latest");
+    let err = image.start().await.unwrap_err();
+
+    let TestcontainersError::PullFailed { image, .. } = &err else {
+        panic!("expected a pull error, got: {err}");
+    };
+    assert_eq!(image, "testcontainers/this-image-does-not-exist:latest");
+}
+
+#[tokio::test]
+async fn wait_for_command_to_succeed() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("curlimages/curl", "latest")
+        .with_entrypoint("sh")
+        .with_wait_for(WaitFor::command(["test", "-f", "/tmp/ready"]));
+    let args = vec![
+        "-c".to_string(),
+        "sleep 1 && touch /tmp/ready && sleep 60".to_string(),
+    ];
+    let _container = RunnableImage::from((image, args)).start().await.unwrap();
+}
+
+#[tokio::test]
+async fn wait_for_file_to_exist() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("curlimages/curl", "latest")
+        .with_entrypoint("sh")
+        .with_wait_for(WaitFor::file_exists("/tmp/ready"));
+    let args = vec![
+        "-c".to_string(),
+        "sleep 1 && touch /tmp/ready && sleep 60".to_string(),
+    ];
+    let _container = RunnableImage::from((image, args)).start().await.unwrap();
+}
+
+#[tokio::test]
+async fn wait_for_inspect_field() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::inspect_field("/State/Running", true));
+    let container = image.start().await.unwrap();
+
+    let running = Docker::connect_with_unix_defaults()
+        .unwrap()
+        .inspect_container(container.id(), None)
+        .await
+        .unwrap()
+        .state
+        .unwrap()
+        .running;
+    assert_eq!(running, Some(true));
+}
+
+#[tokio::test]
+async fn start_fails_once_startup_attempts_are_exhausted() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("this message is never printed"));
+    let err = RunnableImage::from(image)
+        .with_startup_timeout(Duration::from_secs(1))
+        .with_startup_attempts(2)
+        .start()
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().contains("did not become ready within 1s"));
+}
+
+#[tokio::test]
+async fn start_recreates_container_that_fails_to_become_ready() {
+    let _ = pretty_env_logger::try_init();
+
+    // the volume outlives the attempts, so only the second container prints the message
+    let volume = Volume::new();
+    let image = GenericImage::new("curlimages/curl", "latest")
+        .with_entrypoint("sh")
+        .with_wait_for(WaitFor::message_on_stdout("ready after 2 attempts"));
+    let _container = RunnableImage::from(image)
+        .with_cmd([
+            "-c",
+            "echo attempt >> /data/attempts; \
+             n=$(wc -l < /data/attempts); \
+             [ \"$n\" -ge 2 ] && echo \"ready after $n attempts\"; \
+             sleep 60",
+        ])
+        .with_mount(volume.mount("/data"))
+        .with_startup_timeout(Duration::from_secs(3))
+        .with_startup_attempts(3)
+        .start()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn wait_for_log_silence() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::log_silence(Duration::from_millis(500)));
+    let _container = image.start().await.unwrap();
+}
+
+#[tokio::test]
+async fn wait_for_message_on_either_stream() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_either("server is ready"));
+    let _container = image.start().await.unwrap();
+}
+
+#[tokio::test]
+async fn wait_for_programmatic_healthcheck() {
+    let _ = pretty_env_logger::try_init();
+
+    let image =
+        GenericImage::new("simple_web_server", "latest").with_wait_for(WaitFor::Healthcheck);
+    let _container = RunnableImage::from(image)
+        .with_healthcheck(
+            Healthcheck::cmd_shell("test -x ./foo").with_interval(Duration::from_millis(500)),
+        )
+        .start()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn wait_for_healthcheck_with_strategy() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("simple_web_server", "latest").with_wait_for(
+        WaitFor::healthcheck()
+            .with_poll_interval(Duration::from_millis(250))
+            .with_timeout(Duration::from_secs(30)),
+    );
+    let _container = RunnableImage::from(image)
+        .with_healthcheck(
+            Healthcheck::cmd_shell("test -x ./foo").with_interval(Duration::from_millis(500)),
+        )
+        .start()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn stream_log_lines() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("curlimages/curl", "latest")
+        .with_entrypoint("sh")
+        .with_wait_for(WaitFor::message_on_stderr("first"));
+    let container = RunnableImage::from(image)
+        .with_cmd([
+            "-c",
+            "echo first >&2 && sleep 1 && echo second >&2 && sleep 60",
+        ])
+        .start()
+        .await
+        .unwrap();
+
+    let lines: Vec<_> = container
+        .stderr_lines()
+        .take(2)
+        .map(|line| line.unwrap())
+        .collect()
+        .await;
+    let messages: Vec<_> = lines.iter().map(|line| line.message.as_str()).collect();
+    assert_eq!(messages, vec!["first", "second"]);
+    assert!(lines[0].timestamp < lines[1].timestamp);
+}
+
+#[tokio::test]
+async fn stream_log_lines_longer_than_a_frame() {
+    let _ = pretty_env_logger::try_init();
+
+    // the daemon splits lines longer than 16 KiB into several frames
+    let image = GenericImage::new("curlimages/curl", "latest")
+        .with_entrypoint("sh")
+        .with_wait_for(WaitFor::message_on_stdout("done"));
+    let container = RunnableImage::from(image)
+        .with_cmd([
+            "-c",
+            "head -c 40000 /dev/zero | tr '\\0' a && echo && echo done && sleep 60",
+        ])
+        .start()
+        .await
+        .unwrap();
+
+    let lines: Vec<_> = container
+        .stdout_lines()
+        .take(2)
+        .map(|line| line.unwrap().message)
+        .collect()
+        .await;
+    assert_eq!(lines, vec!["a".repeat(40000), "done".to_string()]);
+}
+
+#[tokio::test]
+async fn forward_logs_to_consumer() {
+    let _ = pretty_env_logger::try_init();
+
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"));
+    let _container = RunnableImage::from(image)
+        .with_log_consumer(move |source: LogSource, line: &LogLine| {
+            let _ = sender.send((source, line.message.clone()));
+        })
+        .start()
+        .await
+        .unwrap();
+
+    let (source, message) = tokio::time::timeout(Duration::from_secs(10), receiver.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(source, LogSource::StdOut);
+    assert_eq!(message, "server is ready");
+}
+
+#[tokio::test]
+async fn forward_logs_to_consumer_after_invalid_utf8() {
+    let _ = pretty_env_logger::try_init();
+
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    let image = GenericImage::new("curlimages/curl", "latest")
+        .with_entrypoint("sh")
+        .with_wait_for(WaitFor::message_on_stdout("done"));
+    let _container = RunnableImage::from(image)
+        .with_cmd(["-c", "printf 'caf\\351\\n' && echo done && sleep 60"])
+        .with_log_consumer(move |_: LogSource, line: &LogLine| {
+            let _ = sender.send(line.message.clone());
+        })
+        .start()
+        .await
+        .unwrap();
+
+    let mut messages = vec![];
+    for _ in 0..2 {
+        let message = tokio::time::timeout(Duration::from_secs(10), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        messages.push(message);
+    }
+    assert_eq!(messages, vec!["caf\u{fffd}", "done"]);
+}
+
+#[tokio::test]
+async fn dump_logs_to_file_on_drop() {
+    let _ = pretty_env_logger::try_init();
+
+    let name = format!("tc-log-dump-{}", std::process::id());
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"));
+    let container = RunnableImage::from(image)
+        .with_container_name(&name)
+        .with_log_dump(LogDump::Always)
+        .start()
+        .await
+        .unwrap();
+    drop(container);
+
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR"))
+        .parent()
+        .unwrap()
+        .join("testcontainers")
+        .join(format!("{name}.log"));
+    let logs = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(logs.contains("server is ready"));
+}
+
+#[tokio::test]
+async fn container_group_injects_addresses_of_dependencies() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"));
+    let group = ContainerGroup::new()
+        .with_container("app", image.clone(), DependsOn::containers(["web-db"]))
+        .with_container("web-db", image, DependsOn::nothing())
+        .start()
+        .await
+        .unwrap();
+
+    let db = group.get::<GenericImage>("web-db").unwrap();
+    let app = group.get::<GenericImage>("app").unwrap();
+    let db_address = db.get_network_ip(group.network()).await.to_string();
+    assert!(app
+        .inspect()
+        .await
+        .config
+        .env
+        .contains(&("WEB_DB_HOST".to_string(), db_address)));
+    assert!(group.get::<HelloWorld>("app").is_none());
+}
+
+#[tokio::test]
+async fn compose_starts_services_and_removes_them_with_volumes() {
+    let _ = pretty_env_logger::try_init();
+
+    let compose = compose::DockerCompose::from_file(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/compose/docker-compose.yml"
+    ))
+    .with_env_var(("GREETING", "hi"))
+    .with_wait_for("web", WaitFor::message_on_stdout("server is ready"))
+    .up()
+    .await
+    .unwrap();
+    let project = compose.project().to_string();
+
+    let docker = Docker::connect_with_unix_defaults().unwrap();
+    let web = docker
+        .inspect_container(&compose.container_id("web").await.unwrap(), None)
+        .await
+        .unwrap();
+    let env = web.config.and_then(|config| config.env).unwrap_or_default();
+    assert!(env.contains(&"GREETING=hi".to_string()), "env: {env:?}");
+    assert_ne!(compose.service_port("web", 80).await.unwrap(), 0);
+    assert!(matches!(
+        compose.service_port("web", 1234).await,
+        Err(TestcontainersError::ComposeFailed { .. })
+    ));
+    assert!(matches!(
+        compose.container_id("missing").await,
+        Err(TestcontainersError::ComposeFailed { .. })
+    ));
+
+    drop(compose);
+    let filters = std::collections::HashMap::from([(
+        "label",
+        vec![format!("com.docker.compose.project={project}")],
+    )]);
+    let containers = docker
+        .list_containers(Some(bollard::container::ListContainersOptions {
+            all: true,
+            filters: filters.clone(),
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+    assert!(containers.is_empty(), "containers: {containers:?}");
+    let volumes = docker
+        .list_volumes(Some(bollard::volume::ListVolumesOptions { filters }))
+        .await
+        .unwrap()
+        .volumes
+        .unwrap_or_default();
+    assert!(volumes.is_empty(), "volumes: {volumes:?}");
+}
+
+#[tokio::test]
+async fn compose_starts_services_of_active_profiles_scaled() {
+    let _ = pretty_env_logger::try_init();
+
+    let file = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/compose/docker-compose.yml"
+    );
+    let without_profile = compose::DockerCompose::from_file(file).up().await.unwrap();
+    assert!(without_profile
+        .container_ids("worker")
+        .await
+        .unwrap()
+        .is_empty());
+    drop(without_profile);
+
+    let compose = compose::DockerCompose::from_file(file)
+        .with_profile("workers")
+        .with_scale("worker", 2)
+        .with_wait_for("worker", WaitFor::message_on_stdout("server is ready"))
+        .up()
+        .await
+        .unwrap();
+    assert_eq!(compose.container_ids("web").await.unwrap().len(), 1);
+    let workers = compose.container_ids("worker").await.unwrap();
+    assert_eq!(workers.len(), 2);
+
+    compose.scale("worker", 3).await.unwrap();
+    let scaled = compose.container_ids("worker").await.unwrap();
+    assert_eq!(scaled.len(), 3);
+    assert_eq!(scaled[..2], workers[..], "running workers are kept");
+}
+
+static SHARED_WEB_SERVER: SharedContainer<GenericImage> = SharedContainer::new();
+
+fn shared_web_server() -> GenericImage {
+    GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"))
+}
+
+#[tokio::test]
+async fn shared_container_is_started_once() {
+    let _ = pretty_env_logger::try_init();
+
+    let container = SHARED_WEB_SERVER
+        .get_or_start(shared_web_server)
+        .await
+        .unwrap();
+    let again = SHARED_WEB_SERVER
+        .get_or_start(|| -> GenericImage { unreachable!("the container was started before") })
+        .await
+        .unwrap();
+    assert_eq!(container.id(), again.id());
+    assert_eq!(
+        SHARED_WEB_SERVER.get().map(|c| c.id()),
+        Some(container.id())
+    );
+}
+
+#[test]
+fn shared_container_is_usable_from_other_runtimes() {
+    let _ = pretty_env_logger::try_init();
+
+    let ids: Vec<String> = (0..2)
+        .map(|_| {
+            std::thread::spawn(|| {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .unwrap();
+                runtime.block_on(async {
+                    let container = SHARED_WEB_SERVER
+                        .get_or_start(shared_web_server)
+                        .await
+                        .unwrap();
+                    let result = container
+                        .exec_to_completion(ExecCommand::new(vec![
+                            "echo".to_string(),
+                            "ok".to_string(),
+                        ]))
+                        .await;
+                    assert_eq!(result.stdout, b"ok\n");
+                    container.id().to_string()
+                })
+            })
+            .join()
+            .unwrap()
+        })
+        .collect();
+    assert_eq!(ids[0], ids[1]);
+}
+
+#[tokio::test]
+async fn keep_container_of_failed_test() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"));
+    let passed = RunnableImage::from(image.clone())
+        .with_remove_on_drop(RemovePolicy::OnSuccess)
+        .start()
+        .await
+        .unwrap();
+    let passed_id = passed.id().to_string();
+    drop(passed);
+
+    let (id_tx, id_rx) = std::sync::mpsc::channel();
+    let failed = tokio::spawn(async move {
+        let container = RunnableImage::from(image)
+            .with_remove_on_drop(RemovePolicy::OnSuccess)
+            .start()
+            .await
+            .unwrap();
+        id_tx.send(container.id().to_string()).unwrap();
+        panic!("the test failed");
+    })
+    .await;
+    assert!(failed.unwrap_err().is_panic());
+    let failed_id = id_rx.recv().unwrap();
+
+    let docker = Docker::connect_with_unix_defaults().unwrap();
+    assert!(docker.inspect_container(&passed_id, None).await.is_err());
+    let kept = docker.inspect_container(&failed_id, None).await.unwrap();
+    assert_eq!(kept.state.and_then(|state| state.running), Some(true));
+
+    docker
+        .remove_container(
+            &failed_id,
+            Some(bollard::container::RemoveContainerOptions {
+                force: true,
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn container_stats() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"));
+    let container = RunnableImage::from(image)
+        .with_memory_limit(64 * 1024 * 1024)
+        .start()
+        .await
+        .unwrap();
+
+    let stats = container.stats().await;
+    assert!(stats.memory_usage > 0);
+    assert_eq!(stats.memory_limit, 64 * 1024 * 1024);
+    assert!(stats.pids > 0);
+
+    let samples: Vec<_> = container.stats_stream().take(2).collect().await;
+    assert_eq!(samples.len(), 2);
+    assert!(samples.iter().all(Result::is_ok));
+}
+
+#[tokio::test]
+async fn container_stats_reflect_cpu_and_network_usage() {
+    let _ = pretty_env_logger::try_init();
+
+    let busy = GenericImage::new("curlimages/curl", "latest").with_entrypoint("sh");
+    let busy = RunnableImage::from(busy)
+        .with_cmd(["-c", "while :; do :; done"])
+        .start()
+        .await
+        .unwrap();
+    // a busy loop keeps about one CPU busy
+    let stats = busy.stats().await;
+    assert!(stats.cpu_percent > 20.0, "{stats:?}");
+
+    let web_server = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"))
+        .start()
+        .await
+        .unwrap();
+    let before = web_server.stats().await;
+    let port = web_server.get_host_port_ipv4(80).await;
+    for _ in 0..10 {
+        reqwest::get(format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+    }
+    let after = web_server.stats().await;
+    assert!(after.network_rx_bytes > before.network_rx_bytes);
+    assert!(after.network_tx_bytes > before.network_tx_bytes);
+}
+
+#[tokio::test]
+async fn exec_returns_output_and_exit_code() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"));
+    let container = image.start().await.unwrap();
+
+    let result = container
+        .exec_to_completion(
+            ExecCommand::new(vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                "ls /; exit 2".to_string(),
+            ])
+            .with_cmd_ready_condition(WaitFor::message_on_stdout("bin")),
+        )
+        .await;
+    assert_eq!(result.exit_code, Some(2));
+    assert!(String::from_utf8_lossy(&result.stdout).contains("etc"));
+
+    let result = container
+        .exec_to_completion(ExecCommand::new(vec![
+            "ls".to_string(),
+            "/missing".to_string(),
+        ]))
+        .await;
+    assert_ne!(result.exit_code, Some(0));
+    assert!(String::from_utf8_lossy(&result.stderr).contains("missing"));
+}
+
+#[tokio::test]
+async fn exec_with_options_and_detached() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"));
+    let container = image.start().await.unwrap();
+
+    let result = container
+        .exec_to_completion(
+            ExecCommand::new(vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                "echo $(id -u) $GREETING $(pwd)".to_string(),
+            ])
+            .with_user("nobody")
+            .with_env_var(("GREETING", "hello"))
+            .with_working_dir("/tmp"),
+        )
+        .await;
+    assert_eq!(result.exit_code, Some(0));
+    assert_eq!(result.stdout, b"65534 hello /tmp\n");
+
+    let result = container
+        .exec_to_completion(
+            ExecCommand::new(vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                "sleep 1 && touch /tmp/background && sleep 60".to_string(),
+            ])
+            .with_detach(true)
+            .with_container_ready_conditions(vec![WaitFor::file_exists("/tmp/background")]),
+        )
+        .await;
+    assert_eq!(result.exit_code, None);
+}
+
+#[tokio::test]
+async fn exec_returns_once_long_running_command_is_ready() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"));
+    let container = image.start().await.unwrap();
+
+    let cmd = ExecCommand::new(vec![
+        "sh".to_string(),
+        "-c".to_string(),
+        "echo starting; echo ready; sleep 300".to_string(),
+    ])
+    .with_cmd_ready_condition(WaitFor::message_on_stdout("ready"));
+    tokio::time::timeout(Duration::from_secs(30), container.exec(cmd))
+        .await
+        .expect("exec did not return once the command was ready");
+}
+
+#[tokio::test]
+async fn copy_file_and_directory_into_running_container() {
+    let _ = pretty_env_logger::try_init();
+
+    let fixtures = std::env::temp_dir().join(format!("tc-fixtures-{}", std::process::id()));
+    std::fs::create_dir_all(fixtures.join("nested")).unwrap();
+    std::fs::write(fixtures.join("config.toml"), "debug = true").unwrap();
+    std::fs::write(fixtures.join("nested/data.sql"), "select 1;").unwrap();
+
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"));
+    let container = image.start().await.unwrap();
+
+    container
+        .copy_file_to(fixtures.join("config.toml"), "/etc/app/config.toml")
+        .await;
+    container.copy_dir_to(&fixtures, "/fixtures").await;
+    std::fs::remove_dir_all(&fixtures).unwrap();
+
+    let check = ExecCommand::new(vec![
+        "sh".to_string(),
+        "-c".to_string(),
+        r#"test "$(cat /etc/app/config.toml)" = "debug = true" \
+            && test "$(cat /fixtures/nested/data.sql)" = "select 1;" \
+            && echo copied"#
+            .to_string(),
+    ])
+    .with_cmd_ready_condition(WaitFor::message_on_stdout("copied"));
+    container.exec(check).await;
+}
+
+#[tokio::test]
+async fn read_file_and_directory_from_container() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("curlimages/curl", "latest")
+        .with_entrypoint("sh")
+        .with_wait_for(WaitFor::file_exists("/tmp/done"));
+    let container = RunnableImage::from(image)
+        .with_cmd([
+            "-c",
+            "mkdir -p /tmp/reports/nested && echo passed > /tmp/reports/nested/result.txt \
+                && touch /tmp/done && sleep 60",
+        ])
+        .start()
+        .await
+        .unwrap();
+
+    let content = container.read_file("/tmp/reports/nested/result.txt").await;
+    assert_eq!(content, b"passed\n");
+
+    let reports = std::env::temp_dir().join(format!("tc-reports-{}", std::process::id()));
+    container.copy_dir_from("/tmp/reports", &reports).await;
+    let content = std::fs::read_to_string(reports.join("nested/result.txt")).unwrap();
+    std::fs::remove_dir_all(&reports).unwrap();
+    assert_eq!(content, "passed\n");
+}
+
+#[tokio::test]
+async fn managed_volume_is_shared_and_removed_once_unused() {
+    let _ = pretty_env_logger::try_init();
+
+    let volume = Volume::new();
+    let name = volume.name().to_string();
+    {
+        let writer = GenericImage::new("curlimages/curl", "latest")
+            .with_entrypoint("sh")
+            .with_wait_for(WaitFor::exit_code(0));
+        let _writer = RunnableImage::from(writer)
+            .with_cmd(["-c", "echo shared-content > /data/file"])
+            .with_mount(volume.mount("/data"))
+            .start()
+            .await
+            .unwrap();
+
+        let reader = GenericImage::new("curlimages/curl", "latest")
+            .with_entrypoint("sh")
+            .with_wait_for(WaitFor::message_on_stdout("shared-content"));
+        let _reader = RunnableImage::from(reader)
+            .with_cmd(["-c", "cat /data/file && sleep 60"])
+            .with_mount(volume.mount("/data").with_read_only(true))
+            .start()
+            .await
+            .unwrap();
+    }
+    drop(volume);
+
+    let docker = Docker::connect_with_unix_defaults().unwrap();
+    assert!(docker.inspect_volume(&name).await.is_err());
+}
+
+#[tokio::test]
+async fn shutdown_commands_run_before_container_is_removed() {
+    let _ = pretty_env_logger::try_init();
+
+    let volume = Volume::new();
+    let image = GenericImage::new("curlimages/curl", "latest").with_entrypoint("sh");
+    let container = RunnableImage::from(image.clone())
+        .with_cmd(["-c", "sleep 60"])
+        .with_mount(volume.mount("/data"))
+        .with_stop_signal("SIGKILL")
+        .with_shutdown_command(ExecCommand::new(vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "echo flushed > /data/state".to_string(),
+        ]))
+        .start()
+        .await
+        .unwrap();
+    drop(container);
+
+    let reader = image.with_wait_for(WaitFor::message_on_stdout("flushed"));
+    let _reader = RunnableImage::from(reader)
+        .with_cmd(["-c", "cat /data/state && sleep 60"])
+        .with_mount(volume.mount("/data"))
+        .with_startup_timeout(Duration::from_secs(10))
+        .start()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn container_set_removes_dependents_first() {
+    let _ = pretty_env_logger::try_init();
+
+    let network = Network::new();
+    let volume = Volume::new();
+    let web_server = RunnableImage::from(
+        GenericImage::new("simple_web_server", "latest")
+            .with_wait_for(WaitFor::message_on_stdout("server is ready")),
+    )
+    .with_network(&network)
+    .with_network_alias("web")
+    .start()
+    .await
+    .unwrap();
+    let image = GenericImage::new("curlimages/curl", "latest").with_entrypoint("sh");
+    let client = RunnableImage::from(image.clone())
+        .with_cmd(["-c", "sleep 60"])
+        .with_network(&network)
+        .with_mount(volume.mount("/data"))
+        .with_stop_signal("SIGKILL")
+        .with_shutdown_command(ExecCommand::new(vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "curl -sf http://web > /dev/null && echo deregistered > /data/state".to_string(),
+        ]))
+        .start()
+        .await
+        .unwrap();
+
+    // a `Vec` of the containers, or their reverse order, would remove the web server first
+    let containers = ContainerSet::new()
+        .with_container("client", client, DependsOn::containers(["web"]))
+        .with_container("web", web_server, DependsOn::nothing());
+    assert!(containers.get::<GenericImage>("client").is_some());
+    drop(containers);
+
+    let reader = image.with_wait_for(WaitFor::message_on_stdout("deregistered"));
+    let _reader = RunnableImage::from(reader)
+        .with_cmd(["-c", "cat /data/state && sleep 60"])
+        .with_mount(volume.mount("/data"))
+        .with_startup_timeout(Duration::from_secs(10))
+        .start()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn container_set_keeps_containers_of_failed_test() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"));
+    let (id_tx, id_rx) = std::sync::mpsc::channel();
+    let failed = tokio::spawn(async move {
+        let container = RunnableImage::from(image)
+            .with_remove_on_drop(RemovePolicy::OnSuccess)
+            .start()
+            .await
+            .unwrap();
+        id_tx.send(container.id().to_string()).unwrap();
+        let _containers =
+            ContainerSet::new().with_container("web", container, DependsOn::nothing());
+        panic!("the test failed");
+    })
+    .await;
+    assert!(failed.unwrap_err().is_panic());
+    let failed_id = id_rx.recv().unwrap();
+
+    let docker = Docker::connect_with_unix_defaults().unwrap();
+    let kept = docker.inspect_container(&failed_id, None).await.unwrap();
+    assert_eq!(kept.state.and_then(|state| state.running), Some(true));
+
+    docker
+        .remove_container(
+            &failed_id,
+            Some(bollard::container::RemoveContainerOptions {
+                force: true,
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn remove_after_cleans_up_without_blocking_runtime() {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"))
+        .start()
+        .await
+        .unwrap();
+    let id = container
+        .remove_after(|container| async move { container.id().to_string() }.boxed())
+        .await;
+
+    let docker = Docker::connect_with_unix_defaults().unwrap();
+    assert!(docker.inspect_container(&id, None).await.is_err());
+}
+
+#[tokio::test]
+async fn copies_started_from_snapshot_share_its_state() {
+    let _ = pretty_env_logger::try_init();
+
+    let sh = |script: &str| {
+        ExecCommand::new(vec!["sh".to_string(), "-c".to_string(), script.to_string()])
+    };
+    let image = GenericImage::new("curlimages/curl", "latest").with_entrypoint("sh");
+    let seeded = RunnableImage::from(image)
+        .with_cmd(["-c", "sleep 60"])
+        .start()
+        .await
+        .unwrap();
+    seeded
+        .exec_to_completion(sh("echo seeded > /tmp/state"))
+        .await;
+
+    let snapshot = seeded.snapshot().await;
+    drop(seeded);
+    let first = snapshot.start().await.unwrap();
+    let second = snapshot.start().await.unwrap();
+
+    first
+        .exec_to_completion(sh("echo changed > /tmp/state"))
+        .await;
+    assert_eq!(
+        first.exec_to_completion(sh("cat /tmp/state")).await.stdout,
+        b"changed\n"
+    );
+    assert_eq!(
+        second.exec_to_completion(sh("cat /tmp/state")).await.stdout,
+        b"seeded\n"
+    );
+
+    let descriptor = snapshot.descriptor().to_string();
+    drop((first, second));
+    drop(snapshot);
+    let docker = Docker::connect_with_unix_defaults().unwrap();
+    assert!(docker.inspect_image(&descriptor).await.is_err());
+}
+
+#[tokio::test]
+async fn stop_start_and_restart_container() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("curlimages/curl", "latest")
+        .with_entrypoint("sh")
+        .with_wait_for(WaitFor::file_exists("/tmp/starts"));
+    let container = RunnableImage::from(image)
+        .with_cmd(["-c", "echo started >> /tmp/starts && sleep 60"])
+        .start()
+        .await
+        .unwrap();
+
+    container.stop_with_timeout(Duration::from_secs(1)).await;
+    container.start().await;
+    container.restart().await;
+
+    // the command appended a line on every start, as the filesystem is kept
+    let result = container
+        .exec_to_completion(ExecCommand::new(vec![
+            "cat".to_string(),
+            "/tmp/starts".to_string(),
+        ]))
+        .await;
+    assert_eq!(result.stdout, b"started\nstarted\nstarted\n");
+}
+
+#[tokio::test]
+async fn wait_for_container_to_exit() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("curlimages/curl", "latest").with_entrypoint("sh");
+    let container = RunnableImage::from(image)
+        .with_cmd(["-c", "sleep 1 && exit 3"])
+        .start()
+        .await
+        .unwrap();
+
+    let status = container.wait_with_timeout(Duration::from_secs(30)).await;
+    assert_eq!(status.code(), 3);
+    assert!(!status.success());
+    // waiting again returns the status of the exited container
+    assert_eq!(container.wait().await, status);
+}
+
+#[tokio::test]
+#[should_panic(expected = "did not exit within")]
+async fn wait_fails_once_timeout_elapsed() {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"))
+        .start()
+        .await
+        .unwrap();
+
+    container
+        .wait_with_timeout(Duration::from_millis(500))
+        .await;
+}
+
+#[tokio::test]
+async fn inspect_running_container() {
+    let _ = pretty_env_logger::try_init();
+
+    let volume = Volume::new();
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"));
+    let container = RunnableImage::from(image)
+        .with_env_var(("GREETING", "hello"))
+        .with_label("com.example.suite", "inspect")
+        .with_mount(volume.mount("/data").with_read_only(true))
+        .start()
+        .await
+        .unwrap();
+
+    let info = container.inspect().await;
+
+    assert_eq!(info.id, container.id());
+    assert_eq!(info.state.status, "running");
+    assert!(info.state.pid.is_some());
+    assert_eq!(info.config.image, "simple_web_server:latest");
+    assert!(info
+        .config
+        .env
+        .contains(&("GREETING".to_string(), "hello".to_string())));
+    assert_eq!(
+        info.config
+            .labels
+            .get("com.example.suite")
+            .map(String::as_str),
+        Some("inspect")
+    );
+    let mount = info
+        .mounts
+        .iter()
+        .find(|mount| mount.destination == "/data")
+        .unwrap();
+    assert_eq!(mount.name.as_deref(), Some(volume.name()));
+    assert!(mount.read_only);
+    assert!(info.networks["bridge"].ip_address.is_some());
+}
+
+#[tokio::test]
+async fn inspect_container_on_network_and_after_exit() {
+    let _ = pretty_env_logger::try_init();
+
+    let network = Network::new();
+    let name = format!("tc-inspect-{}", std::process::id());
+    let image = GenericImage::new("curlimages/curl", "latest")
+        .with_entrypoint("sh")
+        .with_wait_for(WaitFor::exit_code(3));
+    let container = RunnableImage::from(image)
+        .with_cmd(["-c", "exit 3"])
+        .with_container_name(&name)
+        .with_env_var(("PASSWORD", "a=b"))
+        .with_env_var(("EMPTY", ""))
+        .with_network(&network)
+        .with_network_alias("exiting")
+        .start()
+        .await
+        .unwrap();
+
+    let info = container.inspect().await;
+
+    assert_eq!(info.name, name);
+    assert!(!info.state.running);
+    assert_eq!(info.state.status, "exited");
+    assert_eq!(info.state.exit_code, Some(3));
+    assert!(info.state.finished_at.is_some());
+    assert!(info
+        .config
+        .env
+        .contains(&("PASSWORD".to_string(), "a=b".to_string())));
+    assert!(info
+        .config
+        .env
+        .contains(&("EMPTY".to_string(), String::new())));
+    let endpoint = &info.networks[network.name()];
+    assert!(endpoint.aliases.contains(&"exiting".to_string()));
+    // the container no longer has an address once it exited
+    assert_eq!(endpoint.ip_address, None);
+}
+
+#[tokio::test]
+async fn sibling_container_reaches_container_by_network_ip() {
+    let _ = pretty_env_logger::try_init();
+
+    let server = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"));
+    let server = RunnableImage::from(server)
+        .with_network("tc-network-ip")
+        .start()
+        .await
+        .unwrap();
+    let client = GenericImage::new("curlimages/curl", "latest").with_entrypoint("sh");
+    let client = RunnableImage::from(client)
+        .with_cmd(["-c", "sleep 60"])
+        .with_network("tc-network-ip")
+        .start()
+        .await
+        .unwrap();
+
+    let ip = server.get_network_ip("tc-network-ip").await;
+    assert_ne!(ip, client.get_network_ip("tc-network-ip").await);
+
+    let result = client
+        .exec_to_completion(ExecCommand::new(vec![
+            "curl".to_string(),
+            "-s".to_string(),
+            format!("http://{ip}"),
+        ]))
+        .await;
+    assert_eq!(result.stdout, b"simple_web_server");
+}
+
+#[tokio::test]
+async fn network_is_removed_with_last_container() {
+    let _ = pretty_env_logger::try_init();
+
+    let network = Network::new();
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"));
+    let first = RunnableImage::from(image.clone())
+        .with_network(&network)
+        .start()
+        .await
+        .unwrap();
+    let second = RunnableImage::from(image)
+        .with_network(&network)
+        .start()
+        .await
+        .unwrap();
+    assert_ne!(
+        first.get_network_ip(network.name()).await,
+        second.get_network_ip(network.name()).await
+    );
+
+    let docker = Docker::connect_with_unix_defaults().unwrap();
+    drop(first);
+    assert!(docker
+        .inspect_network::<String>(network.name(), None)
+        .await
+        .is_ok());
+    drop(second);
+    assert!(docker
+        .inspect_network::<String>(network.name(), None)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn gateway_ip_is_gateway_of_bridge_network() {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"))
+        .start()
+        .await
+        .unwrap();
+
+    let docker = Docker::connect_with_unix_defaults().unwrap();
+    let bridge = docker
+        .inspect_network::<String>("bridge", None)
+        .await
+        .unwrap();
+    let gateways: Vec<_> = bridge
+        .ipam
+        .and_then(|ipam| ipam.config)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|config| config.gateway)
+        .collect();
+
+    let gateway = container.get_gateway_ip().await;
+    assert!(gateways.contains(&gateway.to_string()));
+}
+
+#[tokio::test]
+async fn send_signals_to_container() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("curlimages/curl", "latest")
+        .with_entrypoint("sh")
+        .with_wait_for(WaitFor::message_on_stdout("ready"));
+    let container = RunnableImage::from(image)
+        .with_cmd([
+            "-c",
+            "trap 'echo reloaded' HUP; echo ready; while true; do sleep 1 & wait $!; done",
+        ])
+        .start()
+        .await
+        .unwrap();
+
+    container.kill(Signal::Hup).await;
+    let reloaded = container
+        .stdout_lines()
+        .any(|line| async move { line.unwrap().message == "reloaded" });
+    assert!(tokio::time::timeout(Duration::from_secs(10), reloaded)
+        .await
+        .unwrap());
+
+    container.kill(Signal::Kill).await;
+    assert_eq!(container.wait().await.code(), 137);
+}
+
+#[tokio::test]
+async fn commit_container_to_image() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("curlimages/curl", "latest")
+        .with_entrypoint("sh")
+        .with_wait_for(WaitFor::file_exists("/tmp/seed"));
+    let container = RunnableImage::from(image)
+        .with_cmd(["-c", "echo seeded > /tmp/seed && sleep 60"])
+        .start()
+        .await
+        .unwrap();
+
+    let committed = container
+        .commit("testcontainers-committed", "seeded")
+        .await
+        .with_entrypoint("sh")
+        .with_wait_for(WaitFor::message_on_stdout("seeded"));
+    drop(container);
+    {
+        // the seeded file is part of the image
+        let _container = RunnableImage::from(committed)
+            .with_cmd(["-c", "cat /tmp/seed && sleep 60"])
+            .start()
+            .await
+            .unwrap();
+    }
+
+    let docker = Docker::connect_with_unix_defaults().unwrap();
+    docker
+        .remove_image("testcontainers-committed:seeded", None, None)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn exec_interactive_writes_stdin_and_streams_output() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("curlimages/curl", "latest").with_entrypoint("sh");
+    let container = RunnableImage::from(image)
+        .with_cmd(["-c", "sleep 60"])
+        .start()
+        .await
+        .unwrap();
+
+    let mut session = container
+        .exec_interactive(ExecCommand::new(vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "while read line; do echo \"echo: $line\"; done; echo done >&2; exit 4".to_string(),
+        ]))
+        .await;
+
+    session.write_stdin("hello\n").await.unwrap();
+    let (source, output) = session.read_output().await.unwrap().unwrap();
+    assert_eq!(source, LogSource::StdOut);
+    assert_eq!(output, b"echo: hello\n");
+
+    session.write_stdin("world\n").await.unwrap();
+    session.close_stdin().await.unwrap();
+    assert!(session.write_stdin("ignored\n").await.is_err());
+
+    let result = session.wait().await;
+    assert_eq!(result.exit_code, Some(4));
+    assert_eq!(result.stdout, b"echo: world\n");
+    assert_eq!(result.stderr, b"done\n");
+}
+
+#[tokio::test]
+async fn try_get_host_port_returns_error_for_unmapped_port() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_exposed_port(80)
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"));
+    let container = image.start().await.unwrap();
+
+    assert!(container.try_get_host_port_ipv4(80).await.is_ok());
+    assert_eq!(
+        container.try_get_host_port_ipv4(8080).await,
+        Err(PortError::NotMappedIpv4 { port: 8080 })
+    );
+}
+
+#[tokio::test]
+async fn list_mapped_ports_of_container() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"));
+    let container = RunnableImage::from(image)
+        .with_mapped_port((45080, 80))
+        .start()
+        .await
+        .unwrap();
+
+    let ports = container.ports().await;
+    assert!(ports.ipv4_mappings().any(|mapping| mapping == (80, 45080)));
+}
+
+#[tokio::test]
+async fn query_health_status_of_container() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("curlimages/curl", "latest").with_entrypoint("sh");
+    let container = RunnableImage::from(image)
+        .with_cmd(["-c", "sleep 60"])
+        .with_healthcheck(
+            Healthcheck::cmd_shell("cat /tmp/healthy")
+                .with_interval(Duration::from_millis(200))
+                .with_retries(1),
+        )
+        .start()
+        .await
+        .unwrap();
+
+    let wait_for_state = |state: HealthState| {
+        let container = &container;
+        async move {
+            loop {
+                let health = container.health().await;
+                if health.state == state {
+                    break health;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        }
+    };
+
+    let health = tokio::time::timeout(
+        Duration::from_secs(10),
+        wait_for_state(HealthState::Unhealthy),
+    )
+    .await
+    .unwrap();
+    assert!(health.failing_streak >= 1);
+    let probe = health.last_probe.unwrap();
+    assert_eq!(probe.exit_code, 1);
+    assert!(probe.output.contains("/tmp/healthy"), "{probe:?}");
+
+    container
+        .exec(ExecCommand::new(vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "echo ok > /tmp/healthy".to_string(),
+        ]))
+        .await;
+    let health = tokio::time::timeout(
+        Duration::from_secs(10),
+        wait_for_state(HealthState::Healthy),
+    )
+    .await
+    .unwrap();
+    assert_eq!(health.failing_streak, 0);
+    assert_eq!(health.last_probe.unwrap().output, "ok\n");
+}
+
+#[tokio::test]
+async fn query_health_status_of_container_without_healthcheck() {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"))
+        .start()
+        .await
+        .unwrap();
+
+    let health = container.health().await;
+    assert_eq!(health.state, HealthState::None);
+    assert_eq!(health.failing_streak, 0);
+    assert_eq!(health.last_probe, None);
+}
+
+#[tokio::test]
+async fn subscribe_to_container_events() {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"))
+        .start()
+        .await
+        .unwrap();
+
+    let events = container.events();
+    container.kill(Signal::Kill).await;
+
+    let died = events
+        .map(|event| event.unwrap().kind)
+        .filter(|kind| futures::future::ready(matches!(kind, ContainerEventKind::Die { .. })))
+        .boxed()
+        .next();
+    let died = tokio::time::timeout(Duration::from_secs(10), died)
+        .await
+        .unwrap();
+    assert_eq!(
+        died,
+        Some(ContainerEventKind::Die {
+            exit_code: Some(137)
+        })
+    );
+}
+
+#[tokio::test]
+async fn container_events_report_health_exec_and_kill() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("curlimages/curl", "latest").with_entrypoint("sh");
+    let container = RunnableImage::from(image)
+        .with_cmd(["-c", "sleep 60"])
+        .with_label("com.example.suite", "events")
+        .with_healthcheck(
+            Healthcheck::cmd_shell("cat /tmp/healthy").with_interval(Duration::from_millis(200)),
+        )
+        .start()
+        .await
+        .unwrap();
+
+    let subscribed_at = std::time::SystemTime::now() - Duration::from_secs(1);
+    let mut events = container.events().boxed();
+    container
+        .exec(ExecCommand::new(vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "echo ok > /tmp/healthy".to_string(),
+        ]))
+        .await;
+
+    // kill the container once it is healthy and collect the events until it dies
+    let mut kinds = vec![];
+    let died = tokio::time::timeout(Duration::from_secs(30), async {
+        loop {
+            let event = events.next().await.unwrap().unwrap();
+            match &event.kind {
+                ContainerEventKind::HealthStatus(HealthState::Healthy) => {
+                    container.kill(Signal::Kill).await
+                }
+                ContainerEventKind::Die { .. } => break event,
+                _ => {}
+            }
+            kinds.push(event.kind);
+        }
+    })
+    .await
+    .unwrap();
+
+    assert!(kinds.iter().any(
+        |kind| matches!(kind, ContainerEventKind::Other(action) if action.starts_with("exec_start"))
+    ));
+    assert!(kinds.contains(&ContainerEventKind::Kill {
+        signal: Some("9".to_string())
+    }));
+    assert_eq!(
+        died.kind,
+        ContainerEventKind::Die {
+            exit_code: Some(137)
+        }
+    );
+    assert_eq!(died.attributes["com.example.suite"], "events");
+    assert!(died.time >= subscribed_at);
+}
+
+#[tokio::test]
+async fn list_filesystem_changes_of_container() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("curlimages/curl", "latest")
+        .with_entrypoint("sh")
+        .with_wait_for(WaitFor::file_exists("/tmp/written"));
+    let container = RunnableImage::from(image)
+        .with_cmd(["-c", "echo data > /tmp/written && sleep 60"])
+        .start()
+        .await
+        .unwrap();
+
+    let changes = container.changes().await;
+    assert!(changes.contains(&FileChange {
+        path: "/tmp/written".to_string(),
+        kind: FileChangeKind::Added,
+    }));
+}
+
+#[tokio::test]
+async fn list_deleted_and_modified_files_of_container() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("curlimages/curl", "latest")
+        .with_entrypoint("sh")
+        .with_wait_for(WaitFor::message_on_stdout("deleted"));
+    let container = RunnableImage::from(image)
+        .with_user("root")
+        .with_cmd(["-c", "rm /etc/motd && echo deleted && sleep 60"])
+        .start()
+        .await
+        .unwrap();
+
+    let changes = container.changes().await;
+    assert!(changes.contains(&FileChange {
+        path: "/etc/motd".to_string(),
+        kind: FileChangeKind::Deleted,
+    }));
+    assert!(changes.contains(&FileChange {
+        path: "/etc".to_string(),
+        kind: FileChangeKind::Modified,
+    }));
+}
+
+#[tokio::test]
+async fn export_filesystem_of_container() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("curlimages/curl", "latest")
+        .with_entrypoint("sh")
+        .with_wait_for(WaitFor::file_exists("/tmp/evidence"));
+    let container = RunnableImage::from(image)
+        .with_cmd(["-c", "echo failure > /tmp/evidence && sleep 60"])
+        .start()
+        .await
+        .unwrap();
+
+    let mut archive = Vec::new();
+    container.export(&mut archive).await.unwrap();
+
+    let mut entries = tar::Archive::new(archive.as_slice());
+    let found = entries
+        .entries()
+        .unwrap()
+        .any(|entry| entry.unwrap().path().unwrap().ends_with("tmp/evidence"));
+    assert!(found);
+}
+
+#[tokio::test]
+async fn update_resource_limits_of_running_container() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"));
+    let container = RunnableImage::from(image)
+        .with_memory_limit(256 * 1024 * 1024)
+        .start()
+        .await
+        .unwrap();
+
+    container
+        .update_limits(
+            ResourceLimits::new()
+                .with_memory_limit(64 * 1024 * 1024)
+                .with_memory_swap(64 * 1024 * 1024),
+        )
+        .await;
+
+    assert_eq!(container.stats().await.memory_limit, 64 * 1024 * 1024);
+}
+
+#[tokio::test]
+async fn list_processes_of_container() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("curlimages/curl", "latest")
+        .with_entrypoint("sh")
+        .with_wait_for(WaitFor::message_on_stdout("workers started"));
+    let container = RunnableImage::from(image)
+        .with_cmd(["-c", "sleep 61 & sleep 62 & echo workers started && wait"])
+        .start()
+        .await
+        .unwrap();
+
+    let processes = container.top().await;
+    assert_eq!(processes.len(), 3);
+    let commands = processes.column("CMD").unwrap();
+    assert!(commands.contains(&"sleep 61"));
+    assert!(commands.contains(&"sleep 62"));
+
+    let processes = container.top_with_ps_args("-o pid,stat").await;
+    assert_eq!(processes.titles, vec!["PID", "STAT"]);
+}
+
+#[tokio::test]
+async fn detect_zombie_processes_of_container() {
+    let _ = pretty_env_logger::try_init();
+
+    // the sleep that replaces the shell never reaps the child it inherited
+    let image = GenericImage::new("curlimages/curl", "latest").with_entrypoint("sh");
+    let container = RunnableImage::from(image)
+        .with_cmd(["-c", "sleep 0 & exec sleep 60"])
+        .start()
+        .await
+        .unwrap();
+
+    let processes = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let processes = container.top_with_ps_args("-o pid,stat,comm").await;
+            let states = processes.column("STAT").unwrap();
+            if states.iter().any(|state| state.starts_with('Z')) {
+                break processes;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(processes.len(), 2, "{processes:?}");
+    assert!(processes.column("COMMAND").unwrap().contains(&"sleep"));
+    assert_eq!(processes.column("UID"), None);
+}
+
+#[tokio::test]
+async fn pull_all_images_with_progress() {
+    let _ = pretty_env_logger::try_init();
+
+    let progress = std::sync::Mutex::new(Vec::new());
+    images::pull_all_with_progress(&["hello-world", "alpine:3.19"], |update| {
+        progress.lock().unwrap().push(update.clone());
+    })
+    .await
+    .unwrap();
+
+    let progress = progress.into_inner().unwrap();
+    for image in ["hello-world", "alpine:3.19"] {
+        assert!(progress
+            .iter()
+            .any(|update| update.image == image && update.status.starts_with("Status:")));
+    }
+
+    let docker = Docker::connect_with_unix_defaults().unwrap();
+    assert!(docker.inspect_image("alpine:3.19").await.is_ok());
+}
+
+#[tokio::test]
+async fn pull_all_returns_error_of_failed_pull() {
+    let err = images::pull_all(&["hello-world", "testcontainers/does-not-exist:1.0"])
+        .await
+        .unwrap_err();
+
+    let TestcontainersError::PullFailed { image, .. } = &err else {
+        panic!("unexpected error: {err:?}");
+    };
+    assert_eq!(image, "testcontainers/does-not-exist:1.0");
+}
+
+#[tokio::test]
+async fn exited_returns_exit_code_and_logs_of_crashed_container() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("curlimages/curl", "latest")
+        .with_entrypoint("sh")
+        .with_wait_for(WaitFor::message_on_stdout("started"));
+    let container = RunnableImage::from(image)
+        .with_cmd(["-c", "echo started; sleep 1; echo crashing >&2; exit 3"])
+        .start()
+        .await
+        .unwrap();
+
+    let died = container.exited().await;
+    assert_eq!(died.container_id, container.id());
+    assert_eq!(died.exit_code, 3);
+    assert_eq!(died.stdout_tail, "started");
+    assert_eq!(died.stderr_tail, "crashing");
+}
+
+#[tokio::test]
+#[should_panic(expected = "exited unexpectedly with code 3")]
+async fn guard_panics_when_container_exits() {
+    let image = GenericImage::new("curlimages/curl", "latest")
+        .with_entrypoint("sh")
+        .with_wait_for(WaitFor::message_on_stdout("started"));
+    let container = RunnableImage::from(image)
+        .with_cmd(["-c", "echo started; sleep 1; exit 3"])
+        .start()
+        .await
+        .unwrap();
+
+    container
+        .guard(tokio::time::sleep(Duration::from_secs(30)))
+        .await;
+}
+
+#[tokio::test]
+async fn guard_returns_output_of_future() {
+    let container = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"))
+        .start()
+        .await
+        .unwrap();
+
+    assert_eq!(container.guard(async { 42 }).await, 42);
 }
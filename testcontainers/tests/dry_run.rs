@@ -0,0 +1,24 @@
+use testcontainers::{core::TestcontainersError, runners::AsyncRunner, GenericImage};
+
+// the environment variables apply to all tests of the binary, so they are tested in a binary
+// of its own
+#[tokio::test]
+async fn dry_run_plans_container_without_docker_daemon() {
+    let _ = pretty_env_logger::try_init();
+    std::env::set_var("TESTCONTAINERS_DRY_RUN", "1");
+    // nothing listens there, so any call to the daemon would fail
+    std::env::set_var("DOCKER_HOST", "tcp://127.0.0.1:1");
+
+    let err = GenericImage::new("redis", "7.2.4")
+        .with_env_var("REDIS_ARGS", "--save 60 1")
+        .start()
+        .await
+        .unwrap_err();
+
+    let TestcontainersError::DryRun(plan) = err else {
+        panic!("expected a dry run, got: {err}");
+    };
+    assert!(plan
+        .docker_run_command()
+        .contains("--env 'REDIS_ARGS=--save 60 1'"));
+}
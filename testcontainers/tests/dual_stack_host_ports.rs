@@ -15,7 +15,7 @@ fn test_ipv4_ipv6_host_ports() {
 
     // Run one container, and check what ephemeral ports it uses. Perform test HTTP requests to
     // both bound ports.
-    let first_container = image.clone().start();
+    let first_container = image.clone().start().unwrap();
     let first_ipv4_port = first_container.get_host_port_ipv4(80);
     let first_ipv6_port = first_container.get_host_port_ipv6(80);
     assert_eq!(
@@ -46,7 +46,7 @@ fn test_ipv4_ipv6_host_ports() {
     // Run a second container, and repeat test HTTP requests with it. This confirms that handling
     // of both IPv4 and IPv6 host port bindings is correct, because at this point,
     // `second_ipv4_port` and `second_ipv6_port` are very unlikely to be the same.
-    let second_container = image.start();
+    let second_container = image.start().unwrap();
     let second_ipv4_port = second_container.get_host_port_ipv4(80);
     let second_ipv6_port = second_container.get_host_port_ipv6(80);
     assert_eq!(
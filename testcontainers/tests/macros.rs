@@ -0,0 +1,46 @@
+#![cfg(feature = "macros")]
+
+use testcontainers::{core::WaitFor, ContainerAsync, GenericImage, Image};
+
+#[derive(Debug, Default)]
+pub struct HelloWorld;
+
+impl Image for HelloWorld {
+    type Args = ();
+
+    fn name(&self) -> String {
+        "hello-world".to_owned()
+    }
+
+    fn tag(&self) -> String {
+        "latest".to_owned()
+    }
+
+    fn ready_conditions(&self) -> Vec<WaitFor> {
+        vec![WaitFor::message_on_stdout("Hello from Docker!")]
+    }
+}
+
+#[testcontainers::test]
+async fn containers_are_injected(
+    hello_world: ContainerAsync<HelloWorld>,
+    #[image(GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready")))]
+    web_server: ContainerAsync<GenericImage>,
+) {
+    let _ = pretty_env_logger::try_init();
+
+    assert_ne!(hello_world.id(), web_server.id());
+    assert!(web_server.inspect().await.state.running);
+}
+
+#[testcontainers::test(flavor = "multi_thread")]
+async fn arguments_are_passed_on(hello_world: ContainerAsync<HelloWorld>) {
+    assert!(!hello_world.id().is_empty());
+}
+
+#[cfg(feature = "blocking")]
+#[testcontainers::test]
+fn containers_are_injected_into_sync_tests(hello_world: testcontainers::Container<HelloWorld>) {
+    assert!(!hello_world.id().is_empty());
+}
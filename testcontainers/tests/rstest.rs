@@ -0,0 +1,62 @@
+#![cfg(feature = "rstest_fixtures")]
+
+use rstest::{fixture, rstest};
+use testcontainers::{core::WaitFor, runners::AsyncRunner, ContainerAsync, GenericImage};
+
+fn simple_web_server() -> GenericImage {
+    GenericImage::new("simple_web_server", "latest")
+        .with_exposed_port(80)
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"))
+}
+
+#[fixture]
+#[once]
+fn shared_web_server() -> ContainerAsync<GenericImage> {
+    let _ = pretty_env_logger::try_init();
+    testcontainers::rstest::start_once(simple_web_server())
+}
+
+#[fixture]
+async fn web_server() -> ContainerAsync<GenericImage> {
+    simple_web_server().start().await.unwrap()
+}
+
+#[rstest]
+#[tokio::test]
+async fn once_fixture_is_shared(shared_web_server: &ContainerAsync<GenericImage>) {
+    assert!(shared_web_server.inspect().await.state.running);
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn once_fixture_is_shared_with_other_runtimes(
+    shared_web_server: &ContainerAsync<GenericImage>,
+) {
+    let port = shared_web_server.get_host_port_ipv4(80).await;
+    let response = reqwest::get(format!("http://127.0.0.1:{port}"))
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+}
+
+#[rstest]
+#[tokio::test]
+async fn async_fixture_is_started_per_test(
+    #[future(awt)] web_server: ContainerAsync<GenericImage>,
+    shared_web_server: &ContainerAsync<GenericImage>,
+) {
+    assert_ne!(web_server.id(), shared_web_server.id());
+}
+
+#[cfg(feature = "blocking")]
+#[fixture]
+#[once]
+fn blocking_web_server() -> testcontainers::Container<GenericImage> {
+    testcontainers::rstest::start_once_blocking(simple_web_server())
+}
+
+#[cfg(feature = "blocking")]
+#[rstest]
+fn once_fixture_of_sync_tests(blocking_web_server: &testcontainers::Container<GenericImage>) {
+    assert!(blocking_web_server.get_host_port_ipv4(80) > 0);
+}
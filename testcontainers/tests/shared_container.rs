@@ -0,0 +1,56 @@
+use std::{process::Command, time::Duration};
+
+use bollard::Docker;
+use testcontainers::{core::WaitFor, GenericImage, SharedContainer};
+
+static WEB_SERVER: SharedContainer<GenericImage> = SharedContainer::new();
+
+/// Starts the shared container in a process of its own, see
+/// `shared_container_is_removed_once_the_process_exits`.
+#[tokio::test]
+async fn start_shared_container_in_child_process() {
+    if std::env::var_os("TC_SHARED_CONTAINER_CHILD").is_none() {
+        return;
+    }
+
+    let container = WEB_SERVER
+        .get_or_start(|| {
+            GenericImage::new("simple_web_server", "latest")
+                .with_wait_for(WaitFor::message_on_stdout("server is ready"))
+        })
+        .await
+        .unwrap();
+    println!("shared container id: {}", container.id());
+}
+
+#[tokio::test]
+async fn shared_container_is_removed_once_the_process_exits() {
+    let _ = pretty_env_logger::try_init();
+
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args([
+            "--exact",
+            "start_shared_container_in_child_process",
+            "--nocapture",
+        ])
+        .env("TC_SHARED_CONTAINER_CHILD", "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let id = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("shared container id: "))
+        .unwrap()
+        .to_string();
+
+    // the resource reaper waits a few seconds for the process to reconnect before removing it
+    let docker = Docker::connect_with_unix_defaults().unwrap();
+    let removed = tokio::time::timeout(Duration::from_secs(60), async {
+        while docker.inspect_container(&id, None).await.is_ok() {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+    })
+    .await;
+    assert!(removed.is_ok(), "shared container {id} is still running");
+}
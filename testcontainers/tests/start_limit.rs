@@ -0,0 +1,42 @@
+use testcontainers::{core::WaitFor, runners::AsyncRunner, GenericImage};
+
+/// Parses a timestamp of docker in RFC 3339 format with up to nine fractional digits into a
+/// value that orders like the timestamps, as docker trims trailing zeros of the fraction.
+fn sortable(timestamp: &str) -> (String, u32) {
+    let timestamp = timestamp.trim_end_matches('Z');
+    let (seconds, fraction) = timestamp.split_once('.').unwrap_or((timestamp, ""));
+    let nanos = format!("{fraction:0<9}").parse().unwrap();
+    (seconds.to_string(), nanos)
+}
+
+// the limit applies to all tests of the binary, so it is tested in a binary of its own
+#[tokio::test(flavor = "multi_thread")]
+async fn containers_start_within_limit() {
+    let _ = pretty_env_logger::try_init();
+    testcontainers::config::set_max_concurrent_starts(1);
+    assert_eq!(testcontainers::config::max_concurrent_starts(), Some(1));
+
+    let image = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"));
+    let (first, second, third) =
+        tokio::join!(image.clone().start(), image.clone().start(), image.start());
+
+    // with a limit of 1, each container is created only once the previous one was started
+    let mut windows = Vec::new();
+    for container in [first.unwrap(), second.unwrap(), third.unwrap()] {
+        let info = container.inspect().await;
+        assert!(info.state.running);
+        windows.push((
+            sortable(&info.created),
+            sortable(&info.state.started_at.unwrap()),
+        ));
+    }
+    windows.sort();
+    for pair in windows.windows(2) {
+        let ((_, started), (next_created, _)) = (&pair[0], &pair[1]);
+        assert!(
+            started < next_created,
+            "the windows of creating and starting containers overlap: {windows:?}"
+        );
+    }
+}
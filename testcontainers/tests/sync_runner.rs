@@ -1,8 +1,9 @@
 #![cfg(feature = "blocking")]
 
+use std::time::Duration;
 use testcontainers::{
-    core::{Host, WaitFor},
-    runners::SyncRunner,
+    core::{ExecCommand, Host, WaitFor},
+    runners::{self, SyncRunner},
     *,
 };
 
@@ -33,14 +34,14 @@ impl Image for HelloWorld {
 #[test]
 fn sync_can_run_hello_world() {
     let _ = pretty_env_logger::try_init();
-    let _container = HelloWorld.start();
+    let _container = HelloWorld.start().unwrap();
 }
 
 #[test]
 fn generic_image_with_custom_entrypoint() {
     let generic = get_server_container(None);
 
-    let node = generic.start();
+    let node = generic.start().unwrap();
     let port = node.get_host_port_ipv4(80);
     assert_eq!(
         "foo",
@@ -52,7 +53,7 @@ fn generic_image_with_custom_entrypoint() {
 
     let generic = get_server_container(None).with_entrypoint("./bar");
 
-    let node = generic.start();
+    let node = generic.start().unwrap();
     let port = node.get_host_port_ipv4(80);
     assert_eq!(
         "bar",
@@ -75,7 +76,7 @@ fn generic_image_exposed_ports() {
         // Explicitly expose the port, which otherwise would not be available.
         .with_exposed_port(target_port);
 
-    let node = generic_server.start();
+    let node = generic_server.start().unwrap();
     let port = node.get_host_port_ipv4(target_port);
     assert!(reqwest::blocking::get(format!("http://127.0.0.1:{port}"))
         .unwrap()
@@ -86,7 +87,7 @@ fn generic_image_exposed_ports() {
 #[test]
 fn generic_image_running_with_extra_hosts_added() {
     let server_1 = get_server_container(None);
-    let node = server_1.start();
+    let node = server_1.start().unwrap();
     let port = node.get_host_port_ipv4(80);
 
     let msg = WaitFor::message_on_stdout("foo");
@@ -99,7 +100,7 @@ fn generic_image_running_with_extra_hosts_added() {
     let server_2 = RunnableImage::from((server_2, vec![format!("http://custom-host:{port}")]))
         .with_host("custom-host", Host::HostGateway);
 
-    server_2.start();
+    server_2.start().unwrap();
 }
 
 #[test]
@@ -112,7 +113,7 @@ fn generic_image_port_not_exposed() {
     // This image binds to 0.0.0.0:8080, does not EXPOSE ports in its dockerfile.
     let generic_server = GenericImage::new("no_expose_port", "latest")
         .with_wait_for(WaitFor::message_on_stdout("listening on 0.0.0.0:8080"));
-    let node = generic_server.start();
+    let node = generic_server.start().unwrap();
 
     // Without exposing the port with `with_exposed_port()`, we cannot get a mapping to it.
     node.get_host_port_ipv4(target_port);
@@ -124,7 +125,116 @@ fn start_multiple_containers() {
 
     let image = GenericImage::new("hello-world", "latest").with_wait_for(WaitFor::seconds(2));
 
-    let _container_1 = image.clone().start();
-    let _container_2 = image.clone().start();
-    let _container_3 = image.start();
+    let _container_1 = image.clone().start().unwrap();
+    let _container_2 = image.clone().start().unwrap();
+    let _container_3 = image.start().unwrap();
+}
+
+#[test]
+fn sync_exec_returns_output_and_exit_code() {
+    let _ = pretty_env_logger::try_init();
+
+    let container = get_server_container(None).start().unwrap();
+
+    let result = container.exec_to_completion(ExecCommand::new(vec![
+        "sh".to_string(),
+        "-c".to_string(),
+        "echo out && echo err >&2 && exit 3".to_string(),
+    ]));
+
+    assert_eq!(result.exit_code, Some(3));
+    assert_eq!(result.stdout, b"out\n");
+    assert_eq!(result.stderr, b"err\n");
+}
+
+#[test]
+fn sync_iterate_log_lines() {
+    let _ = pretty_env_logger::try_init();
+
+    let container = get_server_container(None).start().unwrap();
+
+    let line = container.stdout_lines().next().unwrap().unwrap();
+    assert_eq!(line.message, "server is ready");
+}
+
+#[test]
+fn sync_stop_start_and_restart_container() {
+    let _ = pretty_env_logger::try_init();
+
+    let container = get_server_container(None).start().unwrap();
+
+    container.stop_with_timeout(Duration::from_secs(1));
+    container.start();
+    container.restart();
+
+    let result = container.exec_to_completion(ExecCommand::new(vec!["true".to_string()]));
+    assert_eq!(result.exit_code, Some(0));
+}
+
+#[test]
+fn sync_wait_for_container_to_exit() {
+    let _ = pretty_env_logger::try_init();
+
+    let image = GenericImage::new("curlimages/curl", "latest").with_entrypoint("sh");
+    let container = RunnableImage::from(image)
+        .with_cmd(["-c", "sleep 1"])
+        .start()
+        .unwrap();
+
+    let status = container.wait_with_timeout(Duration::from_secs(30));
+    assert!(status.success());
+}
+
+#[test]
+fn sync_inspect_running_container() {
+    let _ = pretty_env_logger::try_init();
+
+    let container = get_server_container(None).start().unwrap();
+
+    let info = container.inspect();
+    assert_eq!(info.id, container.id());
+    assert!(info.state.running);
+}
+
+#[test]
+#[should_panic(expected = "is not connected to network")]
+fn sync_get_network_ip_panics_for_unknown_network() {
+    let _ = pretty_env_logger::try_init();
+
+    let container = get_server_container(None).start().unwrap();
+
+    container.get_network_ip("tc-unknown-network");
+}
+
+#[test]
+fn sync_exec_interactive_writes_stdin() {
+    let _ = pretty_env_logger::try_init();
+
+    let container = get_server_container(None).start().unwrap();
+
+    let mut session = container.exec_interactive(ExecCommand::new(vec![
+        "sh".to_string(),
+        "-c".to_string(),
+        "read line && echo \"got $line\"".to_string(),
+    ]));
+    session.write_stdin("input\n").unwrap();
+
+    let result = session.wait();
+    assert_eq!(result.exit_code, Some(0));
+    assert_eq!(result.stdout, b"got input\n");
+}
+
+#[test]
+fn sync_start_all_containers() {
+    let _ = pretty_env_logger::try_init();
+
+    let containers =
+        runners::start_all_blocking(vec![get_server_container(None), get_server_container(None)])
+            .unwrap();
+
+    assert_eq!(containers.len(), 2);
+    assert_ne!(containers[0].id(), containers[1].id());
+    for container in &containers {
+        assert!(container.inspect().state.running);
+    }
 }